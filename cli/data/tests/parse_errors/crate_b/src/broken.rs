@@ -0,0 +1,2 @@
+#[typeshare]
+pub struct Broken {
@@ -0,0 +1,4 @@
+#[typeshare]
+pub struct Team {
+    size: u64,
+}
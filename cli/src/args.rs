@@ -8,12 +8,29 @@ pub enum AvailableLanguage {
     Scala,
     Swift,
     Typescript,
+    JsonSchema,
+    Openapi,
+    Proto,
+    Java,
+    Csharp,
+    Dart,
+    Graphql,
+    Avro,
     #[cfg(feature = "go")]
     Go,
     #[cfg(feature = "python")]
     Python,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MessageFormat {
+    /// Human readable messages
+    #[default]
+    Human,
+    /// One JSON object per line on stdout
+    Json,
+}
+
 #[derive(clap::Parser)]
 #[command(
     version,
@@ -73,18 +90,69 @@ pub struct Args {
     #[arg(required=true, num_args = 1..)]
     pub directories: Vec<PathBuf>,
 
-    /// Optional restrict to target_os
+    /// Optional restrict to target_os. Shorthand for `--cfg target_os=<target>`
     #[arg(short, long, num_args = 1..)]
     pub target_os: Option<Vec<String>>,
+
+    /// Active cfg options used to filter types, fields and variants, e.g. `feature=pro`
+    #[arg(long, num_args = 1..)]
+    pub cfg: Option<Vec<String>>,
+
+    /// Treat unknown or misplaced typeshare attributes as errors instead of warnings
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Stop at the first file that fails to parse instead of reporting every error
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Format of diagnostic messages
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
 }
 
-#[derive(Debug, Clone, Copy, clap::Subcommand)]
+impl Args {
+    /// The format of diagnostic messages for the command being run, since `diff` has a
+    /// `--message-format` of its own.
+    pub fn effective_message_format(&self) -> MessageFormat {
+        match &self.subcommand {
+            Some(Command::Diff { message_format, .. }) => *message_format,
+            _ => self.message_format,
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
 pub enum Command {
     /// Generate shell completions
     Completions {
         /// The shell to generate the completions for
         shell: clap_complete::Shell,
     },
+    /// Compare the typeshared types of two source trees and report changes that break
+    /// clients built against the base version. Exits with an error on breaking changes.
+    Diff {
+        /// Directory with the base version of the sources, e.g. a checkout of the last release,
+        /// or a git ref, such as a tag, of the repository containing `--head`
+        #[arg(long)]
+        base: String,
+
+        /// Directory with the new version of the sources
+        #[arg(long)]
+        head: PathBuf,
+
+        /// Active cfg options used to filter types, fields and variants, e.g. `feature=pro`
+        #[arg(long, num_args = 1..)]
+        cfg: Option<Vec<String>>,
+
+        /// Follow symbolic links to directories instead of ignoring them.
+        #[arg(short = 'L', long)]
+        follow_links: bool,
+
+        /// Format of the report
+        #[arg(long, value_enum, default_value_t)]
+        message_format: MessageFormat,
+    },
 }
 
 #[derive(clap::Args, Debug)]
@@ -27,6 +27,17 @@ pub struct KotlinParams {
     pub type_mappings: HashMap<String, String>,
 }
 
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct JavaParams {
+    pub package: String,
+    /// The class the generated types are nested in, named after the output file.
+    pub class_name: String,
+    /// The fully qualified `@Nullable` annotation to use.
+    pub nullable_annotation: String,
+    pub type_mappings: HashMap<String, String>,
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
 #[serde(default)]
 pub struct ScalaParams {
@@ -50,6 +61,60 @@ pub struct SwiftParams {
 #[serde(default)]
 pub struct TypeScriptParams {
     pub type_mappings: HashMap<String, String>,
+    /// Generate zod schemas, and the types inferred from them.
+    pub zod: bool,
+    /// The zod schemas of the types in `type_mappings`.
+    pub zod_type_mappings: HashMap<String, String>,
+    /// Generate `isFoo` type guards and `assertFoo` assertion functions.
+    pub type_guards: bool,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct JsonSchemaParams {
+    pub type_mappings: HashMap<String, String>,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct OpenApiParams {
+    pub type_mappings: HashMap<String, String>,
+    /// Write JSON instead of YAML.
+    pub json: bool,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct ProtoParams {
+    pub package: String,
+    pub strict_tags: bool,
+    pub type_mappings: HashMap<String, String>,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct CSharpParams {
+    pub namespace: String,
+    pub type_mappings: HashMap<String, String>,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct DartParams {
+    pub type_mappings: HashMap<String, String>,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct GraphQLParams {
+    pub type_mappings: HashMap<String, String>,
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct AvroParams {
+    pub namespace: String,
+    pub type_mappings: HashMap<String, String>,
 }
 
 #[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
@@ -60,6 +125,7 @@ pub struct GoParams {
     pub uppercase_acronyms: Vec<String>,
     pub no_pointer_slice: bool,
     pub type_mappings: HashMap<String, String>,
+    pub tag_template: Option<String>,
 }
 
 /// The parameters that are used to configure the behaviour of typeshare
@@ -70,11 +136,21 @@ pub(crate) struct Config {
     pub swift: SwiftParams,
     pub typescript: TypeScriptParams,
     pub kotlin: KotlinParams,
+    pub java: JavaParams,
     pub scala: ScalaParams,
+    pub json_schema: JsonSchemaParams,
+    pub openapi: OpenApiParams,
+    pub proto: ProtoParams,
+    pub csharp: CSharpParams,
+    pub dart: DartParams,
+    pub graphql: GraphQLParams,
+    pub avro: AvroParams,
     #[cfg(feature = "python")]
     pub python: PythonParams,
     #[cfg(feature = "go")]
     pub go: GoParams,
+    /// Active cfg options like `feature=pro`.
+    pub cfg: Vec<String>,
     #[serde(skip)]
     pub target_os: Vec<String>,
 }
@@ -167,8 +243,27 @@ mod test {
 
         assert_eq!(config.swift.type_mappings["DateTime"], "Date");
         assert_eq!(config.kotlin.type_mappings["DateTime"], "String");
+        assert_eq!(config.java.type_mappings["DateTime"], "String");
+        assert_eq!(config.java.class_name, "Accounts");
+        assert_eq!(config.csharp.namespace, "Example.Accounts");
+        assert_eq!(config.csharp.type_mappings["DateTime"], "string");
+        assert_eq!(config.dart.type_mappings["DateTime"], "String");
+        assert_eq!(config.graphql.type_mappings["DateTime"], "DateTime");
+        assert_eq!(config.avro.namespace, "com.example.accounts");
+        assert_eq!(config.avro.type_mappings["DateTime"], "string");
         assert_eq!(config.scala.type_mappings["DateTime"], "String");
         assert_eq!(config.typescript.type_mappings["DateTime"], "string");
+        assert!(config.typescript.type_guards);
+        assert_eq!(
+            config.typescript.zod_type_mappings["DateTime"],
+            "z.string().datetime()"
+        );
+        assert_eq!(config.json_schema.type_mappings["DateTime"], "string");
+        assert_eq!(config.openapi.type_mappings["DateTime"], "string");
+        assert!(config.openapi.json);
+        assert_eq!(config.proto.type_mappings["DateTime"], "string");
+        assert_eq!(config.proto.package, "accounts.v1");
+        assert!(config.proto.strict_tags);
         #[cfg(feature = "python")]
         {
             assert_eq!(config.python.type_mappings["Url"], "AnyUrl");
@@ -196,6 +291,14 @@ mod test {
         assert_eq!(config.swift.default_generic_constraints[0], "Sendable");
     }
 
+    #[test]
+    fn cfg_test() {
+        let path = config_file_path("cfg_config.toml");
+        let config = load_config(Some(&path)).unwrap();
+
+        assert_eq!(config.cfg, ["feature=pro", "target_family=unix"]);
+    }
+
     #[test]
     fn swift_prefix_test() {
         let path = config_file_path("swift_prefix_config.toml");
@@ -211,4 +314,16 @@ mod test {
 
         assert_eq!(config.go.package, "testPackage");
     }
+
+    #[test]
+    #[cfg(feature = "go")]
+    fn go_tag_template_test() {
+        let path = config_file_path("go_config.toml");
+        let config = load_config(Some(&path)).unwrap();
+
+        assert_eq!(
+            config.go.tag_template.as_deref(),
+            Some(r#"db:"{field}" yaml:"{name}""#)
+        );
+    }
 }
@@ -0,0 +1,168 @@
+//! Machine readable diagnostics, emitted as one JSON object per line on stdout when
+//! running with `--message-format json`.
+use flexi_logger::DeferredNow;
+use log::{Level, Record};
+use serde::Serialize;
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+use typeshare_core::{
+    compat::Change,
+    parser::{ErrorInfo, ParseError, SourceLocation},
+};
+
+/// A single diagnostic message.
+#[derive(Debug, Serialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+pub enum Message<'a> {
+    /// A typeshared item failed to parse.
+    ParseError {
+        /// The `ParseError` variant name.
+        kind: &'static str,
+        message: String,
+        item: Option<&'a str>,
+        path: &'a str,
+        span: Option<Span<'a>>,
+        /// Another definition involved in the problem, like the first of duplicate types.
+        related: Option<Related<'a>>,
+    },
+    /// A problem with a typeshared item that doesn't stop generation.
+    ParseWarning {
+        /// The `ParseError` variant name.
+        kind: &'static str,
+        message: String,
+        item: Option<&'a str>,
+        path: &'a str,
+        span: Option<Span<'a>>,
+        /// Another definition involved in the problem, like the first of duplicate types.
+        related: Option<Related<'a>>,
+    },
+    /// A warning or error logged while running.
+    Warning {
+        message: String,
+    },
+    Error {
+        message: String,
+    },
+    /// Any other log output, only shown when enabled with `RUST_LOG`.
+    Log {
+        level: &'static str,
+        message: String,
+    },
+    /// A file that was generated.
+    GeneratedFile {
+        path: &'a Path,
+        fresh: bool,
+    },
+    /// A change between two versions of the typeshared types, reported by `typeshare diff`.
+    WireChange {
+        /// The `ChangeKind` variant name.
+        kind: &'static str,
+        breaking: bool,
+        message: String,
+        item: &'a str,
+    },
+}
+
+/// The location of the source that triggered a diagnostic.
+#[derive(Debug, Serialize)]
+pub struct Span<'a> {
+    line: usize,
+    column: usize,
+    end_column: usize,
+    text: &'a str,
+}
+
+/// Another location in the source relevant to a diagnostic.
+#[derive(Debug, Serialize)]
+pub struct Related<'a> {
+    message: &'static str,
+    path: &'a str,
+    span: Span<'a>,
+}
+
+impl<'a> Related<'a> {
+    fn from_error(error: &'a ParseError) -> Option<Self> {
+        match error {
+            ParseError::DuplicateTypeName { first_defined, .. } => Some(Self {
+                message: "first defined here",
+                path: &first_defined.file_name,
+                span: Span::from(&first_defined.location),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> From<&'a SourceLocation> for Span<'a> {
+    fn from(location: &'a SourceLocation) -> Self {
+        Self {
+            line: location.line,
+            column: location.column,
+            end_column: location.end_column,
+            text: &location.source_line,
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    pub fn parse_error(error: &'a ErrorInfo) -> Self {
+        Message::ParseError {
+            kind: error.error.kind(),
+            message: error.error.to_string(),
+            item: error.item_name.as_deref(),
+            path: &error.file_name,
+            span: error.location.as_ref().map(Span::from),
+            related: Related::from_error(&error.error),
+        }
+    }
+
+    pub fn parse_warning(warning: &'a ErrorInfo) -> Self {
+        Message::ParseWarning {
+            kind: warning.error.kind(),
+            message: warning.error.to_string(),
+            item: warning.item_name.as_deref(),
+            path: &warning.file_name,
+            span: warning.location.as_ref().map(Span::from),
+            related: Related::from_error(&warning.error),
+        }
+    }
+
+    pub fn wire_change(change: &'a Change) -> Self {
+        Message::WireChange {
+            kind: change.kind.kind(),
+            breaking: change.kind.is_breaking(),
+            message: change.kind.to_string(),
+            item: &change.item,
+        }
+    }
+
+    /// Write this message as a single JSON line on stdout.
+    pub fn emit(&self) {
+        let mut line = serde_json::to_string(self).expect("diagnostics are always serializable");
+        line.push('\n');
+        // Ignore broken pipes, there is nobody left to report to.
+        let _ = io::stdout().lock().write_all(line.as_bytes());
+    }
+}
+
+/// Log format used with `--message-format json`. Warnings and errors logged by typeshare
+/// become diagnostic messages.
+pub fn json_log_format(
+    w: &mut dyn Write,
+    _now: &mut DeferredNow,
+    record: &Record,
+) -> io::Result<()> {
+    let message = record.args().to_string();
+    let message = match record.level() {
+        Level::Error => Message::Error { message },
+        Level::Warn => Message::Warning { message },
+        level => Message::Log {
+            level: level.as_str(),
+            message,
+        },
+    };
+    let line = serde_json::to_string(&message).map_err(io::Error::other)?;
+    w.write_all(line.as_bytes())
+}
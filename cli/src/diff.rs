@@ -0,0 +1,125 @@
+//! Wire compatibility report between two versions of the typeshared types.
+use crate::{
+    args::MessageFormat, check_parse_errors, diagnostics::Message, parse::parallel_parse,
+    walker_builder,
+};
+use anyhow::{anyhow, Context};
+use log::info;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::TempDir;
+use typeshare_core::{
+    cfg::CfgOptions,
+    compat::diff,
+    context::ParseContext,
+    language::{CrateName, SupportedLanguage},
+    parser::ParsedData,
+    validate::check_duplicate_types,
+};
+
+/// Compare the typeshared types under `base` and `head`, report every change and fail
+/// if any of them breaks clients built against `base`. `base` is either a directory or a
+/// git ref of the repository containing `head`.
+pub fn diff_types(
+    base: &str,
+    head: &Path,
+    cfg: Vec<String>,
+    follow_links: bool,
+    message_format: MessageFormat,
+) -> anyhow::Result<()> {
+    let mut cfg_options = CfgOptions::default();
+    for option in &cfg {
+        cfg_options.insert_str(option);
+    }
+    let parse_context = ParseContext {
+        cfg: cfg_options,
+        ..Default::default()
+    };
+
+    let parse_tree = |dir: &Path| -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
+        info!("typeshare parsing {}", dir.display());
+        // The language only decides output file names, which are not compared.
+        let mut parsed_data = parallel_parse(
+            &parse_context,
+            walker_builder(&[dir.to_path_buf()], follow_links)?,
+            SupportedLanguage::TypeScript,
+            false,
+        );
+        check_duplicate_types(&mut parsed_data);
+        check_parse_errors(&parsed_data, message_format)?;
+        Ok(parsed_data)
+    };
+
+    let base_checkout;
+    let base = if Path::new(base).is_dir() {
+        PathBuf::from(base)
+    } else {
+        let (checkout, path) = checkout_ref(head, base)?;
+        base_checkout = checkout;
+        info!("checked out {base} into {}", base_checkout.path().display());
+        path
+    };
+
+    let changes = diff(&parse_tree(&base)?, &parse_tree(head)?);
+
+    for change in &changes {
+        match message_format {
+            MessageFormat::Human => {
+                let label = if change.kind.is_breaking() {
+                    "breaking"
+                } else {
+                    "compatible"
+                };
+                println!("{label}: {change}");
+            }
+            MessageFormat::Json => Message::wire_change(change).emit(),
+        }
+    }
+
+    let breaking = changes.iter().filter(|c| c.kind.is_breaking()).count();
+    if message_format == MessageFormat::Human {
+        println!("{} changes, {breaking} breaking", changes.len());
+    }
+
+    if breaking > 0 {
+        Err(anyhow!("found {breaking} breaking changes"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Extract `git_ref` of the repository containing `dir` into a temporary directory, which is
+/// removed when dropped. Returns it with the path of `dir` within the checkout.
+fn checkout_ref(dir: &Path, git_ref: &str) -> anyhow::Result<(TempDir, PathBuf)> {
+    let git = |args: &[&str]| -> anyhow::Result<Vec<u8>> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(anyhow!(
+                "`{git_ref}` is neither a directory nor a git ref: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    };
+
+    // Archive only `dir`, but keep its path within the repository so crate names are found
+    // the same way in both trees.
+    let prefix = String::from_utf8(git(&["rev-parse", "--show-prefix"])?)?;
+    let archive = git(&["archive", "--format=tar", git_ref, "--", "."])?;
+
+    let checkout = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let path = checkout.path().join(prefix.trim());
+    tar::Archive::new(archive.as_slice())
+        .unpack(&path)
+        .with_context(|| format!("Failed to extract {git_ref}"))?;
+    Ok((checkout, path))
+}
@@ -4,6 +4,8 @@
 
 mod args;
 mod config;
+mod diagnostics;
+mod diff;
 mod parse;
 mod writer;
 
@@ -18,7 +20,7 @@ use clap::{CommandFactory, Parser};
 use clap_complete::aot::generate;
 use flexi_logger::AdaptiveFormat;
 use ignore::{overrides::OverrideBuilder, types::TypesBuilder, WalkBuilder};
-use log::{error, info};
+use log::{error, info, warn};
 use parse::parallel_parse;
 use typeshare_core::language::GenericConstraints;
 #[cfg(feature = "go")]
@@ -26,27 +28,50 @@ use typeshare_core::language::Go;
 #[cfg(feature = "python")]
 use typeshare_core::language::Python;
 use typeshare_core::{
+    cfg::CfgOptions,
     context::ParseContext,
-    language::{CrateName, Kotlin, Language, Scala, SupportedLanguage, Swift, TypeScript},
-    parser::ParsedData,
-    reconcile::reconcile_aliases,
+    language::{
+        Avro, CSharp, CrateName, Dart, GraphQL, Java, JsonSchema, Kotlin, Language, OpenApi, Proto,
+        Scala, SupportedLanguage, Swift, TypeScript, Zod,
+    },
+    parser::{ErrorInfo, ParsedData},
+    reconcile::{reconcile_aliases, reconcile_foreign_types},
+    validate::{check_duplicate_types, check_language_references},
 };
 
 use crate::{
-    args::{Args, Command},
+    args::{Args, Command, MessageFormat},
     config::Config,
+    diagnostics::{json_log_format, Message},
     parse::all_types,
     writer::{write_generated, Output},
 };
 
 fn main() -> anyhow::Result<()> {
-    flexi_logger::Logger::try_with_env_or_str("info")?
-        .adaptive_format_for_stderr(AdaptiveFormat::Opt)
-        .adaptive_format_for_stdout(AdaptiveFormat::Opt)
-        .start()?;
-
     let options = Args::parse();
+    let message_format = options.effective_message_format();
+
+    match message_format {
+        MessageFormat::Human => flexi_logger::Logger::try_with_env_or_str("info")?
+            .adaptive_format_for_stderr(AdaptiveFormat::Opt)
+            .adaptive_format_for_stdout(AdaptiveFormat::Opt)
+            .start()?,
+        // Keep stdout free of anything but diagnostics.
+        MessageFormat::Json => flexi_logger::Logger::try_with_env_or_str("warn")?
+            .log_to_stdout()
+            .format(json_log_format)
+            .start()?,
+    };
+
+    let result = run(options);
+    if result.is_err() && message_format == MessageFormat::Json {
+        // The error has already been logged as a diagnostic.
+        std::process::exit(1);
+    }
+    result
+}
 
+fn run(options: Args) -> anyhow::Result<()> {
     if let Some(options) = options.subcommand {
         match options {
             Command::Completions { shell } => {
@@ -54,6 +79,22 @@ fn main() -> anyhow::Result<()> {
                 let bin_name = cmd.get_name().to_string();
                 generate(shell, &mut cmd, bin_name, &mut io::stdout());
             }
+            Command::Diff {
+                base,
+                head,
+                cfg,
+                follow_links,
+                message_format,
+            } => {
+                return diff::diff_types(
+                    &base,
+                    &head,
+                    cfg.unwrap_or_default(),
+                    follow_links,
+                    message_format,
+                )
+                .inspect_err(|err| error!("typeshare diff failed: {err}"));
+            }
         }
 
         return Ok(());
@@ -91,6 +132,14 @@ fn generate_types(config_file: Option<&Path>, options: &Args) -> anyhow::Result<
             args::AvailableLanguage::Scala => SupportedLanguage::Scala,
             args::AvailableLanguage::Swift => SupportedLanguage::Swift,
             args::AvailableLanguage::Typescript => SupportedLanguage::TypeScript,
+            args::AvailableLanguage::JsonSchema => SupportedLanguage::JsonSchema,
+            args::AvailableLanguage::Openapi => SupportedLanguage::OpenApi,
+            args::AvailableLanguage::Proto => SupportedLanguage::Proto,
+            args::AvailableLanguage::Java => SupportedLanguage::Java,
+            args::AvailableLanguage::Csharp => SupportedLanguage::CSharp,
+            args::AvailableLanguage::Dart => SupportedLanguage::Dart,
+            args::AvailableLanguage::Graphql => SupportedLanguage::GraphQL,
+            args::AvailableLanguage::Avro => SupportedLanguage::Avro,
             #[cfg(feature = "go")]
             args::AvailableLanguage::Go => SupportedLanguage::Go,
             #[cfg(feature = "python")]
@@ -110,22 +159,29 @@ fn generate_types(config_file: Option<&Path>, options: &Args) -> anyhow::Result<
     };
 
     let multi_file = matches!(destination, Output::Folder(_));
-    let target_os = config.target_os.clone();
+    let cfg = cfg_options(&config);
     let mut lang = language(language_type, config, multi_file);
 
+    let type_mapping_keys = lang.type_map().keys().cloned().collect();
     let parse_context = ParseContext {
         ignored_types: lang.ignored_reference_types(),
         multi_file,
-        target_os,
+        cfg,
+        strict: options.strict,
+        type_mapping_keys,
     };
 
     let mut parsed_data = parallel_parse(
         &parse_context,
-        walker_builder(directories, options)?,
+        walker_builder(directories, options.follow_links)?,
         language_type,
-    )?;
+        options.fail_fast,
+    );
 
     reconcile_aliases(&mut parsed_data);
+    reconcile_foreign_types(&mut parsed_data);
+    check_duplicate_types(&mut parsed_data);
+    check_language_references(&mut parsed_data, language_type);
 
     // Collect all the types into a map of the file name they
     // belong too and the list of type names. Used for generating
@@ -136,11 +192,22 @@ fn generate_types(config_file: Option<&Path>, options: &Args) -> anyhow::Result<
         HashMap::new()
     };
 
-    check_parse_errors(&parsed_data)?;
+    check_parse_errors(&parsed_data, options.message_format)?;
 
     info!("typeshare started writing generated types");
 
-    write_generated(destination, lang.as_mut(), parsed_data, import_candidates)?;
+    let generated_files =
+        write_generated(destination, lang.as_mut(), parsed_data, import_candidates)?;
+
+    if options.message_format == MessageFormat::Json {
+        for file in &generated_files {
+            Message::GeneratedFile {
+                path: &file.path,
+                fresh: file.fresh,
+            }
+            .emit();
+        }
+    }
 
     info!("typeshare finished generating types");
     Ok(())
@@ -148,7 +215,7 @@ fn generate_types(config_file: Option<&Path>, options: &Args) -> anyhow::Result<
 
 fn walker_builder(
     directories: &[std::path::PathBuf],
-    options: &Args,
+    follow_links: bool,
 ) -> anyhow::Result<WalkBuilder> {
     let mut types = TypesBuilder::new();
     types
@@ -170,7 +237,7 @@ fn walker_builder(
         .sort_by_file_path(Path::cmp)
         .types(types.build().context("Failed to build types")?)
         .overrides(overrides)
-        .follow_links(options.follow_links);
+        .follow_links(follow_links);
     for root in directories.iter().skip(1) {
         walker_builder.add(root);
     }
@@ -208,8 +275,54 @@ fn language(
             type_mappings: config.scala.type_mappings,
             ..Default::default()
         }),
+        SupportedLanguage::TypeScript if config.typescript.zod => Box::new(Zod {
+            type_mappings: config.typescript.type_mappings,
+            zod_type_mappings: config.typescript.zod_type_mappings,
+            ..Default::default()
+        }),
         SupportedLanguage::TypeScript => Box::new(TypeScript {
             type_mappings: config.typescript.type_mappings,
+            type_guards: config.typescript.type_guards,
+            ..Default::default()
+        }),
+        SupportedLanguage::JsonSchema => Box::new(JsonSchema {
+            type_mappings: config.json_schema.type_mappings,
+            ..Default::default()
+        }),
+        SupportedLanguage::OpenApi => Box::new(OpenApi {
+            type_mappings: config.openapi.type_mappings,
+            json: config.openapi.json,
+            ..Default::default()
+        }),
+        SupportedLanguage::Proto => Box::new(Proto {
+            package: config.proto.package,
+            strict_tags: config.proto.strict_tags,
+            type_mappings: config.proto.type_mappings,
+            ..Default::default()
+        }),
+        SupportedLanguage::Java => Box::new(Java {
+            package: config.java.package,
+            class_name: config.java.class_name,
+            nullable_annotation: config.java.nullable_annotation,
+            type_mappings: config.java.type_mappings,
+            ..Default::default()
+        }),
+        SupportedLanguage::CSharp => Box::new(CSharp {
+            namespace: config.csharp.namespace,
+            type_mappings: config.csharp.type_mappings,
+            ..Default::default()
+        }),
+        SupportedLanguage::Dart => Box::new(Dart {
+            type_mappings: config.dart.type_mappings,
+            ..Default::default()
+        }),
+        SupportedLanguage::GraphQL => Box::new(GraphQL {
+            type_mappings: config.graphql.type_mappings,
+            ..Default::default()
+        }),
+        SupportedLanguage::Avro => Box::new(Avro {
+            namespace: config.avro.namespace,
+            type_mappings: config.avro.type_mappings,
             ..Default::default()
         }),
         #[cfg(feature = "go")]
@@ -218,6 +331,7 @@ fn language(
             type_mappings: config.go.type_mappings,
             uppercase_acronyms: config.go.uppercase_acronyms,
             no_pointer_slice: config.go.no_pointer_slice,
+            tag_template: config.go.tag_template,
             ..Default::default()
         }),
         #[cfg(not(feature = "go"))]
@@ -248,6 +362,7 @@ fn override_configuration(mut config: Config, options: &Args) -> anyhow::Result<
 
     if let Some(java_package) = options.java_package.as_ref() {
         config.kotlin.package = java_package.clone();
+        config.java.package = java_package.clone();
     }
 
     if let Some(module_name) = options.kotlin_module_name.as_ref() {
@@ -278,29 +393,105 @@ fn override_configuration(mut config: Config, options: &Args) -> anyhow::Result<
 
     config.target_os = options.target_os.as_deref().unwrap_or_default().to_vec();
 
+    if let Some(cfg) = options.cfg.as_ref() {
+        config.cfg.extend(cfg.iter().cloned());
+    }
+
     Ok(config)
 }
 
-/// Prints out all parsing errors if any and returns Err.
-fn check_parse_errors(parsed_crates: &BTreeMap<CrateName, ParsedData>) -> anyhow::Result<()> {
-    let mut errors_encountered = false;
-    for data in parsed_crates
-        .values()
-        .filter(|parsed_data| !parsed_data.errors.is_empty())
-    {
-        errors_encountered = true;
-        for error in &data.errors {
-            error!(
-                "Parsing error: \"{}\" in file \"{}\"",
-                error.error, error.file_name
-            );
+/// The active cfg options from the configuration, including the `target_os` targets.
+fn cfg_options(config: &Config) -> CfgOptions {
+    let mut cfg = CfgOptions::from_target_os(&config.target_os);
+    for option in &config.cfg {
+        cfg.insert_str(option);
+    }
+    cfg
+}
+
+/// Prints out all parsing warnings and errors if any and returns Err if there were errors.
+fn check_parse_errors(
+    parsed_crates: &BTreeMap<CrateName, ParsedData>,
+    message_format: MessageFormat,
+) -> anyhow::Result<()> {
+    let warnings = sorted_errors(parsed_crates.values().flat_map(|data| &data.warnings));
+    let errors = sorted_errors(parsed_crates.values().flat_map(|data| &data.errors));
+
+    for warning in &warnings {
+        match message_format {
+            MessageFormat::Human => warn!("{}", render_parse_error("Parsing warning", warning)),
+            MessageFormat::Json => Message::parse_warning(warning).emit(),
+        }
+    }
+
+    for error in &errors {
+        match message_format {
+            MessageFormat::Human => error!("{}", render_parse_error("Parsing error", error)),
+            MessageFormat::Json => Message::parse_error(error).emit(),
         }
     }
 
-    if errors_encountered {
+    if !errors.is_empty() {
         error!("Errors encountered during parsing.");
         Err(anyhow!("Errors encountered during parsing."))
     } else {
         Ok(())
     }
 }
+
+/// Files are parsed in parallel so sort errors for a stable report.
+fn sorted_errors<'a>(errors: impl Iterator<Item = &'a ErrorInfo>) -> Vec<&'a ErrorInfo> {
+    let mut errors = errors.collect::<Vec<_>>();
+    errors.sort_by_key(|error| {
+        (
+            &error.file_name,
+            error.location.as_ref().map(|location| location.line),
+        )
+    });
+    errors
+}
+
+/// Renders a parsing error along with a snippet of the offending source, e.g.
+///
+/// ```text
+/// Parsing error: unsupported type encountered: i64
+///   --> src/lib.rs:4:14
+///    |
+///  4 |     pub bar: i64,
+///    |              ^^^
+///    = note: while parsing `Foo`
+/// ```
+fn render_parse_error(label: &str, error: &ErrorInfo) -> String {
+    let mut rendered = format!("{label}: {}\n", error.error);
+
+    let Some(location) = &error.location else {
+        rendered.push_str(&format!("  --> {}", error.file_name));
+        if let Some(item_name) = &error.item_name {
+            rendered.push_str(&format!("\n   = note: while parsing `{item_name}`"));
+        }
+        return rendered;
+    };
+
+    let line_number = location.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+    let marker_indent: String = location
+        .source_line
+        .chars()
+        .take(location.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let markers = "^".repeat(location.end_column - location.column);
+
+    rendered.push_str(&format!(
+        "{gutter}--> {}:{}:{}\n",
+        error.file_name, location.line, location.column
+    ));
+    rendered.push_str(&format!("{gutter} |\n"));
+    rendered.push_str(&format!("{line_number} | {}\n", location.source_line));
+    rendered.push_str(&format!("{gutter} | {marker_indent}{markers}"));
+    if let Some(item_name) = &error.item_name {
+        rendered.push_str(&format!("\n{gutter} = note: while parsing `{item_name}`"));
+    }
+
+    rendered
+}
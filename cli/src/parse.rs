@@ -4,27 +4,48 @@ use crossbeam::channel::bounded;
 use ignore::{DirEntry, WalkBuilder, WalkState};
 use std::{
     collections::{BTreeMap, HashMap},
-    mem, thread,
+    mem,
+    path::{Path, PathBuf},
+    thread,
 };
 use typeshare_core::{
     context::{ParseContext, ParseFileContext},
-    language::{CrateName, CrateTypes, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
-    parser::{ParseError, ParsedData},
+    language::{CrateName, CrateTypes, Java, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
+    parser::{ErrorInfo, ParseError, ParsedData, SourceLocation},
     RenameExt,
 };
 
+/// A file that could not be walked, read or parsed, along with the crate it belongs to.
+type FileError = Box<(CrateName, ErrorInfo)>;
+
+/// The crate a source file belongs to, or `None` if it is not part of a crate in multi file mode.
+fn crate_name(multi_file: bool, path: &Path) -> Option<CrateName> {
+    if multi_file {
+        CrateName::find_crate_name(path)
+    } else {
+        Some(SINGLE_FILE_CRATE_NAME)
+    }
+}
+
+fn file_error(crate_name: CrateName, path: &Path, error: ParseError) -> FileError {
+    Box::new((
+        crate_name,
+        ErrorInfo {
+            file_name: path.to_string_lossy().into_owned(),
+            item_name: None,
+            location: None,
+            error,
+        },
+    ))
+}
+
 fn parse_file_context(
     multi_file: bool,
     language_type: SupportedLanguage,
     dir_entry: &DirEntry,
 ) -> anyhow::Result<Option<ParseFileContext>> {
-    let crate_name = if multi_file {
-        let Some(crate_name) = CrateName::find_crate_name(dir_entry.path()) else {
-            return Ok(None);
-        };
-        crate_name
-    } else {
-        SINGLE_FILE_CRATE_NAME
+    let Some(crate_name) = crate_name(multi_file, dir_entry.path()) else {
+        return Ok(None);
     };
     let file_path = dir_entry.path().to_path_buf();
     let out_file_name = output_file_name(language_type, &crate_name);
@@ -59,6 +80,18 @@ fn output_file_name(language_type: SupportedLanguage, crate_name: &CrateName) ->
         SupportedLanguage::Swift => pascal_case(),
         SupportedLanguage::TypeScript => snake_case(),
         SupportedLanguage::Python => snake_case(),
+        SupportedLanguage::JsonSchema => snake_case(),
+        SupportedLanguage::OpenApi => snake_case(),
+        SupportedLanguage::Proto => snake_case(),
+        // The file has to be named after the class it declares.
+        SupportedLanguage::Java => format!(
+            "{}.{extension}",
+            Java::crate_class_name(crate_name.as_str())
+        ),
+        SupportedLanguage::CSharp => pascal_case(),
+        SupportedLanguage::Dart => snake_case(),
+        SupportedLanguage::GraphQL => snake_case(),
+        SupportedLanguage::Avro => snake_case(),
     }
 }
 
@@ -84,60 +117,99 @@ fn parse_dir_entry(
     parse_context: &ParseContext,
     language_type: SupportedLanguage,
     dir_entry: &DirEntry,
-) -> Result<Option<ParsedData>, ParseError> {
+) -> Result<Option<ParsedData>, FileError> {
     if dir_entry.path().is_dir() {
         return Ok(None);
     }
 
+    let path = dir_entry.path();
     let Some(parse_file_context) =
-        parse_file_context(parse_context.multi_file, language_type, dir_entry)
-            .map_err(|err| ParseError::IOError(err.to_string()))?
+        parse_file_context(parse_context.multi_file, language_type, dir_entry).map_err(|err| {
+            let crate_name =
+                crate_name(parse_context.multi_file, path).unwrap_or(SINGLE_FILE_CRATE_NAME);
+            file_error(crate_name, path, ParseError::IOError(format!("{err:#}")))
+        })?
     else {
         return Ok(None);
     };
 
-    typeshare_core::parser::parse(parse_context, parse_file_context)
+    typeshare_core::parser::parse_borrowed(parse_context, &parse_file_context).map_err(|error| {
+        let ParseFileContext {
+            source_code,
+            crate_name,
+            ..
+        } = parse_file_context;
+        let mut file_error = file_error(crate_name, path, error);
+        let error_info = &mut file_error.1;
+        if let ParseError::SynError(err) = &error_info.error {
+            error_info.location = Some(SourceLocation::from_span(err.span(), &source_code));
+        }
+        file_error
+    })
 }
 
 /// Use parallel builder to walk all source directories concurrently.
+///
+/// Files that fail to be walked, read or parsed are recorded as errors of their crate
+/// and walking continues, unless `fail_fast` is set in which case walking stops at the
+/// first such file. Errors in typeshared items never stop walking.
 pub fn parallel_parse(
     parse_context: &ParseContext,
     walker_builder: WalkBuilder,
     language_type: SupportedLanguage,
-) -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
-    let (tx, rx) = bounded::<anyhow::Result<ParsedData>>(100);
+    fail_fast: bool,
+) -> BTreeMap<CrateName, ParsedData> {
+    let (tx, rx) = bounded::<Result<ParsedData, FileError>>(100);
+    let multi_file = parse_context.multi_file;
 
     let collector_thread = thread::spawn(move || {
         let mut crate_parsed_data: BTreeMap<CrateName, ParsedData> = BTreeMap::new();
 
         for result in rx {
-            let parsed_data = result?;
-            let crate_name = parsed_data.crate_name.clone();
-            // Append each yielded parsed data by its respective crate.
-            *crate_parsed_data.entry(crate_name).or_default() += parsed_data;
+            match result {
+                Ok(parsed_data) => {
+                    let crate_name = parsed_data.crate_name.clone();
+                    // Append each yielded parsed data by its respective crate.
+                    *crate_parsed_data.entry(crate_name).or_default() += parsed_data;
+                }
+                Err(file_error) => {
+                    let (crate_name, error_info) = *file_error;
+                    crate_parsed_data
+                        .entry(crate_name.clone())
+                        .or_insert_with(|| ParsedData::new(crate_name, String::new(), multi_file))
+                        .errors
+                        .push(error_info);
+                }
+            }
         }
 
-        Ok(crate_parsed_data)
+        crate_parsed_data
     });
 
     walker_builder.build_parallel().run(|| {
         let tx = tx.clone();
 
         Box::new(move |result| {
-            let result = result.context("Failed traversing").and_then(|dir_entry| {
-                parse_dir_entry(parse_context, language_type, &dir_entry)
-                    .with_context(|| format!("Parsing failed: {:?}", dir_entry.path()))
-            });
-            match result {
-                Ok(Some(parsed_data)) => {
-                    tx.send(Ok(parsed_data)).unwrap();
-                    WalkState::Continue
-                }
-                Ok(None) => WalkState::Continue,
+            let result = match result {
+                Ok(dir_entry) => parse_dir_entry(parse_context, language_type, &dir_entry),
                 Err(err) => {
-                    tx.send(Err(err)).unwrap();
-                    WalkState::Quit
+                    let path = walk_error_path(&err).unwrap_or_default();
+                    Err(file_error(
+                        SINGLE_FILE_CRATE_NAME,
+                        &path,
+                        ParseError::IOError(format!("Failed traversing: {err}")),
+                    ))
                 }
+            };
+            let failed = result.is_err();
+            if let Some(result) = result.transpose() {
+                tx.send(result).unwrap();
+            }
+
+            if failed && fail_fast {
+                WalkState::Quit
+            } else {
+                WalkState::Continue
             }
         })
     });
@@ -145,3 +217,90 @@ pub fn parallel_parse(
     drop(tx);
     collector_thread.join().unwrap()
 }
+
+/// The path a walk error happened at, if any.
+fn walk_error_path(err: &ignore::Error) -> Option<PathBuf> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path.clone()),
+        ignore::Error::WithLineNumber { err, .. } | ignore::Error::WithDepth { err, .. } => {
+            walk_error_path(err)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CURRENT_DIR: &str = env!("CARGO_MANIFEST_DIR");
+    const TEST_DIR: &str = "data/tests/parse_errors";
+
+    /// Parse the given files or directories of the test crates, each of which has a file
+    /// that isn't valid Rust and a file with an unsupported type, and return the kinds of
+    /// errors of each crate.
+    fn parse_errors(roots: &[&str], fail_fast: bool) -> BTreeMap<String, Vec<&'static str>> {
+        let path = |root: &str| -> PathBuf { [CURRENT_DIR, TEST_DIR, root].iter().collect() };
+        let mut walker_builder = WalkBuilder::new(path(roots[0]));
+        for root in &roots[1..] {
+            walker_builder.add(path(root));
+        }
+        // With a single thread, nothing else is parsed once fail fast stops the walk.
+        walker_builder.sort_by_file_path(Path::cmp).threads(1);
+        let parse_context = ParseContext {
+            multi_file: true,
+            ..Default::default()
+        };
+
+        parallel_parse(
+            &parse_context,
+            walker_builder,
+            SupportedLanguage::TypeScript,
+            fail_fast,
+        )
+        .into_iter()
+        .map(|(crate_name, parsed_data)| {
+            let mut kinds = parsed_data
+                .errors
+                .iter()
+                .map(|error| error.error.kind())
+                .collect::<Vec<_>>();
+            kinds.sort_unstable();
+            (crate_name.to_string(), kinds)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn collects_errors_of_every_file_and_crate() {
+        assert_eq!(
+            parse_errors(&["."], false),
+            BTreeMap::from([
+                ("crate_a".to_owned(), vec!["RustTypeParseError", "SynError"]),
+                ("crate_b".to_owned(), vec!["RustTypeParseError", "SynError"]),
+            ])
+        );
+    }
+
+    #[test]
+    fn fail_fast_stops_at_the_first_file_that_fails_to_parse() {
+        let errors = parse_errors(&["."], true);
+        let syn_errors = errors
+            .values()
+            .flatten()
+            .filter(|kind| **kind == "SynError")
+            .count();
+        assert_eq!(syn_errors, 1);
+    }
+
+    #[test]
+    fn fail_fast_continues_after_errors_in_items() {
+        assert_eq!(
+            parse_errors(&["crate_a/src/account.rs", "crate_b/src/team.rs"], true),
+            BTreeMap::from([
+                ("crate_a".to_owned(), vec!["RustTypeParseError"]),
+                ("crate_b".to_owned(), vec!["RustTypeParseError"]),
+            ])
+        );
+    }
+}
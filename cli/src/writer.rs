@@ -4,7 +4,7 @@ use log::info;
 use std::{
     collections::{BTreeMap, HashMap},
     fs,
-    path::Path,
+    path::{Path, PathBuf},
 };
 use typeshare_core::{
     language::{CrateName, CrateTypes, Language, SINGLE_FILE_CRATE_NAME},
@@ -17,13 +17,21 @@ pub enum Output<'a> {
     Folder(&'a Path),
 }
 
+/// A file written by typeshare.
+#[derive(Debug)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    /// The file already had the generated contents and was left untouched.
+    pub fresh: bool,
+}
+
 /// Write the parsed data to the one or more files depending on command line options.
 pub fn write_generated(
     destination: Output<'_>,
     lang: &mut (impl Language + ?Sized),
     crate_parsed_data: BTreeMap<CrateName, ParsedData>,
     import_candidates: CrateTypes,
-) -> Result<(), anyhow::Error> {
+) -> Result<Vec<GeneratedFile>, anyhow::Error> {
     match destination {
         Output::File(path) => write_single_file(lang, path, crate_parsed_data),
         Output::Folder(path) => {
@@ -38,46 +46,57 @@ fn write_multiple_files(
     output_folder: &Path,
     crate_parsed_data: BTreeMap<CrateName, ParsedData>,
     import_candidates: CrateTypes,
-) -> Result<(), anyhow::Error> {
+) -> Result<Vec<GeneratedFile>, anyhow::Error> {
+    let mut generated_files = Vec::new();
     for (_crate_name, parsed_data) in crate_parsed_data {
-        let outfile = Path::new(output_folder).join(&parsed_data.file_name);
+        let outfile = Path::new(output_folder)
+            .join(&parsed_data.file_name)
+            .with_extension(lang.file_extension());
         let mut generated_contents = Vec::new();
         lang.generate_types(&mut generated_contents, &import_candidates, parsed_data)?;
-        check_write_file(&outfile, generated_contents)?;
+        generated_files.extend(check_write_file(&outfile, generated_contents)?);
     }
 
     lang.post_generation(&output_folder.as_os_str().to_string_lossy())
         .context("Post generation failed")?;
 
-    Ok(())
+    Ok(generated_files)
 }
 
 /// Write the file if the contents have changed.
-fn check_write_file(outfile: &Path, output: Vec<u8>) -> anyhow::Result<()> {
+fn check_write_file(outfile: &Path, output: Vec<u8>) -> anyhow::Result<Option<GeneratedFile>> {
     match fs::read(outfile) {
         Ok(buf) if buf == output => {
             // avoid writing the file to leave the mtime intact
             // for tools which might use it to know when to
             // rebuild.
             info!("Skipping writing to {outfile:?} no changes");
-            return Ok(());
+            return Ok(Some(GeneratedFile {
+                path: outfile.to_path_buf(),
+                fresh: true,
+            }));
         }
         _ => {}
     }
 
-    if !output.is_empty() {
-        let out_dir = outfile
-            .parent()
-            .with_context(|| format!("Could not get parent for {outfile:?}"))?;
-        // If the output directory doesn't already exist, create it.
-        if !out_dir.exists() {
-            fs::create_dir_all(out_dir).context("failed to create output directory")?;
-        }
+    if output.is_empty() {
+        return Ok(None);
+    }
 
-        fs::write(outfile, output)
-            .with_context(|| format!("failed to write output: {}", outfile.to_string_lossy()))?;
+    let out_dir = outfile
+        .parent()
+        .with_context(|| format!("Could not get parent for {outfile:?}"))?;
+    // If the output directory doesn't already exist, create it.
+    if !out_dir.exists() {
+        fs::create_dir_all(out_dir).context("failed to create output directory")?;
     }
-    Ok(())
+
+    fs::write(outfile, output)
+        .with_context(|| format!("failed to write output: {}", outfile.to_string_lossy()))?;
+    Ok(Some(GeneratedFile {
+        path: outfile.to_path_buf(),
+        fresh: false,
+    }))
 }
 
 /// Write all types to a single file.
@@ -85,7 +104,7 @@ fn write_single_file(
     lang: &mut (impl Language + ?Sized),
     file_name: &Path,
     mut crate_parsed_data: BTreeMap<CrateName, ParsedData>,
-) -> Result<(), anyhow::Error> {
+) -> Result<Vec<GeneratedFile>, anyhow::Error> {
     let parsed_data = crate_parsed_data
         .remove(&SINGLE_FILE_CRATE_NAME)
         .context("Could not get parsed data for single file output")?;
@@ -94,6 +113,5 @@ fn write_single_file(
     lang.generate_types(&mut output, &HashMap::new(), parsed_data)?;
 
     let outfile = Path::new(file_name).to_path_buf();
-    check_write_file(&outfile, output)?;
-    Ok(())
+    Ok(check_write_file(&outfile, output)?.into_iter().collect())
 }
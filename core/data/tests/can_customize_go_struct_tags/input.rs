@@ -0,0 +1,11 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    #[typeshare(go(tag = "validate:\"required\""))]
+    user_id: String,
+    #[typeshare(go(omitempty))]
+    display_name: String,
+    #[typeshare(go(string, tag = "db:\"balance_cents\""))]
+    balance: u32,
+    nickname: Option<String>,
+}
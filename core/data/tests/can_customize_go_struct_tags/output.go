@@ -0,0 +1,10 @@
+package proto
+
+import "encoding/json"
+
+type Account struct {
+	UserId string `json:"userId" db:"user_id" yaml:"userId" validate:"required"`
+	DisplayName string `json:"displayName,omitempty" db:"display_name" yaml:"displayName"`
+	Balance uint32 `json:"balance,string" db:"balance_cents" yaml:"balance"`
+	Nickname *string `json:"nickname,omitempty" db:"nickname" yaml:"nickname"`
+}
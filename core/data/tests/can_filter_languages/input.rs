@@ -0,0 +1,35 @@
+/// Only generated for mobile clients.
+#[typeshare(lang = "swift, kotlin")]
+pub struct MobileOnly {
+    push_token: String,
+}
+
+#[typeshare(skip(swift, kotlin))]
+pub struct WebOnly {
+    cookie: String,
+}
+
+#[typeshare]
+pub struct Session {
+    id: String,
+    #[typeshare(lang = "swift, kotlin")]
+    mobile: MobileOnly,
+    #[typeshare(skip(swift, kotlin))]
+    web: WebOnly,
+}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Platform {
+    #[typeshare(lang = "swift, kotlin")]
+    Mobile(MobileOnly),
+    #[typeshare(skip(swift, kotlin))]
+    Web { session: WebOnly },
+    Other,
+}
+
+#[typeshare(skip(typescript))]
+pub type MobileToken = String;
+
+#[typeshare(lang = "typescript")]
+pub const WEB_TIMEOUT: u32 = 30;
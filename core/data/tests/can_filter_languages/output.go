@@ -0,0 +1,79 @@
+package proto
+
+import "encoding/json"
+
+type MobileToken string
+
+type WebOnly struct {
+	Cookie string `json:"cookie"`
+}
+type Session struct {
+	Id string `json:"id"`
+	Web WebOnly `json:"web"`
+}
+// Generated type representing the anonymous struct variant `Web` of the `Platform` Rust enum
+type PlatformWebInner struct {
+	Session WebOnly `json:"session"`
+}
+type PlatformTypes string
+const (
+	PlatformTypeVariantWeb PlatformTypes = "Web"
+	PlatformTypeVariantOther PlatformTypes = "Other"
+)
+type Platform struct{ 
+	Type PlatformTypes `json:"type"`
+	content interface{}
+}
+
+func (p *Platform) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    PlatformTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	p.Type = enum.Tag
+	switch p.Type {
+	case PlatformTypeVariantWeb:
+		var res PlatformWebInner
+		p.content = &res
+	case PlatformTypeVariantOther:
+		return nil
+
+	}
+	if err := json.Unmarshal(enum.Content, &p.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (p Platform) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    PlatformTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = p.Type
+    enum.Content = p.content
+    return json.Marshal(enum)
+}
+
+func (p Platform) Web() *PlatformWebInner {
+	res, _ := p.content.(*PlatformWebInner)
+	return res
+}
+
+func NewPlatformTypeVariantWeb(content *PlatformWebInner) Platform {
+    return Platform{
+        Type: PlatformTypeVariantWeb,
+        content: content,
+    }
+}
+func NewPlatformTypeVariantOther() Platform {
+    return Platform{
+        Type: PlatformTypeVariantOther,
+    }
+}
+
@@ -0,0 +1,29 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+
+typealias MobileToken = String
+
+/// Only generated for mobile clients.
+@Serializable
+data class MobileOnly (
+	val push_token: String
+)
+
+@Serializable
+data class Session (
+	val id: String,
+	val mobile: MobileOnly
+)
+
+@Serializable
+sealed class Platform {
+	@Serializable
+	@SerialName("Mobile")
+	data class Mobile(val content: MobileOnly): Platform()
+	@Serializable
+	@SerialName("Other")
+	object Other: Platform()
+}
+
@@ -0,0 +1,34 @@
+from __future__ import annotations
+
+from enum import Enum
+from pydantic import BaseModel
+from typing import Literal, Union
+
+
+MobileToken = str
+
+class WebOnly(BaseModel):
+    cookie: str
+
+class Session(BaseModel):
+    id: str
+    web: WebOnly
+
+class PlatformWebInner(BaseModel):
+    """
+    Generated type representing the anonymous struct variant `Web` of the `Platform` Rust enum
+    """
+    session: WebOnly
+
+class PlatformTypes(str, Enum):
+    WEB = "Web"
+    OTHER = "Other"
+
+class PlatformWeb(BaseModel):
+    type: Literal[PlatformTypes.WEB] = PlatformTypes.WEB
+    content: PlatformWebInner
+
+class PlatformOther(BaseModel):
+    type: Literal[PlatformTypes.OTHER] = PlatformTypes.OTHER
+
+Platform = Union[PlatformWeb, PlatformOther]
@@ -0,0 +1,36 @@
+package com.agilebits
+
+package object onepassword {
+
+type MobileToken = String
+
+}
+package onepassword {
+
+case class Session (
+	id: String,
+	web: WebOnly
+)
+
+case class WebOnly (
+	cookie: String
+)
+
+// Generated type representing the anonymous struct variant `Web` of the `Platform` Rust enum
+case class PlatformWebInner (
+	session: WebOnly
+)
+
+sealed trait Platform {
+	def serialName: String
+}
+object Platform {
+	case class Web(content: PlatformWebInner) extends Platform {
+		val serialName: String = "Web"
+	}
+	case object Other extends Platform {
+		val serialName: String = "Other"
+	}
+}
+
+}
@@ -0,0 +1,64 @@
+import Foundation
+
+public typealias MobileToken = String
+
+/// Only generated for mobile clients.
+public struct MobileOnly: Codable {
+	public let push_token: String
+
+	public init(push_token: String) {
+		self.push_token = push_token
+	}
+}
+
+public struct Session: Codable {
+	public let id: String
+	public let mobile: MobileOnly
+
+	public init(id: String, mobile: MobileOnly) {
+		self.id = id
+		self.mobile = mobile
+	}
+}
+
+public enum Platform: Codable {
+	case mobile(MobileOnly)
+	case other
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case mobile = "Mobile",
+			other = "Other"
+	}
+
+	private enum ContainerCodingKeys: String, CodingKey {
+		case type, content
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+		if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+			switch type {
+			case .mobile:
+				if let content = try? container.decode(MobileOnly.self, forKey: .content) {
+					self = .mobile(content)
+					return
+				}
+			case .other:
+				self = .other
+				return
+			}
+		}
+		throw DecodingError.typeMismatch(Platform.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Platform"))
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+		switch self {
+		case .mobile(let content):
+			try container.encode(CodingKeys.mobile, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .other:
+			try container.encode(CodingKeys.other, forKey: .type)
+		}
+	}
+}
@@ -0,0 +1,16 @@
+export interface WebOnly {
+	cookie: string;
+}
+
+export interface Session {
+	id: string;
+	web: WebOnly;
+}
+
+export type Platform = 
+	| { type: "Web", content: {
+	session: WebOnly;
+}}
+	| { type: "Other", content?: undefined };
+
+export const WEB_TIMEOUT: number = 30;
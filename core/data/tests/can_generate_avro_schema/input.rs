@@ -0,0 +1,59 @@
+#[typeshare]
+pub const MAX_SESSIONS: u32 = 5;
+
+/// A user of the service.
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    /// The user's identifier.
+    pub user_id: UserId,
+    pub display_name: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub login_count: u32,
+    pub rating: f64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub scores: HashMap<String, f64>,
+    #[serde(default)]
+    #[typeshare(avro(default = r#""ACTIVE""#))]
+    pub status: Status,
+    #[serde(default)]
+    #[typeshare(avro(default = r#"{"street": "", "city": null}"#))]
+    pub address: Address,
+    pub last_event: Option<Event>,
+    pub last_seen: Option<OffsetDateTime>,
+}
+
+#[typeshare]
+pub type UserId = String;
+
+/// Where a user lives.
+#[typeshare]
+#[derive(Default)]
+pub struct Address {
+    pub street: String,
+    pub city: Option<String>,
+}
+
+#[typeshare]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    #[default]
+    Active,
+    /// Can't log in.
+    Suspended,
+}
+
+/// Something that happened to a user.
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    /// The user was renamed.
+    Renamed { from: String, to: Option<String> },
+    Tagged(Vec<String>),
+    Deleted,
+}
@@ -0,0 +1,180 @@
+#nullable enable
+
+using System;
+using System.Collections.Generic;
+using System.Text.Json;
+using System.Text.Json.Serialization;
+
+namespace AgileBits.OnePassword;
+
+public sealed record Empty();
+
+public sealed record Page<T>(
+    [property: JsonPropertyName("items")] List<T> Items,
+    [property: JsonPropertyName("next")] string? Next
+);
+
+[JsonConverter(typeof(JsonStringEnumConverter))]
+public enum Status
+{
+    [JsonStringEnumMemberName("ACTIVE")]
+    Active,
+    /// <summary>
+    /// Can't log in.
+    /// </summary>
+    [JsonStringEnumMemberName("SUSPENDED")]
+    Suspended,
+    [JsonStringEnumMemberName("gone")]
+    Deleted
+}
+
+/// <summary>
+/// A user of the service.
+/// </summary>
+/// <param name="UserId">The user's identifier.</param>
+public sealed record User(
+    [property: JsonPropertyName("userId")] string UserId,
+    [property: JsonPropertyName("displayName")] string DisplayName,
+    [property: JsonPropertyName("email")] string? Email,
+    [property: JsonPropertyName("verified")] [property: JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)] bool? Verified,
+    [property: JsonPropertyName("age")] byte Age,
+    [property: JsonPropertyName("tags")] List<string> Tags,
+    [property: JsonPropertyName("scores")] Dictionary<string, double> Scores,
+    [property: JsonPropertyName("status")] Status Status,
+    [property: JsonPropertyName("class")] string Class,
+    [property: JsonPropertyName("avatar")] System.Uri Avatar
+);
+
+/// <summary>
+/// Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+/// </summary>
+public sealed record EventRenamedInner(
+    [property: JsonPropertyName("from")] string From,
+    [property: JsonPropertyName("to")] string To
+);
+
+/// <summary>
+/// Something that happened to a user.
+/// </summary>
+[JsonConverter(typeof(EventJsonConverter))]
+public abstract record Event<T>
+{
+    private protected Event() {}
+}
+
+public sealed record EventCreated<T>(User Content) : Event<T>;
+
+public sealed record EventRenamed<T>(EventRenamedInner Content) : Event<T>;
+
+public sealed record EventCustom<T>(T Content) : Event<T>;
+
+public sealed record EventDeleted<T>() : Event<T>;
+
+public sealed class EventJsonConverter : JsonConverterFactory
+{
+    public override bool CanConvert(Type typeToConvert) =>
+        typeToConvert.IsGenericType && typeToConvert.GetGenericTypeDefinition() == typeof(Event<>);
+
+    public override JsonConverter CreateConverter(Type typeToConvert, JsonSerializerOptions options) =>
+        (JsonConverter)Activator.CreateInstance(
+            typeof(EventJsonConverter<>).MakeGenericType(typeToConvert.GetGenericArguments()))!;
+}
+
+public sealed class EventJsonConverter<T> : JsonConverter<Event<T>>
+{
+    public override Event<T> Read(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options)
+    {
+        var element = JsonElement.ParseValue(ref reader);
+        var tag = element.GetProperty("type").GetString();
+        return tag switch
+        {
+            "Created" => new EventCreated<T>(element.GetProperty("content").Deserialize<User>(options)!),
+            "Renamed" => new EventRenamed<T>(element.GetProperty("content").Deserialize<EventRenamedInner>(options)!),
+            "Custom" => new EventCustom<T>(element.GetProperty("content").Deserialize<T>(options)!),
+            "Deleted" => new EventDeleted<T>(),
+            _ => throw new JsonException($"Unknown Event variant: {tag}"),
+        };
+    }
+
+    public override void Write(Utf8JsonWriter writer, Event<T> value, JsonSerializerOptions options)
+    {
+        writer.WriteStartObject();
+        switch (value)
+        {
+            case EventCreated<T> variant:
+                writer.WriteString("type", "Created");
+                writer.WritePropertyName("content");
+                JsonSerializer.Serialize(writer, variant.Content, options);
+                break;
+            case EventRenamed<T> variant:
+                writer.WriteString("type", "Renamed");
+                writer.WritePropertyName("content");
+                JsonSerializer.Serialize(writer, variant.Content, options);
+                break;
+            case EventCustom<T> variant:
+                writer.WriteString("type", "Custom");
+                writer.WritePropertyName("content");
+                JsonSerializer.Serialize(writer, variant.Content, options);
+                break;
+            case EventDeleted<T>:
+                writer.WriteString("type", "Deleted");
+                break;
+        }
+        writer.WriteEndObject();
+    }
+}
+
+[JsonConverter(typeof(NotificationJsonConverter))]
+public abstract record Notification
+{
+    private protected Notification() {}
+}
+
+public sealed record NotificationUser(User Data) : Notification;
+
+public sealed record NotificationMessage(string Data) : Notification;
+
+public sealed record NotificationPing() : Notification;
+
+public sealed class NotificationJsonConverter : JsonConverter<Notification>
+{
+    public override Notification Read(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options)
+    {
+        var element = JsonElement.ParseValue(ref reader);
+        var tag = element.GetProperty("kind").GetString();
+        return tag switch
+        {
+            "User" => new NotificationUser(element.GetProperty("data").Deserialize<User>(options)!),
+            "Message" => new NotificationMessage(element.GetProperty("data").Deserialize<string>(options)!),
+            "Ping" => new NotificationPing(),
+            _ => throw new JsonException($"Unknown Notification variant: {tag}"),
+        };
+    }
+
+    public override void Write(Utf8JsonWriter writer, Notification value, JsonSerializerOptions options)
+    {
+        writer.WriteStartObject();
+        switch (value)
+        {
+            case NotificationUser variant:
+                writer.WriteString("kind", "User");
+                writer.WritePropertyName("data");
+                JsonSerializer.Serialize(writer, variant.Data, options);
+                break;
+            case NotificationMessage variant:
+                writer.WriteString("kind", "Message");
+                writer.WritePropertyName("data");
+                JsonSerializer.Serialize(writer, variant.Data, options);
+                break;
+            case NotificationPing:
+                writer.WriteString("kind", "Ping");
+                break;
+        }
+        writer.WriteEndObject();
+    }
+}
+
+public static class Constants
+{
+    public const uint MaxSessions = 5;
+}
@@ -0,0 +1,258 @@
+enum Status {
+  active('ACTIVE'),
+  /// Can't log in.
+  suspended('SUSPENDED'),
+  deleted('gone');
+
+  const Status(this.value);
+
+  final String value;
+
+  static Status fromJson(Object? json) {
+    return values.firstWhere(
+      (v) => v.value == json,
+      orElse: () => throw ArgumentError.value(json, 'json', 'Unknown Status value'),
+    );
+  }
+
+  String toJson() => value;
+}
+
+typedef PriceMap = Map<int, List<Status>>;
+
+typedef UserId = String;
+
+class Empty {
+  const Empty();
+
+  factory Empty.fromJson(Object? json) {
+    return const Empty();
+  }
+
+  Map<String, dynamic> toJson() {
+    return {};
+  }
+}
+
+/// A user of the service.
+class User {
+  const User({
+    required this.userId,
+    required this.displayName,
+    this.email,
+    this.verified,
+    required this.age,
+    required this.rating,
+    required this.tags,
+    required this.scores,
+    required this.status,
+    required this.pastStatuses,
+    this.roles,
+    this.lastSeen,
+    required this.class_,
+    required this.priceCents,
+    required this.metadata,
+  });
+
+  /// The user's identifier.
+  final UserId userId;
+  final String displayName;
+  final String? email;
+  final bool? verified;
+  final int age;
+  final double rating;
+  final List<String> tags;
+  final Map<String, double> scores;
+  final Status status;
+  final List<Status> pastStatuses;
+  final List<Status>? roles;
+  final DateTime? lastSeen;
+  final String class_;
+  final PriceMap priceCents;
+  final Object? metadata;
+
+  factory User.fromJson(Object? json) {
+    final map = json as Map<String, dynamic>;
+    return User(
+      userId: map['userId'] as String,
+      displayName: map['displayName'] as String,
+      email: map['email'] == null ? null : map['email'] as String,
+      verified: map['verified'] == null ? null : map['verified'] as bool,
+      age: map['age'] as int,
+      rating: (map['rating'] as num).toDouble(),
+      tags: (map['tags'] as List<dynamic>).map((e) => e as String).toList(),
+      scores: (map['scores'] as Map<String, dynamic>).map((k, e) => MapEntry(k, (e as num).toDouble())),
+      status: Status.fromJson(map['status']),
+      pastStatuses: (map['pastStatuses'] as List<dynamic>).map((e) => Status.fromJson(e)).toList(),
+      roles: map['roles'] == null ? null : (map['roles'] as List<dynamic>).map((e) => Status.fromJson(e)).toList(),
+      lastSeen: map['lastSeen'] == null ? null : DateTime.parse(map['lastSeen'] as String),
+      class_: map['class'] as String,
+      priceCents: (map['priceCents'] as Map<String, dynamic>).map((k, e) => MapEntry(int.parse(k), (e as List<dynamic>).map((e) => Status.fromJson(e)).toList())),
+      metadata: map['metadata'] as Object?,
+    );
+  }
+
+  Map<String, dynamic> toJson() {
+    return {
+      'userId': userId,
+      'displayName': displayName,
+      'email': email,
+      if (verified != null) 'verified': verified,
+      'age': age,
+      'rating': rating,
+      'tags': tags,
+      'scores': scores,
+      'status': status.toJson(),
+      'pastStatuses': pastStatuses.map((e) => e.toJson()).toList(),
+      if (roles != null) 'roles': roles?.map((e) => e.toJson()).toList(),
+      'lastSeen': lastSeen?.toIso8601String(),
+      'class': class_,
+      'priceCents': priceCents.map((k, e) => MapEntry(k.toString(), e.map((e) => e.toJson()).toList())),
+      'metadata': metadata,
+    };
+  }
+}
+
+class Page<T> {
+  const Page({
+    required this.items,
+    this.next,
+    this.users,
+  });
+
+  final List<T> items;
+  final String? next;
+  final List<User>? users;
+
+  factory Page.fromJson(Object? json, T Function(Object? json) fromJsonT) {
+    final map = json as Map<String, dynamic>;
+    return Page(
+      items: (map['items'] as List<dynamic>).map((e) => fromJsonT(e)).toList(),
+      next: map['next'] == null ? null : map['next'] as String,
+      users: map['users'] == null ? null : (map['users'] as List<dynamic>).map((e) => User.fromJson(e)).toList(),
+    );
+  }
+
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT) {
+    return {
+      'items': items.map((e) => toJsonT(e)).toList(),
+      'next': next,
+      'users': users?.map((e) => e.toJson()).toList(),
+    };
+  }
+}
+
+/// Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+class EventRenamedInner {
+  const EventRenamedInner({
+    required this.from,
+    required this.to,
+  });
+
+  final String from;
+  final String to;
+
+  factory EventRenamedInner.fromJson(Object? json) {
+    final map = json as Map<String, dynamic>;
+    return EventRenamedInner(
+      from: map['from'] as String,
+      to: map['to'] as String,
+    );
+  }
+
+  Map<String, dynamic> toJson() {
+    return {
+      'from': from,
+      'to': to,
+    };
+  }
+}
+
+/// Something that happened to a user.
+sealed class Event<T> {
+  const Event();
+
+  factory Event.fromJson(Object? json, T Function(Object? json) fromJsonT) {
+    final map = json as Map<String, dynamic>;
+    return switch (map['type']) {
+      'Created' => EventCreated<T>(User.fromJson(map['content'])),
+      'Renamed' => EventRenamed<T>(EventRenamedInner.fromJson(map['content'])),
+      'Custom' => EventCustom<T>(fromJsonT(map['content'])),
+      'Listed' => EventListed<T>(Page<User>.fromJson(map['content'], (e) => User.fromJson(e))),
+      'Deleted' => EventDeleted<T>(),
+      final tag => throw ArgumentError.value(tag, 'type', 'Unknown Event variant'),
+    };
+  }
+
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT);
+}
+
+class EventCreated<T> extends Event<T> {
+  const EventCreated(this.content);
+
+  final User content;
+
+  @override
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT) {
+    return {
+      'type': 'Created',
+      'content': content.toJson(),
+    };
+  }
+}
+
+class EventRenamed<T> extends Event<T> {
+  const EventRenamed(this.content);
+
+  final EventRenamedInner content;
+
+  @override
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT) {
+    return {
+      'type': 'Renamed',
+      'content': content.toJson(),
+    };
+  }
+}
+
+class EventCustom<T> extends Event<T> {
+  const EventCustom(this.content);
+
+  final T content;
+
+  @override
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT) {
+    return {
+      'type': 'Custom',
+      'content': toJsonT(content),
+    };
+  }
+}
+
+class EventListed<T> extends Event<T> {
+  const EventListed(this.content);
+
+  final Page<User> content;
+
+  @override
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT) {
+    return {
+      'type': 'Listed',
+      'content': content.toJson((e) => e.toJson()),
+    };
+  }
+}
+
+class EventDeleted<T> extends Event<T> {
+  const EventDeleted();
+
+  @override
+  Map<String, dynamic> toJson(Object? Function(T value) toJsonT) {
+    return {
+      'type': 'Deleted',
+    };
+  }
+}
+
+const int maxSessions = 5;
+
@@ -0,0 +1,62 @@
+#[typeshare]
+pub const MAX_SESSIONS: u32 = 5;
+
+/// A user of the service.
+#[typeshare(graphql = "input")]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    /// The user's identifier.
+    pub user_id: UserId,
+    pub display_name: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    pub age: u8,
+    pub rating: f64,
+    pub tags: Vec<Option<String>>,
+    pub labels: Option<Vec<String>>,
+    pub scores: HashMap<String, f64>,
+    pub status: Status,
+    pub address: Address,
+    pub last_seen: Option<OffsetDateTime>,
+    #[typeshare(graphql(type = "URL!"))]
+    pub homepage: String,
+    #[typeshare(graphql(name = "avatarUrl"))]
+    pub avatar: Option<String>,
+}
+
+#[typeshare]
+pub type UserId = String;
+
+/// Where a user lives.
+#[typeshare(graphql = "input")]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+}
+
+#[typeshare]
+pub struct Empty {}
+
+#[typeshare]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Active,
+    /// Can't log in.
+    Suspended,
+    #[serde(rename = "GONE")]
+    Deleted,
+}
+
+/// Something that happened to a user.
+///
+/// Events are never deleted.
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    /// The user signed up.
+    Created(User),
+    Renamed { from: String, to: Option<String> },
+    Tagged(Vec<String>),
+    Deleted,
+}
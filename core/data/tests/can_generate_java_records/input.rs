@@ -0,0 +1,59 @@
+#[typeshare]
+pub const MAX_SESSIONS: u32 = 5;
+
+/// A user of the service.
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    /// The user's identifier.
+    pub user_id: UserId,
+    pub display_name: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    pub age: u8,
+    pub tags: Vec<String>,
+    pub scores: HashMap<String, f64>,
+    pub status: Status,
+    #[serde(default)]
+    pub roles: Vec<Status>,
+    #[serde(rename = "class")]
+    pub class: String,
+    #[typeshare(java(type = "java.net.URI"))]
+    pub avatar: String,
+}
+
+#[typeshare]
+pub type UserId = String;
+
+#[typeshare]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+}
+
+#[typeshare]
+pub struct Empty {}
+
+#[typeshare]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Active,
+    /// Can't log in.
+    Suspended,
+    #[serde(rename = "gone")]
+    Deleted,
+}
+
+/// Something that happened to a user.
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event<T> {
+    Created(User),
+    Renamed {
+        from: String,
+        to: String,
+    },
+    Custom(T),
+    Deleted,
+}
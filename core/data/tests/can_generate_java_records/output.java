@@ -0,0 +1,78 @@
+package com.agilebits.onepassword;
+
+import com.fasterxml.jackson.annotation.JsonInclude;
+import com.fasterxml.jackson.annotation.JsonProperty;
+import com.fasterxml.jackson.annotation.JsonSubTypes;
+import com.fasterxml.jackson.annotation.JsonTypeInfo;
+import java.util.List;
+import java.util.Map;
+import javax.annotation.Nullable;
+
+public final class Accounts {
+    private Accounts() {}
+
+    public record Empty() {}
+
+    public record Page<T>(
+        @JsonProperty("items") List<T> items,
+        @JsonProperty("next") @Nullable String next
+    ) {}
+
+    public enum Status {
+        @JsonProperty("ACTIVE")
+        ACTIVE,
+        /**
+         * Can't log in.
+         */
+        @JsonProperty("SUSPENDED")
+        SUSPENDED,
+        @JsonProperty("gone")
+        DELETED
+    }
+
+    /**
+     * A user of the service.
+     *
+     * @param userId The user's identifier.
+     */
+    public record User(
+        @JsonProperty("userId") String userId,
+        @JsonProperty("displayName") String displayName,
+        @JsonProperty("email") @Nullable String email,
+        @JsonProperty("verified") @JsonInclude(JsonInclude.Include.NON_NULL) @Nullable Boolean verified,
+        @JsonProperty("age") short age,
+        @JsonProperty("tags") List<String> tags,
+        @JsonProperty("scores") Map<String, Double> scores,
+        @JsonProperty("status") Status status,
+        @JsonProperty("roles") @JsonInclude(JsonInclude.Include.NON_NULL) @Nullable List<Status> roles,
+        @JsonProperty("class") String class_,
+        @JsonProperty("avatar") java.net.URI avatar
+    ) {}
+
+    /**
+     * Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+     */
+    public record EventRenamedInner(
+        @JsonProperty("from") String from,
+        @JsonProperty("to") String to
+    ) {}
+
+    /**
+     * Something that happened to a user.
+     */
+    @JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.PROPERTY, property = "type")
+    @JsonSubTypes({
+        @JsonSubTypes.Type(value = Event.Created.class, name = "Created"),
+        @JsonSubTypes.Type(value = Event.Renamed.class, name = "Renamed"),
+        @JsonSubTypes.Type(value = Event.Custom.class, name = "Custom"),
+        @JsonSubTypes.Type(value = Event.Deleted.class, name = "Deleted")
+    })
+    public sealed interface Event<T> {
+        record Created<T>(@JsonProperty("content") User content) implements Event<T> {}
+        record Renamed<T>(@JsonProperty("content") EventRenamedInner content) implements Event<T> {}
+        record Custom<T>(@JsonProperty("content") T content) implements Event<T> {}
+        record Deleted<T>() implements Event<T> {}
+    }
+
+    public static final long MAX_SESSIONS = 5L;
+}
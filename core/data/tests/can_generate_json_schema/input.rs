@@ -0,0 +1,44 @@
+/// A user of the service.
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    /// The user's unique identifier.
+    pub user_id: u32,
+    pub display_name: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub scores: HashMap<String, f64>,
+    pub initial: char,
+    pub status: Status,
+    #[typeshare(
+        json_schema(type = "{\"type\": \"string\", \"format\": \"uri\"}"),
+        openapi(type = "{\"type\": \"string\", \"format\": \"uri\"}")
+    )]
+    pub avatar: String,
+}
+
+#[typeshare]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Active,
+    Suspended,
+    #[serde(rename = "gone")]
+    Deleted,
+}
+
+/// Something that happened to a user.
+#[typeshare]
+#[serde(tag = "type", content = "data")]
+pub enum Event {
+    Created(User),
+    Renamed {
+        /// The name before the change.
+        from: String,
+        to: String,
+    },
+    Deleted,
+}
+
+#[typeshare]
+pub type Users = Vec<User>;
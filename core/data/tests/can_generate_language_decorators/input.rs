@@ -0,0 +1,12 @@
+#[typeshare(
+    typescript = "Timestamped, Auditable",
+    python = "Auditable, frozen=True, json_schema_extra={'examples': [1, 2]}",
+    go = "Timestamps",
+    scala = "Auditable, Timestamped"
+)]
+pub struct Document {
+    title: String,
+}
+
+#[typeshare(scala = "Auditable")]
+pub struct Empty {}
@@ -0,0 +1,10 @@
+package proto
+
+import "encoding/json"
+
+type Document struct {
+	Timestamps
+	Title string `json:"title"`
+}
+type Empty struct {
+}
@@ -0,0 +1,12 @@
+from __future__ import annotations
+
+from pydantic import BaseModel, ConfigDict
+
+
+class Document(Auditable, BaseModel):
+    model_config = ConfigDict(frozen=True, json_schema_extra={'examples': [1, 2]})
+
+    title: str
+
+class Empty(BaseModel):
+    pass
@@ -0,0 +1,11 @@
+package com.agilebits
+
+package onepassword {
+
+case class Document (
+	title: String
+) extends Auditable with Timestamped
+
+class Empty extends Serializable with Auditable
+
+}
@@ -0,0 +1,7 @@
+export interface Document extends Timestamped, Auditable {
+	title: string;
+}
+
+export interface Empty {
+}
+
@@ -0,0 +1,42 @@
+/// A user of the service.
+#[typeshare]
+pub struct User {
+    #[typeshare(proto(tag = 1))]
+    pub id: UserId,
+    /// Shown to other users.
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub email: Option<String>,
+    #[typeshare(proto(tag = 2))]
+    pub created_at: u32,
+    pub tags: Vec<String>,
+    pub scores: HashMap<String, f64>,
+    pub nicknames: Option<Vec<String>>,
+    pub status: Status,
+    #[typeshare(proto(type = "bytes"))]
+    pub avatar: String,
+}
+
+#[typeshare]
+pub type UserId = String;
+
+#[typeshare]
+pub enum Status {
+    Active,
+    /// Can't log in.
+    Suspended,
+    Deleted,
+}
+
+/// Something that happened to a user.
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    Created(User),
+    Renamed {
+        from: String,
+        to: String,
+    },
+    Tagged(Vec<String>),
+    Deleted,
+}
@@ -0,0 +1,59 @@
+/// A user of the service.
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub user_id: UserId,
+    pub display_name: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    pub age: u8,
+    pub tags: Vec<String>,
+    pub scores: HashMap<u32, f64>,
+    pub roles: HashMap<Role, bool>,
+    pub status: Status,
+    pub last_seen: Option<OffsetDateTime>,
+    #[serde(rename = "x-trace")]
+    pub trace: [u16; 2],
+    #[typeshare(typescript(type = "Blob"))]
+    pub avatar: Vec<u8>,
+}
+
+#[typeshare]
+pub type UserId = String;
+
+#[typeshare]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+}
+
+#[typeshare]
+pub struct Empty {}
+
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Active,
+    Suspended,
+    #[serde(rename = "gone")]
+    Deleted,
+}
+
+/// Something that happened to a user.
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event<T> {
+    Created(User),
+    Renamed { from: String, to: Option<String> },
+    Custom(T),
+    Listed(Page<User>),
+    Paged(Page<Vec<T>>),
+    Deleted,
+}
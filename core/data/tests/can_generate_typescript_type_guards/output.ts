@@ -0,0 +1,193 @@
+export type UserId = string;
+
+export function isUserId(value: unknown): value is UserId {
+	return typeof value === "string";
+}
+
+export function assertUserId(value: unknown): asserts value is UserId {
+	if (!isUserId(value)) {
+		throw new TypeError("Expected a value of type UserId");
+	}
+}
+
+export interface Empty {
+}
+
+export function isEmpty(value: unknown): value is Empty {
+	if (typeof value !== "object" || value === null) {
+		return false;
+	}
+	return true;
+}
+
+export function assertEmpty(value: unknown): asserts value is Empty {
+	if (!isEmpty(value)) {
+		throw new TypeError("Expected a value of type Empty");
+	}
+}
+
+export interface Page<T> {
+	items: T[];
+	next?: string;
+}
+
+export function isPage<T>(value: unknown, isT: (value: unknown) => value is T): value is Page<T> {
+	if (typeof value !== "object" || value === null) {
+		return false;
+	}
+	const valueObject = value as Record<string, unknown>;
+	return Array.isArray(valueObject.items) && valueObject.items.every((e) => isT(e))
+		&& (valueObject.next == null || typeof valueObject.next === "string");
+}
+
+export function assertPage<T>(value: unknown, isT: (value: unknown) => value is T): asserts value is Page<T> {
+	if (!isPage(value, isT)) {
+		throw new TypeError("Expected a value of type Page");
+	}
+}
+
+export enum Role {
+	Admin = "Admin",
+	Member = "Member",
+}
+
+export function isRole(value: unknown): value is Role {
+	return value === "Admin" || value === "Member";
+}
+
+export function assertRole(value: unknown): asserts value is Role {
+	if (!isRole(value)) {
+		throw new TypeError("Expected a value of type Role");
+	}
+}
+
+export enum Status {
+	Active = "ACTIVE",
+	Suspended = "SUSPENDED",
+	Deleted = "gone",
+}
+
+export function isStatus(value: unknown): value is Status {
+	return value === "ACTIVE" || value === "SUSPENDED" || value === "gone";
+}
+
+export function assertStatus(value: unknown): asserts value is Status {
+	if (!isStatus(value)) {
+		throw new TypeError("Expected a value of type Status");
+	}
+}
+
+/** A user of the service. */
+export interface User {
+	userId: UserId;
+	displayName: string;
+	email?: string;
+	verified?: boolean;
+	age: number;
+	tags: string[];
+	scores: Record<number, number>;
+	roles: Record<Role, boolean>;
+	status: Status;
+	lastSeen?: Date;
+	"x-trace": [number, number];
+	avatar: Blob;
+}
+
+export function isUser(value: unknown): value is User {
+	if (typeof value !== "object" || value === null) {
+		return false;
+	}
+	const valueObject = value as Record<string, unknown>;
+	return isUserId(valueObject.userId)
+		&& typeof valueObject.displayName === "string"
+		&& (valueObject.email == null || typeof valueObject.email === "string")
+		&& (valueObject.verified === undefined || typeof valueObject.verified === "boolean")
+		&& typeof valueObject.age === "number"
+		&& Array.isArray(valueObject.tags) && valueObject.tags.every((e) => typeof e === "string")
+		&& typeof valueObject.scores === "object" && valueObject.scores !== null && !Array.isArray(valueObject.scores) && Object.values(valueObject.scores).every((e) => typeof e === "number")
+		&& typeof valueObject.roles === "object" && valueObject.roles !== null && !Array.isArray(valueObject.roles) && Object.entries(valueObject.roles).every(([k, e]) => isRole(k) && typeof e === "boolean")
+		&& isStatus(valueObject.status)
+		&& (valueObject.lastSeen == null || valueObject.lastSeen instanceof Date)
+		&& Array.isArray(valueObject["x-trace"]) && valueObject["x-trace"].length === 2 && valueObject["x-trace"].every((e) => typeof e === "number")
+		&& "avatar" in valueObject;
+}
+
+export function assertUser(value: unknown): asserts value is User {
+	if (!isUser(value)) {
+		throw new TypeError("Expected a value of type User");
+	}
+}
+
+/** Something that happened to a user. */
+export type Event<T> = 
+	| { type: "Created", content: User }
+	| { type: "Renamed", content: {
+	from: string;
+	to?: string;
+}}
+	| { type: "Custom", content: T }
+	| { type: "Listed", content: Page<User> }
+	| { type: "Paged", content: Page<T[]> }
+	| { type: "Deleted", content?: undefined };
+
+export function isEvent<T>(value: unknown, isT: (value: unknown) => value is T): value is Event<T> {
+	if (typeof value !== "object" || value === null) {
+		return false;
+	}
+	const valueObject = value as Record<string, unknown>;
+	switch (valueObject.type) {
+		case "Created": {
+			return isUser(valueObject.content);
+		}
+		case "Renamed": {
+			const content = valueObject.content;
+			if (typeof content !== "object" || content === null) {
+				return false;
+			}
+			const contentObject = content as Record<string, unknown>;
+			return typeof contentObject.from === "string"
+				&& (contentObject.to == null || typeof contentObject.to === "string");
+		}
+		case "Custom": {
+			return isT(valueObject.content);
+		}
+		case "Listed": {
+			return isPage(valueObject.content, isUser);
+		}
+		case "Paged": {
+			return isPage(valueObject.content, (value: unknown): value is T[] => Array.isArray(value) && value.every((e) => isT(e)));
+		}
+		case "Deleted": {
+			return true;
+		}
+		default: {
+			return false;
+		}
+	}
+}
+
+export function assertEvent<T>(value: unknown, isT: (value: unknown) => value is T): asserts value is Event<T> {
+	if (!isEvent(value, isT)) {
+		throw new TypeError("Expected a value of type Event");
+	}
+}
+
+/**
+ * Custom JSON reviver and replacer functions for dynamic data transformation
+ * ReviverFunc is used during JSON parsing to detect and transform specific data structures
+ * ReplacerFunc is used during JSON serialization to modify certain values before stringifying.
+ * These functions allow for flexible encoding and decoding of data, ensuring that complex types are properly handled when converting between TS objects and JSON
+ */
+export const ReviverFunc = (key: string, value: unknown): unknown => {
+    if (typeof value === "string" && /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z$/.test(value) && (key === "lastSeen")) {
+        return new Date(value);
+    }
+    return value;
+};
+
+export const ReplacerFunc = (key: string, value: unknown): unknown => {
+    if (value instanceof Date) {
+        return value.toISOString();
+    }
+    return value;
+};
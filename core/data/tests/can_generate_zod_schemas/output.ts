@@ -0,0 +1,80 @@
+export type UserId = string;
+
+export enum Status {
+	Active = "ACTIVE",
+	/** Can't log in. */
+	Suspended = "SUSPENDED",
+	Deleted = "gone",
+}
+
+/** A user of the service. */
+export interface User {
+	/** The user's identifier. */
+	userId: UserId;
+	displayName: string;
+	email?: string;
+	nickname?: string | null;
+	verified?: boolean;
+	age: number;
+	rating: number;
+	tags: string[];
+	scores: Record<number, number>;
+	status: Status;
+	homepage: string;
+	key: Uint8Array;
+	lastSeen?: Date;
+	contact: string;
+}
+
+/** A comment and the replies to it. */
+export interface Comment {
+	text: string;
+	author?: User;
+	replies: Comment[];
+}
+
+export interface Empty {
+}
+
+export interface Page<T> {
+	items: T[];
+	next?: string;
+}
+
+/** Something that happened to a user. */
+export type Event<T> = 
+	| { type: "Created", content: User }
+	| { type: "Renamed", content: {
+	from: string;
+	to: string;
+}}
+	| { type: "Custom", content: T }
+	| { type: "Listed", content: Page<User> }
+	| { type: "Deleted", content?: undefined };
+
+export const MAX_SESSIONS: number = 5;
+/**
+ * Custom JSON reviver and replacer functions for dynamic data transformation
+ * ReviverFunc is used during JSON parsing to detect and transform specific data structures
+ * ReplacerFunc is used during JSON serialization to modify certain values before stringifying.
+ * These functions allow for flexible encoding and decoding of data, ensuring that complex types are properly handled when converting between TS objects and JSON
+ */
+export const ReviverFunc = (key: string, value: unknown): unknown => {
+    if (typeof value === "string" && /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z$/.test(value) && (key === "lastSeen")) {
+        return new Date(value);
+    }
+    if (Array.isArray(value) && value.every(v => Number.isInteger(v) && v >= 0 && v <= 255) && value.length > 0)  {
+        return new Uint8Array(value);
+    }
+    return value;
+};
+
+export const ReplacerFunc = (key: string, value: unknown): unknown => {
+    if (value instanceof Date) {
+        return value.toISOString();
+    }
+    if (value instanceof Uint8Array) {
+        return Array.from(value);
+    }
+    return value;
+};
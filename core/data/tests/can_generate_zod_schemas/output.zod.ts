@@ -0,0 +1,70 @@
+import { z } from "zod";
+
+export const UserIdSchema = z.string();
+export type UserId = z.infer<typeof UserIdSchema>;
+
+export const StatusSchema = z.enum([
+	"ACTIVE",
+	/** Can't log in. */
+	"SUSPENDED",
+	"gone",
+]);
+export type Status = z.infer<typeof StatusSchema>;
+
+/** A user of the service. */
+export const UserSchema = z.object({
+	/** The user's identifier. */
+	userId: UserIdSchema,
+	displayName: z.string(),
+	email: z.string().nullish(),
+	nickname: z.string().nullable().nullish(),
+	verified: z.boolean().optional(),
+	age: z.number().int(),
+	rating: z.number(),
+	tags: z.array(z.string()),
+	scores: z.record(z.coerce.number(), z.number()),
+	status: StatusSchema,
+	homepage: z.string().url(),
+	key: z.array(z.number().int().min(0).max(255)).transform((bytes) => new Uint8Array(bytes)),
+	lastSeen: z.coerce.date().nullish(),
+	contact: z.string().email(),
+});
+export type User = z.infer<typeof UserSchema>;
+
+/** A comment and the replies to it. */
+export type Comment = {
+	text: string;
+	author?: User | null;
+	replies: Array<Comment>;
+};
+export const CommentSchema: z.ZodType<Comment, z.ZodTypeDef, unknown> = z.object({
+	text: z.string(),
+	author: UserSchema.nullish(),
+	replies: z.array(z.lazy(() => CommentSchema)),
+});
+
+export const EmptySchema = z.object({});
+export type Empty = z.infer<typeof EmptySchema>;
+
+export const PageSchema = <T extends z.ZodTypeAny>(tSchema: T) =>
+	z.object({
+		items: z.array(tSchema),
+		next: z.string().nullish(),
+	});
+export type Page<T> = z.infer<ReturnType<typeof PageSchema<z.ZodType<T>>>>;
+
+/** Something that happened to a user. */
+export const EventSchema = <T extends z.ZodTypeAny>(tSchema: T) =>
+	z.discriminatedUnion("type", [
+		z.object({ type: z.literal("Created"), content: UserSchema }),
+		z.object({ type: z.literal("Renamed"), content: z.object({
+			from: z.string(),
+			to: z.string(),
+		}) }),
+		z.object({ type: z.literal("Custom"), content: tSchema }),
+		z.object({ type: z.literal("Listed"), content: PageSchema(UserSchema) }),
+		z.object({ type: z.literal("Deleted") }),
+	]);
+export type Event<T> = z.infer<ReturnType<typeof EventSchema<z.ZodType<T>>>>;
+
+export const MAX_SESSIONS: number = 5;
@@ -0,0 +1,29 @@
+#[typeshare]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CfgAttrStruct {
+    field_one: String,
+    #[cfg_attr(feature = "serde", serde(rename = "renamedField"))]
+    field_two: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    skipped: u32,
+}
+
+#[typeshare]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "content"))]
+pub enum CfgAttrEnum {
+    First(String),
+    #[cfg_attr(feature = "serde", serde(rename = "second"))]
+    Second {
+        #[cfg_attr(feature = "serde", serde(rename = "renamedField"))]
+        field: u32,
+    },
+}
+
+#[cfg_attr(feature = "typeshare", typeshare)]
+pub struct OptionalTypeshare {
+    field: String,
+}
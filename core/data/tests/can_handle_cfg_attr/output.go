@@ -0,0 +1,83 @@
+package proto
+
+import "encoding/json"
+
+type CfgAttrStruct struct {
+	FieldOne string `json:"fieldOne"`
+	FieldTwo uint32 `json:"renamedField"`
+}
+type OptionalTypeshare struct {
+	Field string `json:"field"`
+}
+// Generated type representing the anonymous struct variant `Second` of the `CfgAttrEnum` Rust enum
+type CfgAttrEnumSecondInner struct {
+	Field uint32 `json:"renamedField"`
+}
+type CfgAttrEnumTypes string
+const (
+	CfgAttrEnumTypeVariantFirst CfgAttrEnumTypes = "First"
+	CfgAttrEnumTypeVariantSecond CfgAttrEnumTypes = "second"
+)
+type CfgAttrEnum struct{ 
+	Type CfgAttrEnumTypes `json:"type"`
+	content interface{}
+}
+
+func (c *CfgAttrEnum) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    CfgAttrEnumTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	c.Type = enum.Tag
+	switch c.Type {
+	case CfgAttrEnumTypeVariantFirst:
+		var res string
+		c.content = &res
+	case CfgAttrEnumTypeVariantSecond:
+		var res CfgAttrEnumSecondInner
+		c.content = &res
+
+	}
+	if err := json.Unmarshal(enum.Content, &c.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (c CfgAttrEnum) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    CfgAttrEnumTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = c.Type
+    enum.Content = c.content
+    return json.Marshal(enum)
+}
+
+func (c CfgAttrEnum) First() string {
+	res, _ := c.content.(*string)
+	return *res
+}
+func (c CfgAttrEnum) Second() *CfgAttrEnumSecondInner {
+	res, _ := c.content.(*CfgAttrEnumSecondInner)
+	return res
+}
+
+func NewCfgAttrEnumTypeVariantFirst(content string) CfgAttrEnum {
+    return CfgAttrEnum{
+        Type: CfgAttrEnumTypeVariantFirst,
+        content: &content,
+    }
+}
+func NewCfgAttrEnumTypeVariantSecond(content *CfgAttrEnumSecondInner) CfgAttrEnum {
+    return CfgAttrEnum{
+        Type: CfgAttrEnumTypeVariantSecond,
+        content: content,
+    }
+}
+
@@ -0,0 +1,32 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+
+@Serializable
+data class CfgAttrStruct (
+	val fieldOne: String,
+	val renamedField: UInt
+)
+
+@Serializable
+data class OptionalTypeshare (
+	val field: String
+)
+
+/// Generated type representing the anonymous struct variant `Second` of the `CfgAttrEnum` Rust enum
+@Serializable
+data class CfgAttrEnumSecondInner (
+	val renamedField: UInt
+)
+
+@Serializable
+sealed class CfgAttrEnum {
+	@Serializable
+	@SerialName("First")
+	data class First(val content: String): CfgAttrEnum()
+	@Serializable
+	@SerialName("second")
+	data class Second(val content: CfgAttrEnumSecondInner): CfgAttrEnum()
+}
+
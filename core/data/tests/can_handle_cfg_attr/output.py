@@ -0,0 +1,37 @@
+from __future__ import annotations
+
+from enum import Enum
+from pydantic import BaseModel, ConfigDict, Field
+from typing import Literal, Union
+
+
+class CfgAttrStruct(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    field_one: str = Field(alias="fieldOne")
+    field_two: int = Field(alias="renamedField")
+
+class OptionalTypeshare(BaseModel):
+    field: str
+
+class CfgAttrEnumSecondInner(BaseModel):
+    """
+    Generated type representing the anonymous struct variant `Second` of the `CfgAttrEnum` Rust enum
+    """
+    model_config = ConfigDict(populate_by_name=True)
+
+    field: int = Field(alias="renamedField")
+
+class CfgAttrEnumTypes(str, Enum):
+    FIRST = "First"
+    SECOND = "second"
+
+class CfgAttrEnumFirst(BaseModel):
+    type: Literal[CfgAttrEnumTypes.FIRST] = CfgAttrEnumTypes.FIRST
+    content: str
+
+class CfgAttrEnumSecond(BaseModel):
+    type: Literal[CfgAttrEnumTypes.SECOND] = CfgAttrEnumTypes.SECOND
+    content: CfgAttrEnumSecondInner
+
+CfgAttrEnum = Union[CfgAttrEnumFirst, CfgAttrEnumSecond]
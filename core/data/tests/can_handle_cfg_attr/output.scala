@@ -0,0 +1,39 @@
+package com.agilebits
+
+package object onepassword {
+
+type UByte = Byte
+type UShort = Short
+type UInt = Int
+type ULong = Int
+
+}
+package onepassword {
+
+case class CfgAttrStruct (
+	fieldOne: String,
+	renamedField: UInt
+)
+
+case class OptionalTypeshare (
+	field: String
+)
+
+// Generated type representing the anonymous struct variant `Second` of the `CfgAttrEnum` Rust enum
+case class CfgAttrEnumSecondInner (
+	renamedField: UInt
+)
+
+sealed trait CfgAttrEnum {
+	def serialName: String
+}
+object CfgAttrEnum {
+	case class First(content: String) extends CfgAttrEnum {
+		val serialName: String = "First"
+	}
+	case class Second(content: CfgAttrEnumSecondInner) extends CfgAttrEnum {
+		val serialName: String = "second"
+	}
+}
+
+}
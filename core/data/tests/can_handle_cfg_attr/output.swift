@@ -0,0 +1,73 @@
+import Foundation
+
+public struct CfgAttrStruct: Codable {
+	public let fieldOne: String
+	public let renamedField: UInt32
+
+	public init(fieldOne: String, renamedField: UInt32) {
+		self.fieldOne = fieldOne
+		self.renamedField = renamedField
+	}
+}
+
+public struct OptionalTypeshare: Codable {
+	public let field: String
+
+	public init(field: String) {
+		self.field = field
+	}
+}
+
+
+/// Generated type representing the anonymous struct variant `Second` of the `CfgAttrEnum` Rust enum
+public struct CfgAttrEnumSecondInner: Codable {
+	public let renamedField: UInt32
+
+	public init(renamedField: UInt32) {
+		self.renamedField = renamedField
+	}
+}
+public enum CfgAttrEnum: Codable {
+	case first(String)
+	case second(CfgAttrEnumSecondInner)
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case first = "First",
+			second
+	}
+
+	private enum ContainerCodingKeys: String, CodingKey {
+		case type, content
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+		if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+			switch type {
+			case .first:
+				if let content = try? container.decode(String.self, forKey: .content) {
+					self = .first(content)
+					return
+				}
+			case .second:
+				if let content = try? container.decode(CfgAttrEnumSecondInner.self, forKey: .content) {
+					self = .second(content)
+					return
+				}
+			}
+		}
+		throw DecodingError.typeMismatch(CfgAttrEnum.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for CfgAttrEnum"))
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+		switch self {
+		case .first(let content):
+			try container.encode(CodingKeys.first, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .second(let content):
+			try container.encode(CodingKeys.second, forKey: .type)
+			try container.encode(content, forKey: .content)
+		}
+	}
+}
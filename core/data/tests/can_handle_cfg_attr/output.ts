@@ -0,0 +1,15 @@
+export interface CfgAttrStruct {
+	fieldOne: string;
+	renamedField: number;
+}
+
+export interface OptionalTypeshare {
+	field: string;
+}
+
+export type CfgAttrEnum = 
+	| { type: "First", content: string }
+	| { type: "second", content: {
+	renamedField: number;
+}};
+
@@ -0,0 +1,7 @@
+#[typeshare]
+pub struct Team {
+    members: NonEmpty<Id<User>>,
+    leads: NonEmpty<NonEmpty<String>>,
+    scores: Pair<String, u32>,
+    created_by: Id<DateTime<Utc>>,
+}
@@ -0,0 +1,13 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+
+@Serializable
+data class Team (
+	val members: List<String>,
+	val leads: List<List<String>>,
+	val scores: Pair<String, UInt>,
+	val created_by: String
+)
+
@@ -0,0 +1,7 @@
+export interface Team {
+	members: Array<string>;
+	leads: Array<Array<string>>;
+	scores: [string, number];
+	created_by: string;
+}
+
@@ -0,0 +1,11 @@
+use accounts::Id;
+use url::Url;
+use uuid::Uuid;
+
+#[typeshare]
+pub struct Session {
+    id: Uuid,
+    account: Id,
+    callback: Url,
+    previous: Option<Vec<Uuid>>,
+}
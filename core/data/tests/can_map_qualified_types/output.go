@@ -0,0 +1,10 @@
+package proto
+
+import "encoding/json"
+
+type Session struct {
+	Id uuid.UUID `json:"id"`
+	Account int64 `json:"account"`
+	Callback string `json:"callback"`
+	Previous *[]uuid.UUID `json:"previous,omitempty"`
+}
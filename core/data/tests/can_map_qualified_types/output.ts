@@ -0,0 +1,7 @@
+export interface Session {
+	id: string;
+	account: AccountId;
+	callback: string;
+	previous?: string[];
+}
+
@@ -0,0 +1,25 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Record {
+    #[typeshare(swift(name = "kind"), kotlin(name = "kind"), go(name = "Kind"))]
+    r#type: String,
+    #[typeshare(go(name = "UserID"))]
+    user_id: String,
+    #[typeshare(swift(name = "isActive"))]
+    active: bool,
+}
+
+#[typeshare]
+pub enum Status {
+    #[typeshare(swift(name = "isOpen"), kotlin(name = "IsOpen"))]
+    Open,
+    Closed,
+}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    #[typeshare(swift(name = "created"), kotlin(name = "Created"))]
+    New(Record),
+    Deleted,
+}
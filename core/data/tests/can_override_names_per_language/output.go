@@ -0,0 +1,76 @@
+package proto
+
+import "encoding/json"
+
+type Record struct {
+	Kind string `json:"type"`
+	UserID string `json:"userId"`
+	Active bool `json:"active"`
+}
+type EventTypes string
+const (
+	EventTypeVariantNew EventTypes = "New"
+	EventTypeVariantDeleted EventTypes = "Deleted"
+)
+type Event struct{ 
+	Type EventTypes `json:"type"`
+	content interface{}
+}
+
+func (e *Event) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    EventTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	e.Type = enum.Tag
+	switch e.Type {
+	case EventTypeVariantNew:
+		var res Record
+		e.content = &res
+	case EventTypeVariantDeleted:
+		return nil
+
+	}
+	if err := json.Unmarshal(enum.Content, &e.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (e Event) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    EventTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = e.Type
+    enum.Content = e.content
+    return json.Marshal(enum)
+}
+
+func (e Event) New() *Record {
+	res, _ := e.content.(*Record)
+	return res
+}
+
+func NewEventTypeVariantNew(content *Record) Event {
+    return Event{
+        Type: EventTypeVariantNew,
+        content: content,
+    }
+}
+func NewEventTypeVariantDeleted() Event {
+    return Event{
+        Type: EventTypeVariantDeleted,
+    }
+}
+
+type Status string
+const (
+	StatusOpen Status = "Open"
+	StatusClosed Status = "Closed"
+)
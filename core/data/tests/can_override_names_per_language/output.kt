@@ -0,0 +1,33 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+
+@Serializable
+data class Record (
+	@SerialName("type")
+	val kind: String,
+	@SerialName("userId")
+	val userId: String,
+	@SerialName("active")
+	val active: Boolean
+)
+
+@Serializable
+sealed class Event {
+	@Serializable
+	@SerialName("New")
+	data class Created(val content: Record): Event()
+	@Serializable
+	@SerialName("Deleted")
+	object Deleted: Event()
+}
+
+@Serializable
+enum class Status(val string: String) {
+	@SerialName("Open")
+	IsOpen("Open"),
+	@SerialName("Closed")
+	Closed("Closed"),
+}
+
@@ -0,0 +1,66 @@
+import Foundation
+
+public struct Record: Codable {
+	public let kind: String
+	public let userId: String
+	public let isActive: Bool
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case kind = "type",
+			userId,
+			isActive = "active"
+	}
+
+	public init(kind: String, userId: String, isActive: Bool) {
+		self.kind = kind
+		self.userId = userId
+		self.isActive = isActive
+	}
+}
+
+public enum Event: Codable {
+	case created(Record)
+	case deleted
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case created = "New",
+			deleted = "Deleted"
+	}
+
+	private enum ContainerCodingKeys: String, CodingKey {
+		case type, content
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+		if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+			switch type {
+			case .created:
+				if let content = try? container.decode(Record.self, forKey: .content) {
+					self = .created(content)
+					return
+				}
+			case .deleted:
+				self = .deleted
+				return
+			}
+		}
+		throw DecodingError.typeMismatch(Event.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Event"))
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+		switch self {
+		case .created(let content):
+			try container.encode(CodingKeys.created, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .deleted:
+			try container.encode(CodingKeys.deleted, forKey: .type)
+		}
+	}
+}
+
+public enum Status: String, Codable {
+	case isOpen = "Open"
+	case closed = "Closed"
+}
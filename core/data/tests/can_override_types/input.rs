@@ -6,7 +6,8 @@ struct OverrideStruct {
         swift(type = "Int"),
         typescript(readonly, type = "any | undefined"),
         kotlin(type = "Int"), go(type = "uint"),
-        scala(type = "Short")
+        scala(type = "Short"),
+        python(type = "int")
     )]
     field_to_override: String,
 }
@@ -22,7 +23,8 @@ enum OverrideEnum {
             swift(type = "Int"),
             typescript(readonly, type = "any | undefined"),
             kotlin(type = "Int"), go(type = "uint"),
-            scala(type = "Short")
+            scala(type = "Short"),
+            python(type = "int")
         )]
         field_to_override: String
     }
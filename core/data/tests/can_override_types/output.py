@@ -0,0 +1,37 @@
+from __future__ import annotations
+
+from enum import Enum
+from pydantic import BaseModel, ConfigDict, Field
+from typing import Literal, Union
+
+
+class OverrideStruct(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    field_to_override: int = Field(alias="fieldToOverride")
+
+class OverrideEnumAnonymousStructVariantInner(BaseModel):
+    """
+    Generated type representing the anonymous struct variant `AnonymousStructVariant` of the `OverrideEnum` Rust enum
+    """
+    model_config = ConfigDict(populate_by_name=True)
+
+    field_to_override: int = Field(alias="fieldToOverride")
+
+class OverrideEnumTypes(str, Enum):
+    UNIT_VARIANT = "UnitVariant"
+    TUPLE_VARIANT = "TupleVariant"
+    ANONYMOUS_STRUCT_VARIANT = "AnonymousStructVariant"
+
+class OverrideEnumUnitVariant(BaseModel):
+    type: Literal[OverrideEnumTypes.UNIT_VARIANT] = OverrideEnumTypes.UNIT_VARIANT
+
+class OverrideEnumTupleVariant(BaseModel):
+    type: Literal[OverrideEnumTypes.TUPLE_VARIANT] = OverrideEnumTypes.TUPLE_VARIANT
+    content: str
+
+class OverrideEnumAnonymousStructVariant(BaseModel):
+    type: Literal[OverrideEnumTypes.ANONYMOUS_STRUCT_VARIANT] = OverrideEnumTypes.ANONYMOUS_STRUCT_VARIANT
+    content: OverrideEnumAnonymousStructVariantInner
+
+OverrideEnum = Union[OverrideEnumUnitVariant, OverrideEnumTupleVariant, OverrideEnumAnonymousStructVariant]
@@ -0,0 +1,469 @@
+//! Validation of the keys used in `#[typeshare(...)]` attributes.
+//!
+//! Unknown keys are silently ignored by the parser, so a typo like `serialised_as` would
+//! otherwise go unnoticed. Every key is checked against the item kinds it is supported on.
+use crate::{
+    language::SupportedLanguage,
+    parser::{parse_field_decorator_list, DecoratorKind, ParseError, SpannedError, TYPESHARE},
+};
+use quote::ToTokens;
+use std::fmt::{self, Display};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Attribute, Fields, ItemConst, ItemEnum, ItemStruct,
+    ItemType, Meta, Token,
+};
+
+/// The kind of item a typeshare attribute is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ItemKind {
+    Struct,
+    Enum,
+    Variant,
+    Field,
+    TypeAlias,
+    Const,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ItemKind::Struct => "structs",
+            ItemKind::Enum => "enums",
+            ItemKind::Variant => "enum variants",
+            ItemKind::Field => "fields",
+            ItemKind::TypeAlias => "type aliases",
+            ItemKind::Const => "constants",
+        })
+    }
+}
+
+/// How a key is written inside the attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyForm {
+    /// `#[typeshare(skip)]`
+    Word,
+    /// `#[typeshare(serialized_as = "String")]`
+    NameValue,
+    /// `#[typeshare(skip(typescript))]`
+    List,
+}
+
+impl KeyForm {
+    fn of(meta: &Meta) -> Option<Self> {
+        match meta {
+            Meta::Path(_) => Some(KeyForm::Word),
+            Meta::NameValue(_) => Some(KeyForm::NameValue),
+            Meta::List(_) => Some(KeyForm::List),
+        }
+    }
+
+    fn example(&self, key: &str) -> String {
+        match self {
+            KeyForm::Word => key.to_string(),
+            KeyForm::NameValue => format!("{key} = \"...\""),
+            KeyForm::List => format!("{key}(...)"),
+        }
+    }
+}
+
+/// A supported typeshare key.
+#[derive(Clone, Copy)]
+struct Key {
+    name: &'static str,
+    form: KeyForm,
+    kinds: &'static [ItemKind],
+}
+
+const TYPE_KINDS: &[ItemKind] = &[ItemKind::Struct, ItemKind::Enum, ItemKind::TypeAlias];
+
+const ALL_KINDS: &[ItemKind] = &[
+    ItemKind::Struct,
+    ItemKind::Enum,
+    ItemKind::Variant,
+    ItemKind::Field,
+    ItemKind::TypeAlias,
+    ItemKind::Const,
+];
+
+/// Every key supported directly inside `#[typeshare(...)]`, apart from the type level
+/// decorators listed by [`decorator_keys`]. Field level language lists like
+/// `typescript(readonly)` are checked separately by [`field_decorator_keys`].
+const KEYS: &[Key] = &[
+    Key {
+        name: "serialized_as",
+        form: KeyForm::NameValue,
+        kinds: &[
+            ItemKind::Struct,
+            ItemKind::Enum,
+            ItemKind::Field,
+            ItemKind::TypeAlias,
+            ItemKind::Const,
+        ],
+    },
+    Key {
+        name: "skip",
+        form: KeyForm::Word,
+        kinds: &[ItemKind::Variant, ItemKind::Field],
+    },
+    Key {
+        name: "skip",
+        form: KeyForm::List,
+        kinds: ALL_KINDS,
+    },
+    Key {
+        name: "lang",
+        form: KeyForm::NameValue,
+        kinds: ALL_KINDS,
+    },
+    Key {
+        name: "redacted",
+        form: KeyForm::Word,
+        kinds: TYPE_KINDS,
+    },
+];
+
+/// Type level decorators like `swift = "Equatable"`, one for every [`DecoratorKind`].
+fn decorator_keys() -> impl Iterator<Item = Key> {
+    DecoratorKind::all().map(|kind| Key {
+        name: kind.as_str(),
+        form: KeyForm::NameValue,
+        kinds: TYPE_KINDS,
+    })
+}
+
+/// Keys supported inside a field's or variant's language list, e.g. `typescript(readonly)`.
+fn field_decorator_keys(
+    language: SupportedLanguage,
+    kind: ItemKind,
+) -> &'static [(&'static str, KeyForm)] {
+    match (language, kind) {
+        (SupportedLanguage::Swift | SupportedLanguage::Kotlin, ItemKind::Variant) => {
+            &[("name", KeyForm::NameValue)]
+        }
+        (SupportedLanguage::Proto, ItemKind::Variant) => &[("tag", KeyForm::NameValue)],
+        (_, ItemKind::Variant) => &[],
+        (SupportedLanguage::TypeScript, _) => &[
+            ("readonly", KeyForm::Word),
+            ("type", KeyForm::NameValue),
+            ("zod", KeyForm::NameValue),
+        ],
+        (SupportedLanguage::Go, _) => &[
+            ("type", KeyForm::NameValue),
+            ("name", KeyForm::NameValue),
+            ("tag", KeyForm::NameValue),
+            ("omitempty", KeyForm::Word),
+            ("string", KeyForm::Word),
+        ],
+        (SupportedLanguage::Proto, _) => {
+            &[("type", KeyForm::NameValue), ("tag", KeyForm::NameValue)]
+        }
+        (SupportedLanguage::Avro, _) => &[
+            ("type", KeyForm::NameValue),
+            ("default", KeyForm::NameValue),
+        ],
+        (
+            SupportedLanguage::Swift
+            | SupportedLanguage::Kotlin
+            | SupportedLanguage::Java
+            | SupportedLanguage::CSharp
+            | SupportedLanguage::Dart
+            | SupportedLanguage::GraphQL,
+            _,
+        ) => &[("type", KeyForm::NameValue), ("name", KeyForm::NameValue)],
+        _ => &[("type", KeyForm::NameValue)],
+    }
+}
+
+/// Check the typeshare attributes of a struct and its fields.
+pub(crate) fn check_struct(s: &ItemStruct) -> Vec<SpannedError> {
+    let mut problems = check_attrs(&s.attrs, ItemKind::Struct);
+    check_fields(&s.fields, &mut problems);
+    problems
+}
+
+/// Check the typeshare attributes of an enum, its variants and their fields.
+pub(crate) fn check_enum(e: &ItemEnum) -> Vec<SpannedError> {
+    let mut problems = check_attrs(&e.attrs, ItemKind::Enum);
+    for variant in &e.variants {
+        problems.extend(check_attrs(&variant.attrs, ItemKind::Variant));
+        check_fields(&variant.fields, &mut problems);
+    }
+    problems
+}
+
+/// Check the typeshare attributes of a type alias.
+pub(crate) fn check_type_alias(t: &ItemType) -> Vec<SpannedError> {
+    check_attrs(&t.attrs, ItemKind::TypeAlias)
+}
+
+/// Check the typeshare attributes of a constant.
+pub(crate) fn check_const(c: &ItemConst) -> Vec<SpannedError> {
+    check_attrs(&c.attrs, ItemKind::Const)
+}
+
+fn check_fields(fields: &Fields, problems: &mut Vec<SpannedError>) {
+    for field in fields {
+        problems.extend(check_attrs(&field.attrs, ItemKind::Field));
+    }
+}
+
+fn check_attrs(attrs: &[Attribute], kind: ItemKind) -> Vec<SpannedError> {
+    let mut problems = Vec::new();
+
+    // A bare `#[typeshare]` has no keys to check.
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(TYPESHARE) && matches!(attr.meta, Meta::List(_)))
+    {
+        match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+            Ok(metas) => {
+                for meta in &metas {
+                    check_meta(meta, kind, &mut problems);
+                }
+            }
+            Err(err) => problems.push(SpannedError {
+                span: err.span(),
+                error: err.into(),
+            }),
+        }
+    }
+
+    problems
+}
+
+fn check_meta(meta: &Meta, kind: ItemKind, problems: &mut Vec<SpannedError>) {
+    let key = meta.path().to_token_stream().to_string();
+    let mut problem = |error| {
+        problems.push(SpannedError {
+            error,
+            span: meta.span(),
+        })
+    };
+
+    // Language lists like `typescript(readonly, type = "any")`.
+    if let (Meta::List(list), Ok(language)) = (meta, key.parse::<SupportedLanguage>()) {
+        if !matches!(kind, ItemKind::Field | ItemKind::Variant) {
+            return problem(ParseError::MisplacedAttribute {
+                key,
+                item_kind: kind.to_string(),
+            });
+        }
+        match parse_field_decorator_list(list) {
+            Ok(decorators) => {
+                for decorator in &decorators {
+                    check_field_decorator(language, kind, &key, decorator, problems);
+                }
+            }
+            Err(err) => problems.push(SpannedError {
+                span: err.span(),
+                error: err.into(),
+            }),
+        }
+        return;
+    }
+
+    let candidates = KEYS
+        .iter()
+        .copied()
+        .chain(decorator_keys())
+        .filter(|candidate| candidate.name == key)
+        .collect::<Vec<_>>();
+    let form = KeyForm::of(meta);
+
+    if candidates.is_empty() {
+        problem(ParseError::UnknownAttribute { key });
+    } else if !candidates.iter().any(|c| c.kinds.contains(&kind)) {
+        problem(ParseError::MisplacedAttribute {
+            key,
+            item_kind: kind.to_string(),
+        });
+    } else if !candidates
+        .iter()
+        .any(|c| c.kinds.contains(&kind) && Some(c.form) == form)
+    {
+        let expected = candidates
+            .iter()
+            .filter(|c| c.kinds.contains(&kind))
+            .map(|c| c.form.example(&key))
+            .collect::<Vec<_>>()
+            .join("` or `");
+        problem(ParseError::MalformedAttribute { expected, key });
+    }
+}
+
+fn check_field_decorator(
+    language: SupportedLanguage,
+    kind: ItemKind,
+    list_name: &str,
+    decorator: &Meta,
+    problems: &mut Vec<SpannedError>,
+) {
+    let name = decorator.path().to_token_stream().to_string();
+    let key = format!("{list_name}({name})");
+    let error = match field_decorator_keys(language, kind)
+        .iter()
+        .find(|(candidate, _)| *candidate == name)
+    {
+        None => ParseError::UnknownAttribute { key },
+        Some((_, form)) if Some(*form) != KeyForm::of(decorator) => {
+            ParseError::MalformedAttribute {
+                expected: format!("{list_name}({})", form.example(&name)),
+                key,
+            }
+        }
+        Some(_) => return,
+    };
+
+    problems.push(SpannedError {
+        error,
+        span: decorator.span(),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_enum, check_struct};
+    use crate::parser::ParseError;
+    use cool_asserts::assert_matches;
+
+    fn struct_problems(source: &str) -> Vec<ParseError> {
+        check_struct(&syn::parse_str(source).unwrap())
+            .into_iter()
+            .map(|problem| problem.error)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_attributes() {
+        assert!(struct_problems(
+            r#"
+            #[typeshare(swift = "Equatable", typescript = "Timestamped", redacted)]
+            pub struct Foo {
+                #[typeshare(serialized_as = "String")]
+                a: u64,
+                #[typeshare(typescript(readonly, type = "any"), kotlin(type = "Int"))]
+                b: u64,
+                #[typeshare(skip)]
+                c: u64,
+                #[typeshare(lang = "swift, kotlin")]
+                d: u64,
+                #[typeshare(skip(typescript, go))]
+                e: u64,
+                #[typeshare(swift(name = "kind"), go(name = "Kind"))]
+                r#type: u64,
+            }
+            "#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_unknown_attribute() {
+        assert_matches!(
+            struct_problems(
+                r#"
+                #[typeshare]
+                pub struct Foo {
+                    #[typeshare(serialised_as = "String")]
+                    a: u64,
+                }
+                "#
+            ).as_slice(),
+            [ParseError::UnknownAttribute { key }] => assert_eq!(key, "serialised_as")
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_decorator() {
+        assert_matches!(
+            struct_problems(
+                r#"
+                #[typeshare]
+                pub struct Foo {
+                    #[typeshare(typescript(readonyl))]
+                    a: u64,
+                }
+                "#
+            ).as_slice(),
+            [ParseError::UnknownAttribute { key }] => assert_eq!(key, "typescript(readonyl)")
+        );
+    }
+
+    #[test]
+    fn test_misplaced_attribute() {
+        assert_matches!(
+            struct_problems(
+                r#"
+                #[typeshare]
+                pub struct Foo {
+                    #[typeshare(redacted)]
+                    a: u64,
+                }
+                "#
+            ).as_slice(),
+            [ParseError::MisplacedAttribute { key, item_kind }] => {
+                assert_eq!(key, "redacted");
+                assert_eq!(item_kind, "fields");
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_attribute() {
+        assert_matches!(
+            check_enum(
+                &syn::parse_str(
+                    r#"
+                    #[typeshare(serialized_as, skip)]
+                    pub enum Foo {
+                        #[typeshare(skip = "true")]
+                        A,
+                    }
+                    "#
+                )
+                .unwrap()
+            )
+            .into_iter()
+            .map(|problem| problem.error)
+            .collect::<Vec<_>>()
+            .as_slice(),
+            [
+                ParseError::MalformedAttribute { key: enum_key, expected: enum_expected },
+                ParseError::MalformedAttribute { key: skip_key, expected: skip_expected },
+                ParseError::MalformedAttribute { key: variant_key, expected: variant_expected },
+            ] => {
+                assert_eq!(enum_key, "serialized_as");
+                assert_eq!(enum_expected, "serialized_as = \"...\"");
+                assert_eq!(skip_key, "skip");
+                assert_eq!(skip_expected, "skip(...)");
+                assert_eq!(variant_key, "skip");
+                assert_eq!(variant_expected, "skip` or `skip(...)");
+            }
+        );
+    }
+
+    #[test]
+    fn test_variant_decorators() {
+        assert_matches!(
+            check_enum(
+                &syn::parse_str(
+                    r#"
+                    #[typeshare]
+                    pub enum Foo {
+                        #[typeshare(swift(name = "first"), kotlin(name = "First"))]
+                        A,
+                        #[typeshare(typescript(readonly))]
+                        B,
+                    }
+                    "#
+                )
+                .unwrap()
+            )
+            .into_iter()
+            .map(|problem| problem.error)
+            .collect::<Vec<_>>()
+            .as_slice(),
+            [ParseError::UnknownAttribute { key }] => assert_eq!(key, "typescript(readonly)")
+        );
+    }
+}
@@ -0,0 +1,699 @@
+//! Evaluation of `#[cfg(...)]` predicates against a set of active cfg options.
+//!
+//! Every key, including `target_os`, is a leaf of the predicate that is either true, false or
+//! unknown. Keys that have no active options are unknown, so a type gated by
+//! `#[cfg(feature = "pro")]` is still generated unless some `feature` option is active, and
+//! flags are unknown until some flag is active. Unknown leaves are left out of `all` and
+//! `any`, and a predicate that is unknown as a whole doesn't filter anything.
+use crate::parser::get_meta_items;
+use log::{debug, log_enabled, warn};
+use quote::ToTokens;
+use std::collections::{BTreeMap, BTreeSet};
+use syn::{
+    punctuated::Punctuated, Attribute, Expr, ExprLit, Fields, ItemConst, ItemEnum, ItemStruct,
+    ItemType, Lit, Meta, Token,
+};
+
+/// The active cfg options typeshared items are evaluated against, e.g. `feature = "pro"`
+/// or `target_os = "ios"`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CfgOptions {
+    /// Active values of name-value options like `feature = "pro"`.
+    values: BTreeMap<String, BTreeSet<String>>,
+    /// Active flag options like `test`.
+    flags: BTreeSet<String>,
+}
+
+impl CfgOptions {
+    /// Options with only the given `target_os` values active.
+    pub fn from_target_os(target_os: &[String]) -> Self {
+        let mut options = Self::default();
+        for target in target_os {
+            options.insert("target_os", Some(target));
+        }
+        options
+    }
+
+    /// Activate a name-value option, or a flag option if `value` is `None`.
+    pub fn insert(&mut self, key: &str, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                self.values
+                    .entry(key.to_string())
+                    .or_default()
+                    .insert(value.to_string());
+            }
+            None => {
+                self.flags.insert(key.to_string());
+            }
+        }
+    }
+
+    /// Activate an option given as `key=value`, `key="value"` or `flag`.
+    pub fn insert_str(&mut self, option: &str) {
+        match option.split_once('=') {
+            Some((key, value)) => self.insert(key.trim(), Some(value.trim().trim_matches('"'))),
+            None => self.insert(option.trim(), None),
+        }
+    }
+
+    /// Are there no active options.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.flags.is_empty()
+    }
+
+    /// Evaluate a cfg predicate. Returns `None` if the predicate only refers to keys
+    /// without active options.
+    fn evaluate(&self, meta: &Meta) -> Option<bool> {
+        match meta {
+            Meta::Path(path) => {
+                let flag = path.get_ident()?.to_string();
+                (!self.flags.is_empty()).then(|| self.flags.contains(&flag))
+            }
+            Meta::NameValue(nv) => {
+                let key = nv.path.get_ident()?.to_string();
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Str(value),
+                    ..
+                }) = &nv.value
+                else {
+                    return None;
+                };
+                self.values
+                    .get(&key)
+                    .map(|values| values.contains(&value.value()))
+            }
+            Meta::List(list) => {
+                let nested = list
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .inspect_err(|err| warn!("Failed to parse cfg predicate: {err}"))
+                    .ok()?;
+                let mut results = nested.iter().filter_map(|meta| self.evaluate(meta));
+
+                if list.path.is_ident("all") {
+                    let results = results.collect::<Vec<_>>();
+                    (!results.is_empty()).then(|| results.into_iter().all(|result| result))
+                } else if list.path.is_ident("any") {
+                    let results = results.collect::<Vec<_>>();
+                    (!results.is_empty()).then(|| results.into_iter().any(|result| result))
+                } else if list.path.is_ident("not") {
+                    results.next().map(|result| !result)
+                } else {
+                    warn!("Unsupported cfg predicate: {}", list.path.to_token_stream());
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Should an item with the given attributes be generated. Rejected if any of its
+/// `#[cfg(...)]` predicates evaluates to false.
+pub(crate) fn accept_cfg(attrs: &[Attribute], cfg: &CfgOptions) -> bool {
+    if cfg.is_empty() {
+        return true;
+    }
+
+    !attrs
+        .iter()
+        .inspect(|attr| {
+            if log_enabled!(log::Level::Debug) {
+                debug!(
+                    "\tchecking attribute {} for {cfg:?} accept",
+                    attr.into_token_stream()
+                );
+            }
+        })
+        .flat_map(|attr| get_meta_items(attr, "cfg"))
+        .map(|meta| cfg.evaluate(&meta))
+        .inspect(|result| debug!("Evaluated {result:?}"))
+        .any(|result| result == Some(false))
+}
+
+/// Replace every `#[cfg_attr(predicate, attrs...)]` whose predicate holds with `attrs`, and
+/// drop the ones whose predicate doesn't. As with `#[cfg(...)]`, predicates that only refer
+/// to keys without active options hold.
+pub(crate) fn expand_cfg_attrs(attrs: &mut Vec<Attribute>, cfg: &CfgOptions) {
+    if !has_cfg_attr(attrs) {
+        return;
+    }
+
+    *attrs = std::mem::take(attrs)
+        .into_iter()
+        .flat_map(|attr| expand_cfg_attr(attr, cfg))
+        .collect();
+}
+
+fn has_cfg_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("cfg_attr"))
+}
+
+fn expand_cfg_attr(attr: Attribute, cfg: &CfgOptions) -> Vec<Attribute> {
+    if !attr.path().is_ident("cfg_attr") {
+        return vec![attr];
+    }
+
+    let Ok(metas) = attr
+        .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .inspect_err(|err| warn!("Failed to parse cfg_attr: {err}"))
+    else {
+        return Vec::new();
+    };
+    let mut metas = metas.into_iter();
+
+    match metas.next() {
+        Some(predicate) if cfg.evaluate(&predicate) != Some(false) => {
+            metas
+                // `cfg_attr` can be nested.
+                .flat_map(|meta| {
+                    expand_cfg_attr(
+                        Attribute {
+                            meta,
+                            ..attr.clone()
+                        },
+                        cfg,
+                    )
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Items whose `cfg_attr` attributes, including those of their fields and variants, can be
+/// expanded before parsing.
+pub(crate) trait ExpandCfgAttrs {
+    /// Does the item, or any of its fields or variants, have a `cfg_attr` attribute.
+    fn has_cfg_attrs(&self) -> bool;
+
+    /// Expand all `cfg_attr` attributes with [`expand_cfg_attrs`].
+    fn expand_cfg_attrs(&mut self, cfg: &CfgOptions);
+}
+
+fn fields_have_cfg_attrs(fields: &Fields) -> bool {
+    fields.iter().any(|field| has_cfg_attr(&field.attrs))
+}
+
+fn expand_fields(fields: &mut Fields, cfg: &CfgOptions) {
+    for field in fields.iter_mut() {
+        expand_cfg_attrs(&mut field.attrs, cfg);
+    }
+}
+
+impl ExpandCfgAttrs for ItemStruct {
+    fn has_cfg_attrs(&self) -> bool {
+        has_cfg_attr(&self.attrs) || fields_have_cfg_attrs(&self.fields)
+    }
+
+    fn expand_cfg_attrs(&mut self, cfg: &CfgOptions) {
+        expand_cfg_attrs(&mut self.attrs, cfg);
+        expand_fields(&mut self.fields, cfg);
+    }
+}
+
+impl ExpandCfgAttrs for ItemEnum {
+    fn has_cfg_attrs(&self) -> bool {
+        has_cfg_attr(&self.attrs)
+            || self.variants.iter().any(|variant| {
+                has_cfg_attr(&variant.attrs) || fields_have_cfg_attrs(&variant.fields)
+            })
+    }
+
+    fn expand_cfg_attrs(&mut self, cfg: &CfgOptions) {
+        expand_cfg_attrs(&mut self.attrs, cfg);
+        for variant in self.variants.iter_mut() {
+            expand_cfg_attrs(&mut variant.attrs, cfg);
+            expand_fields(&mut variant.fields, cfg);
+        }
+    }
+}
+
+impl ExpandCfgAttrs for ItemType {
+    fn has_cfg_attrs(&self) -> bool {
+        has_cfg_attr(&self.attrs)
+    }
+
+    fn expand_cfg_attrs(&mut self, cfg: &CfgOptions) {
+        expand_cfg_attrs(&mut self.attrs, cfg);
+    }
+}
+
+impl ExpandCfgAttrs for ItemConst {
+    fn has_cfg_attrs(&self) -> bool {
+        has_cfg_attr(&self.attrs)
+    }
+
+    fn expand_cfg_attrs(&mut self, cfg: &CfgOptions) {
+        expand_cfg_attrs(&mut self.attrs, cfg);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{accept_cfg, CfgOptions, ExpandCfgAttrs};
+    use flexi_logger::DeferredNow;
+    use log::Record;
+    use quote::ToTokens;
+    use std::{io::Write, sync::Once};
+    use syn::{parse_quote, ItemEnum, ItemStruct};
+
+    static INIT: Once = Once::new();
+
+    fn accept_target_os(attrs: &[syn::Attribute], target_os: &[String]) -> bool {
+        accept_cfg(attrs, &CfgOptions::from_target_os(target_os))
+    }
+
+    fn accept_options(attrs: &[syn::Attribute], options: &[&str]) -> bool {
+        let mut cfg = CfgOptions::default();
+        for option in options {
+            cfg.insert_str(option);
+        }
+        accept_cfg(attrs, &cfg)
+    }
+
+    fn init_log() {
+        INIT.call_once(|| {
+            flexi_logger::Logger::try_with_env()
+                .unwrap()
+                .format(
+                    |write: &mut dyn Write, _now: &mut DeferredNow, record: &Record<'_>| {
+                        let file_name = record.file().unwrap_or_default();
+                        let file_name = if file_name.len() > 15 {
+                            let split = file_name.len() - 15;
+                            &file_name[split..]
+                        } else {
+                            file_name
+                        };
+                        write!(
+                            write,
+                            "{file_name:>15}{:>5} - {}",
+                            record.line().unwrap_or_default(),
+                            record.args()
+                        )
+                    },
+                )
+                .start()
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn test_target_os_nested_reject() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(all(feature = "my-feature", not(target_os = "ios")))]
+            pub struct NestedNotTarget;
+        };
+
+        assert!(!accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_target_os_accept() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(target_os = "android")]
+            pub struct NestedNotTarget;
+        };
+
+        assert!(accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_target_os_combined_any_accepted() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(any(target_os = "android", target_os = "ios"))]
+            pub struct NestedNotTarget;
+        };
+
+        assert!(accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_target_os_combined_all() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(all(target_os = "windows", target_os = "android"))]
+            pub struct NestedNotTarget;
+        };
+
+        // All targets are active at once, like any other option.
+        assert!(!accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+        assert!(accept_target_os(
+            &test_struct.attrs,
+            &["windows".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_target_os_combined_rejected() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(not(any(target_os = "wasm32", target_os = "ios")))]
+            pub struct NestedNotTarget;
+        };
+
+        assert!(!accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_accept_no_target_os() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(feature = "my-feature")]
+            pub struct NestedNotTarget;
+        };
+
+        assert!(accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_accept_no_attribute() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            pub struct NestedNotTarget;
+        };
+
+        assert!(accept_target_os(
+            &test_struct.attrs,
+            &["ios".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_accept_none_excluded() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(not(any(target_os = "wasm32", target_os = "ios")))]
+            pub struct Excluded;
+        };
+
+        assert!(accept_target_os(
+            &test_struct.attrs,
+            &["macos".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_reject_not_target_os() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(target_os = "ios")]
+            pub struct Excluded;
+        };
+
+        assert!(!accept_target_os(
+            &test_struct.attrs,
+            &["macos".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_any_target_not_target_os() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(any(target_os = "ios", feature = "test"))]
+            pub struct Excluded;
+        };
+
+        assert!(!accept_target_os(
+            &test_struct.attrs,
+            &["macos".into(), "android".into()]
+        ));
+    }
+
+    #[test]
+    fn test_not_scope() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(all(not(feature = "my-feature"), target_os = "android"))]
+            pub struct Test;
+        };
+
+        assert!(accept_target_os(&test_struct.attrs, &["android".into()]))
+    }
+
+    #[test]
+    fn test_not_scope_reverse() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(all(target_os = "android", not(feature = "my-feature")))]
+            pub struct Test;
+        };
+
+        assert!(accept_target_os(&test_struct.attrs, &["android".into()]))
+    }
+
+    #[test]
+    fn test_enum_no_target_os_enabled() {
+        init_log();
+
+        let test_enum: ItemEnum = parse_quote! {
+            #[typeshare]
+            pub enum TestEnum {
+                #[cfg(target_os = "ios")]
+                Variant1,
+                #[cfg(target_os = "android")]
+                Variant2,
+            }
+        };
+
+        let variants = test_enum
+            .variants
+            .iter()
+            .map(|v| accept_target_os(&v.attrs, &[]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(&variants, &[true, true]);
+    }
+
+    #[test]
+    fn test_feature_accept_and_reject() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(feature = "pro")]
+            pub struct Pro;
+        };
+
+        assert!(accept_options(&test_struct.attrs, &["feature=pro"]));
+        assert!(accept_options(&test_struct.attrs, &["feature=\"pro\""]));
+        assert!(!accept_options(&test_struct.attrs, &["feature=free"]));
+        // Features are not constrained if none are active.
+        assert!(accept_options(&test_struct.attrs, &["target_arch=wasm32"]));
+    }
+
+    #[test]
+    fn test_combined_predicates() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(all(
+                any(target_family = "unix", target_arch = "wasm32"),
+                not(feature = "legacy")
+            ))]
+            pub struct Combined;
+        };
+
+        assert!(accept_options(
+            &test_struct.attrs,
+            &["target_family=unix", "feature=pro"]
+        ));
+        assert!(accept_options(&test_struct.attrs, &["target_arch=wasm32"]));
+        assert!(!accept_options(
+            &test_struct.attrs,
+            &["target_family=windows", "target_arch=x86_64"]
+        ));
+        assert!(!accept_options(
+            &test_struct.attrs,
+            &["target_family=unix", "feature=legacy"]
+        ));
+    }
+
+    #[test]
+    fn test_flags() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(not(test))]
+            pub struct NotTest;
+        };
+
+        assert!(accept_options(&test_struct.attrs, &["feature=pro"]));
+        assert!(!accept_options(&test_struct.attrs, &["test"]));
+    }
+
+    #[test]
+    fn test_flags_are_false_once_any_flag_is_active() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(test)]
+            pub struct TestOnly;
+        };
+
+        assert!(accept_options(&test_struct.attrs, &["feature=pro"]));
+        assert!(accept_options(&test_struct.attrs, &["test"]));
+        assert!(!accept_options(&test_struct.attrs, &["debug_assertions"]));
+    }
+
+    #[test]
+    fn test_mixed_target_os_predicates() {
+        init_log();
+
+        let any: ItemStruct = parse_quote! {
+            #[cfg(any(feature = "a", target_os = "ios"))]
+            pub struct Any;
+        };
+        assert!(accept_options(
+            &any.attrs,
+            &["feature=a", "target_os=android"]
+        ));
+        assert!(accept_options(&any.attrs, &["feature=b", "target_os=ios"]));
+        assert!(!accept_options(
+            &any.attrs,
+            &["feature=b", "target_os=android"]
+        ));
+
+        let all: ItemStruct = parse_quote! {
+            #[cfg(all(feature = "a", not(target_os = "ios")))]
+            pub struct All;
+        };
+        assert!(accept_options(
+            &all.attrs,
+            &["feature=a", "target_os=android"]
+        ));
+        assert!(!accept_options(&all.attrs, &["feature=a", "target_os=ios"]));
+        assert!(!accept_options(
+            &all.attrs,
+            &["feature=b", "target_os=android"]
+        ));
+        // Without any feature, only the target is checked.
+        assert!(accept_options(&all.attrs, &["target_os=android"]));
+
+        let not: ItemStruct = parse_quote! {
+            #[cfg(not(any(test, target_os = "ios")))]
+            pub struct Not;
+        };
+        assert!(accept_options(
+            &not.attrs,
+            &["debug_assertions", "target_os=android"]
+        ));
+        assert!(!accept_options(&not.attrs, &["test", "target_os=android"]));
+        assert!(!accept_options(&not.attrs, &["target_os=ios"]));
+
+        // `--target-os` targets are evaluated the same way.
+        let mut cfg = CfgOptions::from_target_os(&["android".into()]);
+        cfg.insert_str("feature=a");
+        assert!(accept_cfg(&any.attrs, &cfg));
+    }
+
+    #[test]
+    fn test_multiple_cfg_attributes() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg(feature = "pro")]
+            #[cfg(target_os = "ios")]
+            pub struct Both;
+        };
+
+        assert!(accept_options(
+            &test_struct.attrs,
+            &["feature=pro", "target_os=ios"]
+        ));
+        assert!(!accept_options(
+            &test_struct.attrs,
+            &["feature=pro", "target_os=android"]
+        ));
+    }
+
+    fn expanded_attrs(mut item: ItemStruct, options: &[&str]) -> Vec<String> {
+        let mut cfg = CfgOptions::default();
+        for option in options {
+            cfg.insert_str(option);
+        }
+        item.expand_cfg_attrs(&cfg);
+        item.attrs
+            .iter()
+            .map(|attr| attr.meta.to_token_stream().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_cfg_attr() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[typeshare]
+            #[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+            #[cfg_attr(feature = "pro", serde(tag = "type"))]
+            pub struct Expanded;
+        };
+
+        assert_eq!(
+            expanded_attrs(test_struct.clone(), &[]),
+            [
+                "typeshare",
+                "derive (Serialize)",
+                "serde (rename_all = \"camelCase\")",
+                "serde (tag = \"type\")"
+            ]
+        );
+        assert_eq!(
+            expanded_attrs(test_struct, &["feature=serde"]),
+            [
+                "typeshare",
+                "derive (Serialize)",
+                "serde (rename_all = \"camelCase\")"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_nested_cfg_attr() {
+        init_log();
+
+        let test_struct: ItemStruct = parse_quote! {
+            #[cfg_attr(feature = "serde", cfg_attr(target_os = "ios", typeshare))]
+            pub struct Nested;
+        };
+
+        assert_eq!(
+            expanded_attrs(test_struct.clone(), &["feature=serde", "target_os=ios"]),
+            ["typeshare"]
+        );
+        assert!(expanded_attrs(test_struct, &["feature=serde", "target_os=android"]).is_empty());
+    }
+}
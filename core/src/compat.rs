@@ -0,0 +1,794 @@
+//! Wire compatibility checks between two versions of the typeshared types.
+//!
+//! Types, fields and variants are matched by their serialized name, since that is what ends
+//! up on the wire, so renaming a Rust item without changing its serialized name is not a
+//! change. Types defined with the same name in several crates are matched by crate and Rust
+//! name instead, and type aliases are resolved before types are compared. Every change is
+//! classified as compatible or breaking for clients that were built against the base
+//! version, which may keep talking to the head version for a long time. A change is breaking
+//! if those clients can't read what the head version writes, or the head version can't read
+//! what they write.
+use crate::{
+    language::CrateName,
+    parser::ParsedData,
+    rust_types::{
+        Id, RustEnum, RustEnumVariant, RustField, RustStruct, RustType, RustTypeAlias,
+        SpecialRustType,
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+};
+
+/// A change to a typeshared type between two versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The serialized name of the changed type, or `Enum::Variant` for the fields of an
+    /// anonymous struct variant. Prefixed with the crate name, e.g. `crate::Type`, if the
+    /// name is defined in several crates.
+    pub item: String,
+    /// What changed.
+    pub kind: ChangeKind,
+}
+
+/// The kinds of changes between two versions of a type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// A new type.
+    TypeAdded,
+    /// A type that no longer exists.
+    TypeRemoved,
+    /// A type that changed between a struct, a unit enum, an algebraic enum or an alias.
+    ShapeChanged {
+        /// The shape in the base version.
+        base: &'static str,
+        /// The shape in the head version.
+        head: &'static str,
+    },
+    /// A new field.
+    FieldAdded {
+        /// The serialized field name.
+        field: String,
+        /// Whether the field is neither optional nor has a default.
+        required: bool,
+    },
+    /// A field that no longer exists.
+    FieldRemoved {
+        /// The serialized field name.
+        field: String,
+        /// Whether the field was neither optional nor had a default.
+        required: bool,
+    },
+    /// A field with a different type.
+    FieldTypeChanged {
+        /// The serialized field name.
+        field: String,
+        /// The type in the base version.
+        base: String,
+        /// The type in the head version.
+        head: String,
+    },
+    /// A new enum variant.
+    VariantAdded {
+        /// The serialized variant name.
+        variant: String,
+    },
+    /// An enum variant that no longer exists.
+    VariantRemoved {
+        /// The serialized variant name.
+        variant: String,
+    },
+    /// An enum variant with different content.
+    VariantTypeChanged {
+        /// The serialized variant name.
+        variant: String,
+        /// The content in the base version.
+        base: String,
+        /// The content in the head version.
+        head: String,
+    },
+    /// A renamed `#[serde(tag = "...")]` or `#[serde(content = "...")]` key.
+    TagRenamed {
+        /// The serialized key in the base version.
+        base: String,
+        /// The serialized key in the head version.
+        head: String,
+    },
+    /// A type alias with a different type.
+    AliasTypeChanged {
+        /// The type in the base version.
+        base: String,
+        /// The type in the head version.
+        head: String,
+    },
+}
+
+impl ChangeKind {
+    /// Whether clients built against the base version can no longer talk to the head version.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            ChangeKind::TypeAdded => false,
+            ChangeKind::FieldAdded { required, .. } | ChangeKind::FieldRemoved { required, .. } => {
+                *required
+            }
+            ChangeKind::TypeRemoved
+            | ChangeKind::ShapeChanged { .. }
+            | ChangeKind::FieldTypeChanged { .. }
+            // Old clients fail to decode variants they don't know.
+            | ChangeKind::VariantAdded { .. }
+            | ChangeKind::VariantRemoved { .. }
+            | ChangeKind::VariantTypeChanged { .. }
+            | ChangeKind::TagRenamed { .. }
+            | ChangeKind::AliasTypeChanged { .. } => true,
+        }
+    }
+
+    /// The name of the change, used in machine readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChangeKind::TypeAdded => "TypeAdded",
+            ChangeKind::TypeRemoved => "TypeRemoved",
+            ChangeKind::ShapeChanged { .. } => "ShapeChanged",
+            ChangeKind::FieldAdded { .. } => "FieldAdded",
+            ChangeKind::FieldRemoved { .. } => "FieldRemoved",
+            ChangeKind::FieldTypeChanged { .. } => "FieldTypeChanged",
+            ChangeKind::VariantAdded { .. } => "VariantAdded",
+            ChangeKind::VariantRemoved { .. } => "VariantRemoved",
+            ChangeKind::VariantTypeChanged { .. } => "VariantTypeChanged",
+            ChangeKind::TagRenamed { .. } => "TagRenamed",
+            ChangeKind::AliasTypeChanged { .. } => "AliasTypeChanged",
+        }
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let required = |required: &bool| if *required { "required" } else { "optional" };
+        match self {
+            ChangeKind::TypeAdded => write!(f, "added type"),
+            ChangeKind::TypeRemoved => write!(f, "removed type"),
+            ChangeKind::ShapeChanged { base, head } => {
+                write!(f, "changed from {base} to {head}")
+            }
+            ChangeKind::FieldAdded {
+                field,
+                required: is_required,
+            } => write!(f, "added {} field `{field}`", required(is_required)),
+            ChangeKind::FieldRemoved {
+                field,
+                required: is_required,
+            } => write!(f, "removed {} field `{field}`", required(is_required)),
+            ChangeKind::FieldTypeChanged { field, base, head } => {
+                write!(
+                    f,
+                    "changed type of field `{field}` from `{base}` to `{head}`"
+                )
+            }
+            ChangeKind::VariantAdded { variant } => write!(f, "added variant `{variant}`"),
+            ChangeKind::VariantRemoved { variant } => write!(f, "removed variant `{variant}`"),
+            ChangeKind::VariantTypeChanged {
+                variant,
+                base,
+                head,
+            } => write!(
+                f,
+                "changed content of variant `{variant}` from {base} to {head}"
+            ),
+            ChangeKind::TagRenamed { base, head } => {
+                write!(f, "renamed tag `{base}` to `{head}`")
+            }
+            ChangeKind::AliasTypeChanged { base, head } => {
+                write!(f, "changed aliased type from `{base}` to `{head}`")
+            }
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.item, self.kind)
+    }
+}
+
+/// A typeshared type that can appear on the wire.
+#[derive(Clone, Copy)]
+enum WireType<'a> {
+    Struct(&'a RustStruct),
+    Enum(&'a RustEnum),
+    Alias(&'a RustTypeAlias),
+}
+
+impl<'a> WireType<'a> {
+    fn shape(&self) -> &'static str {
+        match self {
+            WireType::Struct(_) => "a struct",
+            WireType::Enum(RustEnum::Unit(_)) => "a unit enum",
+            WireType::Enum(RustEnum::Algebraic { .. }) => "an algebraic enum",
+            WireType::Alias(_) => "a type alias",
+        }
+    }
+
+    fn id(&self) -> &'a Id {
+        match self {
+            WireType::Struct(s) => &s.id,
+            WireType::Enum(e) => &e.shared().id,
+            WireType::Alias(a) => &a.id,
+        }
+    }
+}
+
+/// A wire type and the crate it is defined in.
+type CrateWireType<'a> = (&'a CrateName, WireType<'a>);
+
+/// Collect the wire types of every crate by their serialized name. A name can be defined
+/// in several crates.
+fn wire_types(
+    parsed_data: &BTreeMap<CrateName, ParsedData>,
+) -> BTreeMap<&str, Vec<CrateWireType<'_>>> {
+    let mut wire_types = BTreeMap::<_, Vec<_>>::new();
+    for (crate_name, data) in parsed_data {
+        let structs = data.structs.iter().map(WireType::Struct);
+        let enums = data.enums.iter().map(WireType::Enum);
+        let aliases = data.aliases.iter().map(WireType::Alias);
+        for wire_type in structs.chain(enums).chain(aliases) {
+            wire_types
+                .entry(wire_type.id().renamed.as_str())
+                .or_default()
+                .push((crate_name, wire_type));
+        }
+    }
+    wire_types
+}
+
+/// The type aliases of both versions by Rust name.
+struct Aliases<'a> {
+    base: HashMap<&'a str, &'a RustTypeAlias>,
+    head: HashMap<&'a str, &'a RustTypeAlias>,
+}
+
+impl<'a> Aliases<'a> {
+    fn new(
+        base: &'a BTreeMap<CrateName, ParsedData>,
+        head: &'a BTreeMap<CrateName, ParsedData>,
+    ) -> Self {
+        let aliases = |parsed_data: &'a BTreeMap<CrateName, ParsedData>| {
+            parsed_data
+                .values()
+                .flat_map(|data| &data.aliases)
+                .map(|alias| (alias.id.original.as_str(), alias))
+                .collect()
+        };
+        Self {
+            base: aliases(base),
+            head: aliases(head),
+        }
+    }
+
+    /// Whether a type in the base version and a type in the head version are the same on
+    /// the wire, once their aliases are resolved.
+    fn same_type(&self, base: &RustType, head: &RustType) -> bool {
+        resolve(base, &self.base) == resolve(head, &self.head)
+    }
+}
+
+/// A type with every type alias in it replaced by the type it aliases.
+fn resolve(ty: &RustType, aliases: &HashMap<&str, &RustTypeAlias>) -> RustType {
+    let resolved = |ty: &RustType| Box::new(resolve(ty, aliases));
+    match ty {
+        RustType::Simple { id } | RustType::Generic { id, .. }
+            if aliases.contains_key(id.as_str()) =>
+        {
+            let alias = aliases[id.as_str()];
+            let arguments = ty.parameters().cloned().collect::<Vec<_>>();
+            resolve(
+                &alias.r#type.substitute(&alias.generic_types, &arguments),
+                aliases,
+            )
+        }
+        RustType::Simple { .. } => ty.clone(),
+        RustType::Generic { id, parameters } => RustType::Generic {
+            id: id.clone(),
+            parameters: parameters.iter().map(|p| resolve(p, aliases)).collect(),
+        },
+        RustType::Special(special) => RustType::Special(match special {
+            SpecialRustType::Vec(rtype) => SpecialRustType::Vec(resolved(rtype)),
+            SpecialRustType::Array(rtype, len) => SpecialRustType::Array(resolved(rtype), *len),
+            SpecialRustType::Slice(rtype) => SpecialRustType::Slice(resolved(rtype)),
+            SpecialRustType::Option(rtype) => SpecialRustType::Option(resolved(rtype)),
+            SpecialRustType::HashMap(rtype1, rtype2) => {
+                SpecialRustType::HashMap(resolved(rtype1), resolved(rtype2))
+            }
+            other => other.clone(),
+        }),
+    }
+}
+
+/// Compare the typeshared types of two versions and list every change, sorted by type name.
+pub fn diff<'a>(
+    base: &'a BTreeMap<CrateName, ParsedData>,
+    head: &'a BTreeMap<CrateName, ParsedData>,
+) -> Vec<Change> {
+    let aliases = Aliases::new(base, head);
+    let base = wire_types(base);
+    let head = wire_types(head);
+    let mut changes = Vec::new();
+
+    let mut names = base.keys().chain(head.keys()).copied().collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+
+    for name in names {
+        let base = base.get(name).map(Vec::as_slice).unwrap_or_default();
+        let head = head.get(name).map(Vec::as_slice).unwrap_or_default();
+        match (base, head) {
+            ([], [(_, head)]) => diff_type(name, None, Some(*head), &aliases, &mut changes),
+            ([(_, base)], []) => diff_type(name, Some(*base), None, &aliases, &mut changes),
+            ([(_, base)], [(_, head)]) => {
+                diff_type(name, Some(*base), Some(*head), &aliases, &mut changes)
+            }
+            _ => {
+                // The name is defined in several crates, match the definitions by crate
+                // and Rust name.
+                let mut keys = base
+                    .iter()
+                    .chain(head)
+                    .map(|(crate_name, wire_type)| (*crate_name, &wire_type.id().original))
+                    .collect::<Vec<_>>();
+                keys.sort_unstable();
+                keys.dedup();
+
+                for (crate_name, original) in keys {
+                    let find = |wire_types: &[CrateWireType<'a>]| {
+                        wire_types
+                            .iter()
+                            .find(|(c, wire_type)| {
+                                *c == crate_name && &wire_type.id().original == original
+                            })
+                            .map(|(_, wire_type)| *wire_type)
+                    };
+                    diff_type(
+                        &format!("{crate_name}::{name}"),
+                        find(base),
+                        find(head),
+                        &aliases,
+                        &mut changes,
+                    );
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+/// Compare two versions of a type, either of which may not exist.
+fn diff_type(
+    name: &str,
+    base: Option<WireType<'_>>,
+    head: Option<WireType<'_>>,
+    aliases: &Aliases<'_>,
+    changes: &mut Vec<Change>,
+) {
+    let mut kinds = Vec::new();
+    let mut variant_changes = Vec::new();
+    match (base, head) {
+        (Some(_), None) => kinds.push(ChangeKind::TypeRemoved),
+        (None, Some(_)) => kinds.push(ChangeKind::TypeAdded),
+        (Some(WireType::Struct(base)), Some(WireType::Struct(head))) => {
+            diff_fields(&base.fields, &head.fields, aliases, &mut kinds);
+        }
+        (Some(WireType::Alias(base)), Some(WireType::Alias(head))) => {
+            if !aliases.same_type(&base.r#type, &head.r#type) {
+                kinds.push(ChangeKind::AliasTypeChanged {
+                    base: base.r#type.to_string(),
+                    head: head.r#type.to_string(),
+                });
+            }
+        }
+        (
+            Some(WireType::Enum(RustEnum::Unit(base))),
+            Some(WireType::Enum(RustEnum::Unit(head))),
+        ) => {
+            diff_variants(
+                name,
+                &base.variants,
+                &head.variants,
+                aliases,
+                &mut kinds,
+                &mut variant_changes,
+            );
+        }
+        (
+            Some(WireType::Enum(RustEnum::Algebraic {
+                tag_key: base_tag,
+                content_key: base_content,
+                shared: base,
+            })),
+            Some(WireType::Enum(RustEnum::Algebraic {
+                tag_key: head_tag,
+                content_key: head_content,
+                shared: head,
+            })),
+        ) => {
+            for (base, head) in [(base_tag, head_tag), (base_content, head_content)] {
+                if base != head {
+                    kinds.push(ChangeKind::TagRenamed {
+                        base: base.clone(),
+                        head: head.clone(),
+                    });
+                }
+            }
+            diff_variants(
+                name,
+                &base.variants,
+                &head.variants,
+                aliases,
+                &mut kinds,
+                &mut variant_changes,
+            );
+        }
+        (Some(base), Some(head)) => kinds.push(ChangeKind::ShapeChanged {
+            base: base.shape(),
+            head: head.shape(),
+        }),
+        (None, None) => unreachable!("`{name}` comes from one of the versions"),
+    }
+
+    changes.extend(kinds.into_iter().map(|kind| Change {
+        item: name.to_owned(),
+        kind,
+    }));
+    changes.extend(variant_changes);
+}
+
+fn is_required(field: &RustField) -> bool {
+    !field.ty.is_optional() && !field.has_default
+}
+
+fn diff_fields(
+    base: &[RustField],
+    head: &[RustField],
+    aliases: &Aliases<'_>,
+    kinds: &mut Vec<ChangeKind>,
+) {
+    for base_field in base {
+        let field = base_field.id.renamed.clone();
+        match head.iter().find(|f| f.id.renamed == field) {
+            None => kinds.push(ChangeKind::FieldRemoved {
+                field,
+                required: is_required(base_field),
+            }),
+            Some(head_field) if !aliases.same_type(&base_field.ty, &head_field.ty) => {
+                kinds.push(ChangeKind::FieldTypeChanged {
+                    field,
+                    base: base_field.ty.to_string(),
+                    head: head_field.ty.to_string(),
+                })
+            }
+            Some(_) => (),
+        }
+    }
+
+    for head_field in head {
+        if !base.iter().any(|f| f.id.renamed == head_field.id.renamed) {
+            kinds.push(ChangeKind::FieldAdded {
+                field: head_field.id.renamed.clone(),
+                required: is_required(head_field),
+            });
+        }
+    }
+}
+
+/// The content of a variant, for reporting.
+fn variant_content(variant: &RustEnumVariant) -> String {
+    match variant {
+        RustEnumVariant::Unit(_) => "no content".to_owned(),
+        RustEnumVariant::Tuple { ty, .. } => format!("`{ty}`"),
+        RustEnumVariant::AnonymousStruct { .. } => "an anonymous struct".to_owned(),
+    }
+}
+
+fn diff_variants(
+    name: &str,
+    base: &[RustEnumVariant],
+    head: &[RustEnumVariant],
+    aliases: &Aliases<'_>,
+    kinds: &mut Vec<ChangeKind>,
+    field_changes: &mut Vec<Change>,
+) {
+    for base_variant in base {
+        let variant = base_variant.shared().id.renamed.clone();
+        match (
+            base_variant,
+            head.iter().find(|v| v.shared().id.renamed == variant),
+        ) {
+            (_, None) => kinds.push(ChangeKind::VariantRemoved { variant }),
+            (RustEnumVariant::Unit(_), Some(RustEnumVariant::Unit(_))) => (),
+            (
+                RustEnumVariant::Tuple { ty: base_ty, .. },
+                Some(RustEnumVariant::Tuple { ty: head_ty, .. }),
+            ) if aliases.same_type(base_ty, head_ty) => (),
+            (
+                RustEnumVariant::AnonymousStruct {
+                    fields: base_fields,
+                    ..
+                },
+                Some(RustEnumVariant::AnonymousStruct {
+                    fields: head_fields,
+                    ..
+                }),
+            ) => {
+                let mut field_kinds = Vec::new();
+                diff_fields(base_fields, head_fields, aliases, &mut field_kinds);
+                field_changes.extend(field_kinds.into_iter().map(|kind| Change {
+                    item: format!("{name}::{}", base_variant.shared().id.original),
+                    kind,
+                }));
+            }
+            (_, Some(head_variant)) => kinds.push(ChangeKind::VariantTypeChanged {
+                variant,
+                base: variant_content(base_variant),
+                head: variant_content(head_variant),
+            }),
+        }
+    }
+
+    for head_variant in head {
+        if !base
+            .iter()
+            .any(|v| v.shared().id.renamed == head_variant.shared().id.renamed)
+        {
+            kinds.push(ChangeKind::VariantAdded {
+                variant: head_variant.shared().id.renamed.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::diff;
+    use crate::{
+        context::{ParseContext, ParseFileContext},
+        language::{CrateName, SINGLE_FILE_CRATE_NAME},
+        parser::parse,
+    };
+    use std::collections::BTreeMap;
+
+    fn changes(base: &str, head: &str) -> Vec<String> {
+        crate_changes(
+            &[(SINGLE_FILE_CRATE_NAME, base)],
+            &[(SINGLE_FILE_CRATE_NAME, head)],
+        )
+    }
+
+    fn crate_changes(base: &[(CrateName, &str)], head: &[(CrateName, &str)]) -> Vec<String> {
+        let parse_crates = |crates: &[(CrateName, &str)]| {
+            crates
+                .iter()
+                .map(|(crate_name, source_code)| {
+                    let parsed_data = parse(
+                        &ParseContext::default(),
+                        ParseFileContext {
+                            source_code: source_code.to_string(),
+                            crate_name: crate_name.clone(),
+                            file_name: "file_name".into(),
+                            file_path: "file_path".into(),
+                        },
+                    )
+                    .unwrap()
+                    .unwrap();
+                    (crate_name.clone(), parsed_data)
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        diff(&parse_crates(base), &parse_crates(head))
+            .into_iter()
+            .map(|change| {
+                let label = if change.kind.is_breaking() {
+                    "breaking"
+                } else {
+                    "compatible"
+                };
+                format!("{label}: {change}")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_struct_changes() {
+        let base = r#"
+            #[typeshare]
+            #[serde(rename_all = "camelCase")]
+            pub struct User {
+                user_id: String,
+                email: String,
+                nickname: Option<String>,
+                age: u32,
+            }
+        "#;
+        let head = r#"
+            #[typeshare]
+            #[serde(rename_all = "camelCase")]
+            pub struct User {
+                #[serde(rename = "userId")]
+                id: String,
+                age: Option<u32>,
+                country: String,
+                #[serde(default)]
+                locale: String,
+            }
+        "#;
+
+        assert_eq!(
+            changes(base, head),
+            [
+                "breaking: `User`: removed required field `email`",
+                "compatible: `User`: removed optional field `nickname`",
+                "breaking: `User`: changed type of field `age` from `u32` to `Option<u32>`",
+                "breaking: `User`: added required field `country`",
+                "compatible: `User`: added optional field `locale`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_changes() {
+        let base = r#"
+            #[typeshare]
+            pub enum Status { Open, Closed, Archived }
+
+            #[typeshare]
+            #[serde(tag = "type", content = "content")]
+            pub enum Event {
+                Created(String),
+                Deleted { id: String },
+                Moved(String),
+            }
+        "#;
+        let head = r#"
+            #[typeshare]
+            pub enum Status { Open, Closed, Pending }
+
+            #[typeshare]
+            #[serde(tag = "kind", content = "content")]
+            pub enum Event {
+                Created(u32),
+                Deleted { id: String, reason: String },
+                Moved,
+            }
+        "#;
+
+        assert_eq!(
+            changes(base, head),
+            [
+                "breaking: `Event`: renamed tag `type` to `kind`",
+                "breaking: `Event`: changed content of variant `Created` from `String` to `u32`",
+                "breaking: `Event`: changed content of variant `Moved` from `String` to no content",
+                "breaking: `Event::Deleted`: added required field `reason`",
+                "breaking: `Status`: removed variant `Archived`",
+                "breaking: `Status`: added variant `Pending`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_changes() {
+        let base = r#"
+            #[typeshare]
+            pub struct Legacy { id: String }
+
+            #[typeshare]
+            pub type Id = String;
+
+            #[typeshare]
+            pub struct Token { value: String }
+        "#;
+        let head = r#"
+            #[typeshare]
+            pub type Id = u32;
+
+            #[typeshare]
+            pub enum Token { Bearer, Basic }
+
+            #[typeshare]
+            pub struct Session { id: Id }
+        "#;
+
+        assert_eq!(
+            changes(base, head),
+            [
+                "breaking: `Id`: changed aliased type from `String` to `u32`",
+                "breaking: `Legacy`: removed type",
+                "compatible: `Session`: added type",
+                "breaking: `Token`: changed from a struct to a unit enum",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alias_changes() {
+        let base = r#"
+            #[typeshare]
+            pub type UserId = u32;
+
+            #[typeshare]
+            pub type Ids<T> = Vec<T>;
+
+            #[typeshare]
+            pub struct User { id: u32, friends: Vec<u32>, team: u32 }
+        "#;
+        let head = r#"
+            #[typeshare]
+            pub type UserId = u32;
+
+            #[typeshare]
+            pub type Ids<T> = Vec<T>;
+
+            #[typeshare]
+            pub type TeamId = String;
+
+            #[typeshare]
+            pub struct User { id: UserId, friends: Ids<UserId>, team: TeamId }
+        "#;
+
+        assert_eq!(
+            changes(base, head),
+            [
+                "compatible: `TeamId`: added type",
+                "breaking: `User`: changed type of field `team` from `u32` to `TeamId`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_renames() {
+        let base = r#"
+            #[typeshare]
+            pub struct User { user_id: String }
+
+            #[typeshare]
+            pub enum Status { Open }
+        "#;
+        let head = r#"
+            #[typeshare]
+            #[serde(rename = "User")]
+            pub struct Account {
+                #[serde(rename = "user_id")]
+                id: String,
+            }
+
+            #[typeshare]
+            #[serde(rename = "Status")]
+            pub enum State {
+                #[serde(rename = "Open")]
+                Active,
+            }
+        "#;
+
+        assert!(changes(base, head).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let base = [
+            ("a".into(), "#[typeshare] pub struct Id { value: String }"),
+            ("b".into(), "#[typeshare] pub struct Id { value: u32 }"),
+        ];
+        let head = [
+            ("a".into(), "#[typeshare] pub struct Id { value: String }"),
+            ("b".into(), "#[typeshare] pub struct Id { value: String }"),
+            ("c".into(), "#[typeshare] pub type Id = String;"),
+        ];
+
+        assert_eq!(
+            crate_changes(&base, &head),
+            [
+                "breaking: `b::Id`: changed type of field `value` from `u32` to `String`",
+                "compatible: `c::Id`: added type",
+            ]
+        );
+    }
+}
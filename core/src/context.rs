@@ -1,6 +1,6 @@
 //! Context types for parsing.
 //!
-use crate::language::CrateName;
+use crate::{cfg::CfgOptions, language::CrateName};
 use std::path::PathBuf;
 
 /// Context for parsing rust source files.
@@ -10,8 +10,13 @@ pub struct ParseContext<'a> {
     pub ignored_types: Vec<&'a str>,
     /// Multi file output enabled.
     pub multi_file: bool,
-    /// `target_os` filtering.
-    pub target_os: Vec<String>,
+    /// Active cfg options used to filter items, fields and variants.
+    pub cfg: CfgOptions,
+    /// Report unknown or misplaced typeshare attributes as errors instead of warnings.
+    pub strict: bool,
+    /// Keys of the target language's type mappings. References to types whose full path is a
+    /// key, such as `uuid::Uuid`, are renamed to that path using the imports of each file.
+    pub type_mapping_keys: Vec<String>,
 }
 
 /// Parsing context for a single rust source file.
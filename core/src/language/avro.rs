@@ -0,0 +1,478 @@
+use crate::{
+    language::{CrateTypes, Language, SupportedLanguage},
+    parser::ParsedData,
+    rust_types::{
+        RustEnum, RustEnumShared, RustEnumVariant, RustField, RustItem, RustType,
+        RustTypeFormatError, SpecialRustType,
+    },
+};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use super::{
+    generated_items, generic_type_mapping,
+    json_schema::{parse_mapping, write_json},
+    substitute_generic_parameters, used_imports, ScopedCrateTypes,
+};
+
+/// All information needed to generate an Avro schema.
+///
+/// The generated `.avsc` file is an array of named types, each defined before it is referenced.
+/// Structs become records and unit enums become enums. Avro unions can't be named, so algebraic
+/// enums become a record per variant, and references to them are unions of those records.
+/// Type aliases are replaced with the aliased type. Fields only get the defaults that can be
+/// worked out from their type, other defaults are set with `#[typeshare(avro(default = "..."))]`.
+#[derive(Default)]
+pub struct Avro {
+    /// Namespace of the generated types. In multi file mode, each crate becomes a namespace within it.
+    pub namespace: String,
+    /// Mappings from Rust type names to Avro types. Values starting with `{` or `[` are used as
+    /// schemas verbatim, any other value is used as the name of a type.
+    pub type_mappings: HashMap<String, String>,
+    /// HashMap<Identifier, AliasedType> of the type aliases in the file being generated and
+    /// the ones it imports from other crates
+    pub aliases: HashMap<String, RustType>,
+    /// HashMap<Identifier, QualifiedIdentifier> of the types imported from other namespaces
+    pub imports: HashMap<String, String>,
+    /// HashMap<Identifier, Enum> of the enums in the file being generated and the ones it
+    /// imports from other crates
+    pub enums: HashMap<String, RustEnum>,
+}
+
+impl Language for Avro {
+    fn generate_types(
+        &mut self,
+        w: &mut dyn Write,
+        all_types: &CrateTypes,
+        data: ParsedData,
+    ) -> io::Result<()> {
+        self.imports.clear();
+        self.aliases = data
+            .aliases
+            .iter()
+            .chain(&data.foreign_aliases)
+            .filter(|alias| alias.generic_types.is_empty())
+            .map(|alias| (alias.id.original.to_string(), alias.r#type.clone()))
+            .collect();
+        self.enums = data
+            .enums
+            .iter()
+            .chain(&data.foreign_enums)
+            .map(|e| (e.shared().id.original.to_string(), e.clone()))
+            .collect();
+
+        let namespace = if data.multi_file {
+            self.write_imports(w, used_imports(&data, all_types))?;
+            self.namespace_for(data.crate_name.as_str())
+        } else {
+            self.namespace.clone()
+        };
+
+        let mut definitions = Vec::new();
+        for item in generated_items(data, self.language()) {
+            match &item {
+                RustItem::Struct(rs) => {
+                    if let Some(generic) = rs.generic_types.first() {
+                        return Err(io::Error::other(
+                            RustTypeFormatError::GenericsForbiddenInAvro(generic.to_owned()),
+                        ));
+                    }
+                    let fields = self.fields(&rs.id.renamed, &rs.fields)?;
+                    definitions.push(record(&rs.id.renamed, &namespace, &rs.comments, fields)?);
+                }
+                RustItem::Enum(RustEnum::Unit(shared)) => {
+                    definitions.push(enum_schema(shared, &namespace)?)
+                }
+                RustItem::Enum(RustEnum::Algebraic {
+                    content_key,
+                    shared,
+                    ..
+                }) => {
+                    if let Some(generic) = shared.generic_types.first() {
+                        return Err(io::Error::other(
+                            RustTypeFormatError::GenericsForbiddenInAvro(generic.to_owned()),
+                        ));
+                    }
+                    for variant in &shared.variants {
+                        let name = variant_record_name(shared, variant);
+                        let fields = match variant {
+                            RustEnumVariant::Unit(_) => Vec::new(),
+                            RustEnumVariant::Tuple { ty, .. } => {
+                                let mut field = json!({
+                                    "name": valid_name(content_key)?,
+                                    "type": self.schema(ty).map_err(io::Error::other)?,
+                                });
+                                if ty.is_optional() {
+                                    field["default"] = Value::Null;
+                                }
+                                vec![field]
+                            }
+                            RustEnumVariant::AnonymousStruct { fields, .. } => {
+                                self.fields(&name, fields)?
+                            }
+                        };
+                        definitions.push(record(
+                            &name,
+                            &namespace,
+                            &variant.shared().comments,
+                            fields,
+                        )?);
+                    }
+                }
+                // Avro has no aliases or constants.
+                RustItem::Alias(_) | RustItem::Const(_) => (),
+            }
+        }
+
+        write_json(w, &Value::Array(definitions))
+    }
+
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Avro
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn format_simple_type(
+        &mut self,
+        base: &String,
+        _generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        self.schema(&RustType::Simple { id: base.clone() })
+            .map(|schema| schema.to_string())
+    }
+
+    fn format_generic_type(
+        &mut self,
+        base: &String,
+        parameters: &[RustType],
+        _generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        self.schema(&RustType::Generic {
+            id: base.clone(),
+            parameters: parameters.to_vec(),
+        })
+        .map(|schema| schema.to_string())
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        _generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        self.special_schema(special_ty)
+            .map(|schema| schema.to_string())
+    }
+
+    fn write_imports(
+        &mut self,
+        _w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        // Imported types are referenced by their full name instead.
+        for (crate_name, types) in imports {
+            let namespace = self.namespace_for(crate_name.as_str());
+            self.imports.extend(
+                types
+                    .into_iter()
+                    .map(|ty| (ty.to_string(), format!("{namespace}.{ty}"))),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Avro {
+    /// The namespace of a crate's types in multi file mode.
+    fn namespace_for(&self, crate_name: &str) -> String {
+        // Namespaces are made of names, which can't contain dashes.
+        let crate_name = crate_name.replace('-', "_");
+        if self.namespace.is_empty() {
+            crate_name
+        } else {
+            format!("{}.{crate_name}", self.namespace)
+        }
+    }
+
+    /// The schema of a Rust type.
+    fn schema(&self, ty: &RustType) -> Result<Value, RustTypeFormatError> {
+        match ty {
+            RustType::Simple { id } => Ok(if let Some(mapped) = self.type_mappings.get(id) {
+                mapped_schema(mapped)?
+            } else if let Some(aliased) = self.aliases.get(id) {
+                self.schema(aliased)?
+            } else if let Some(RustEnum::Algebraic { shared, .. }) = self.enums.get(id) {
+                // The variant records of another crate's enum are in its namespace.
+                let namespace = self
+                    .imports
+                    .get(id)
+                    .and_then(|qualified| qualified.rsplit_once('.'))
+                    .map(|(namespace, _)| namespace);
+                shared
+                    .variants
+                    .iter()
+                    .map(|v| match namespace {
+                        Some(namespace) => {
+                            format!("{namespace}.{}", variant_record_name(shared, v))
+                        }
+                        None => variant_record_name(shared, v),
+                    })
+                    .collect()
+            } else if let Some(qualified) = self.imports.get(id) {
+                json!(qualified)
+            } else {
+                json!(id)
+            }),
+            RustType::Generic { id, parameters } => {
+                let mapped = generic_type_mapping(&self.type_mappings, id, parameters.len())
+                    .ok_or_else(|| RustTypeFormatError::GenericsForbiddenInAvro(id.to_owned()))?;
+                mapped_schema(&substitute_generic_parameters(mapped, parameters, |p| {
+                    Ok::<_, RustTypeFormatError>(self.schema(p)?.to_string())
+                })?)
+            }
+            RustType::Special(special) => self.special_schema(special),
+        }
+    }
+
+    fn special_schema(&self, special_ty: &SpecialRustType) -> Result<Value, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()) {
+            return mapped_schema(mapped);
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype)
+            | SpecialRustType::Array(rtype, _)
+            | SpecialRustType::Slice(rtype) => {
+                json!({ "type": "array", "items": self.schema(rtype)? })
+            }
+            // Map keys are always strings in Avro.
+            SpecialRustType::HashMap(_, rtype) => {
+                json!({ "type": "map", "values": self.schema(rtype)? })
+            }
+            // Unions can't contain unions, so an optional union gets a `null` branch instead.
+            SpecialRustType::Option(rtype) => match self.schema(rtype)? {
+                Value::Array(branches) if branches.first() == Some(&json!("null")) => {
+                    Value::Array(branches)
+                }
+                Value::Array(branches) => [json!("null")].into_iter().chain(branches).collect(),
+                schema => json!(["null", schema]),
+            },
+            SpecialRustType::Unit => json!("null"),
+            SpecialRustType::String | SpecialRustType::Char | SpecialRustType::DateTime => {
+                json!("string")
+            }
+            SpecialRustType::I8
+            | SpecialRustType::I16
+            | SpecialRustType::I32
+            | SpecialRustType::U8
+            | SpecialRustType::U16 => json!("int"),
+            SpecialRustType::U32
+            | SpecialRustType::I54
+            | SpecialRustType::U53
+            | SpecialRustType::I64
+            | SpecialRustType::ISize => json!("long"),
+            SpecialRustType::F32 => json!("float"),
+            SpecialRustType::F64 => json!("double"),
+            SpecialRustType::Bool => json!("boolean"),
+            // `long` is a signed 64 bit integer.
+            SpecialRustType::U64 | SpecialRustType::USize => {
+                return Err(RustTypeFormatError::UnsupportedSpecialType(
+                    special_ty.to_string(),
+                ))
+            }
+        })
+    }
+
+    fn fields(&self, record: &str, fields: &[RustField]) -> io::Result<Vec<Value>> {
+        fields
+            .iter()
+            .map(|field| self.field(record, field))
+            .collect()
+    }
+
+    fn field(&self, record: &str, field: &RustField) -> io::Result<Value> {
+        let schema = match field.type_override(SupportedLanguage::Avro) {
+            Some(type_override) => mapped_schema(type_override),
+            None => self.schema(&field.ty),
+        }
+        .map_err(io::Error::other)?;
+
+        let mut entries = json!({ "name": valid_name(&field.id.renamed)?, "type": schema });
+        if !field.comments.is_empty() {
+            entries["doc"] = field.comments.join("\n").into();
+        }
+        // Readers fall back to the default when the writer's schema doesn't have the field.
+        if let Some(default) = field.default_override(SupportedLanguage::Avro) {
+            entries["default"] = serde_json::from_str(default).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "the Avro default of `{}` in `{record}` is not valid JSON: {err}",
+                        field.id.original
+                    ),
+                )
+            })?;
+        } else if field.ty.is_optional() || field.has_default {
+            let default = self.field_default(field).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "no Avro default can be derived for `{}` in `{record}`, set one with `#[typeshare(avro(default = \"...\"))]`",
+                        field.id.original
+                    ),
+                )
+            })?;
+            entries["default"] = default;
+        }
+        Ok(entries)
+    }
+
+    /// The default of a field, which is what `Default::default()` serializes to.
+    fn field_default(&self, field: &RustField) -> Option<Value> {
+        match field.type_override(SupportedLanguage::Avro) {
+            Some(type_override) => mapped_default(type_override),
+            None => self.default(&field.ty),
+        }
+    }
+
+    /// What `Default::default()` of a type serializes to, if it is known from the type alone.
+    /// Records and enums can implement `Default` in any way, so they have no known default.
+    fn default(&self, ty: &RustType) -> Option<Value> {
+        match ty {
+            RustType::Simple { id } => {
+                if let Some(mapped) = self.type_mappings.get(id) {
+                    mapped_default(mapped)
+                } else if let Some(aliased) = self.aliases.get(id) {
+                    self.default(aliased)
+                } else {
+                    None
+                }
+            }
+            RustType::Generic { .. } => None,
+            RustType::Special(special) => {
+                if let Some(mapped) = self.type_mappings.get(&special.to_string()) {
+                    return mapped_default(mapped);
+                }
+                match special {
+                    SpecialRustType::Option(_) | SpecialRustType::Unit => Some(Value::Null),
+                    SpecialRustType::Vec(_) | SpecialRustType::Slice(_) => Some(json!([])),
+                    SpecialRustType::Array(rtype, len) => {
+                        Some(Value::Array(vec![self.default(rtype)?; *len]))
+                    }
+                    SpecialRustType::HashMap(_, _) => Some(json!({})),
+                    SpecialRustType::String => Some(json!("")),
+                    SpecialRustType::Char => Some(json!("\0")),
+                    SpecialRustType::Bool => Some(json!(false)),
+                    // Date times have no default.
+                    SpecialRustType::DateTime => None,
+                    _ => Some(json!(0)),
+                }
+            }
+        }
+    }
+}
+
+fn record(
+    name: &str,
+    namespace: &str,
+    comments: &[String],
+    fields: Vec<Value>,
+) -> io::Result<Value> {
+    let mut schema = json!({ "type": "record", "name": valid_name(name)? });
+    if !namespace.is_empty() {
+        schema["namespace"] = namespace.into();
+    }
+    if !comments.is_empty() {
+        schema["doc"] = comments.join("\n").into();
+    }
+    schema["fields"] = fields.into();
+    Ok(schema)
+}
+
+fn enum_schema(shared: &RustEnumShared, namespace: &str) -> io::Result<Value> {
+    let symbols = shared
+        .variants
+        .iter()
+        .map(|v| valid_name(&v.shared().id.renamed))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut schema = json!({ "type": "enum", "name": valid_name(&shared.id.renamed)? });
+    if !namespace.is_empty() {
+        schema["namespace"] = namespace.into();
+    }
+    if !shared.comments.is_empty() {
+        schema["doc"] = shared.comments.join("\n").into();
+    }
+    schema["symbols"] = symbols.into();
+    Ok(schema)
+}
+
+/// The name of the record of an algebraic enum's variant.
+fn variant_record_name(shared: &RustEnumShared, variant: &RustEnumVariant) -> String {
+    format!("{}{}", shared.id.renamed, variant.shared().id.original)
+}
+
+/// The schema of a type mapping.
+fn mapped_schema(mapped: &str) -> Result<Value, RustTypeFormatError> {
+    let trimmed = mapped.trim();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        parse_mapping(trimmed)
+    } else {
+        Ok(json!(trimmed))
+    }
+}
+
+/// The default of a type mapped to one of Avro's primitive types.
+fn mapped_default(mapped: &str) -> Option<Value> {
+    match mapped.trim() {
+        "null" => Some(Value::Null),
+        "boolean" => Some(json!(false)),
+        "int" | "long" | "float" | "double" => Some(json!(0)),
+        "bytes" | "string" => Some(json!("")),
+        _ => None,
+    }
+}
+
+/// Check that a name is an Avro name, which are ASCII identifiers.
+fn valid_name(name: &str) -> io::Result<&str> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+    if valid {
+        Ok(name)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("`{name}` is not a valid Avro name"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{mapped_default, valid_name};
+
+    #[test]
+    fn names_are_ascii_identifiers() {
+        assert!(valid_name("userId").is_ok());
+        assert!(valid_name("_private").is_ok());
+        assert!(valid_name("").is_err());
+        assert!(valid_name("2fa").is_err());
+        assert!(valid_name("x-trace").is_err());
+    }
+
+    #[test]
+    fn mapped_primitives_have_defaults() {
+        assert!(mapped_default("long").is_some());
+        assert!(mapped_default(" string ").is_some());
+        assert!(mapped_default("com.example.Money").is_none());
+        assert!(mapped_default(r#"{"type": "long", "logicalType": "timestamp-millis"}"#).is_none());
+    }
+}
@@ -0,0 +1,562 @@
+use crate::{
+    language::{CrateTypes, Language, SupportedLanguage},
+    parser::ParsedData,
+    rename::RenameExt,
+    rust_types::{
+        RustConst, RustConstExpr, RustEnum, RustEnumShared, RustEnumVariant, RustField, RustItem,
+        RustStruct, RustType, RustTypeFormatError, SpecialRustType,
+    },
+};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, Write},
+};
+
+use super::{generated_items, used_imports, ScopedCrateTypes};
+
+/// All information needed to generate C# type-code for System.Text.Json
+///
+/// Structs become positional records and unit enums become enums serialized as strings with
+/// `JsonStringEnumConverter`. Algebraic enums are always adjacently tagged, which
+/// `[JsonPolymorphic]` can't express for generic types, so they become an abstract record that
+/// a record for each variant derives from, serialized by a generated `JsonConverter`. Variant
+/// records are named after the enum and the variant, since nested records would hide types with
+/// the same name as a variant from the variants' content. Type aliases are replaced with the
+/// aliased type, and constants are collected in a static `Constants` class.
+#[derive(Default)]
+pub struct CSharp {
+    /// Name of the namespace. In multi file mode, each crate becomes a namespace within it.
+    pub namespace: String,
+    /// Conversions from Rust type names to C# type names.
+    pub type_mappings: HashMap<String, String>,
+    /// Whether or not to exclude the version header that normally appears at the top of generated code.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+    /// BTreeSet<Namespace> of the `using` directives of the file being generated
+    pub usings: BTreeSet<String>,
+    /// HashMap<Identifier, AliasedType> of the type aliases in the file being generated and
+    /// the ones it imports from other crates
+    pub aliases: HashMap<String, RustType>,
+}
+
+impl Language for CSharp {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::CSharp
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn generate_types(
+        &mut self,
+        w: &mut dyn Write,
+        all_types: &CrateTypes,
+        data: ParsedData,
+    ) -> io::Result<()> {
+        self.usings.clear();
+        self.aliases = data
+            .aliases
+            .iter()
+            .chain(&data.foreign_aliases)
+            .filter(|alias| alias.generic_types.is_empty())
+            .map(|alias| (alias.id.original.to_string(), alias.r#type.clone()))
+            .collect();
+
+        let namespace = if data.multi_file {
+            self.namespace_for(data.crate_name.as_str())
+        } else {
+            self.namespace.clone()
+        };
+        if data.multi_file {
+            self.write_imports(w, used_imports(&data, all_types))?;
+        }
+
+        let mut body = Vec::new();
+        let mut constants = Vec::new();
+        for item in generated_items(data, self.language()) {
+            match &item {
+                RustItem::Struct(rs) => self.write_struct(&mut body, rs)?,
+                RustItem::Enum(e) => self.write_enum(&mut body, e)?,
+                // Aliases are replaced by the aliased type.
+                RustItem::Alias(_) => (),
+                RustItem::Const(c) => self.write_const(&mut constants, c)?,
+            }
+        }
+        if !constants.is_empty() {
+            writeln!(body, "public static class Constants\n{{")?;
+            body.extend(constants);
+            writeln!(body, "}}")?;
+        }
+
+        if !self.no_version_header {
+            writeln!(w, "// <auto-generated>")?;
+            writeln!(w, "// Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
+            writeln!(w, "// </auto-generated>")?;
+            writeln!(w)?;
+        }
+        writeln!(w, "#nullable enable\n")?;
+        if !self.usings.is_empty() {
+            for using in &self.usings {
+                writeln!(w, "using {using};")?;
+            }
+            writeln!(w)?;
+        }
+        if !namespace.is_empty() {
+            writeln!(w, "namespace {namespace};\n")?;
+        }
+
+        let body = String::from_utf8(body).map_err(io::Error::other)?;
+        writeln!(w, "{}", body.trim_end())
+    }
+
+    fn format_simple_type(
+        &mut self,
+        base: &String,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(base) {
+            Ok(mapped.to_owned())
+        } else if generic_types.contains(base) {
+            Ok(base.to_owned())
+        } else if let Some(aliased) = self.aliases.get(base).cloned() {
+            self.format_type(&aliased, generic_types)
+        } else {
+            Ok(base.to_owned())
+        }
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()) {
+            return Ok(mapped.to_owned());
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype)
+            | SpecialRustType::Array(rtype, _)
+            | SpecialRustType::Slice(rtype) => {
+                self.usings.insert("System.Collections.Generic".into());
+                format!("List<{}>", self.format_type(rtype, generic_types)?)
+            }
+            SpecialRustType::Option(rtype) => nullable(self.format_type(rtype, generic_types)?),
+            SpecialRustType::HashMap(rtype1, rtype2) => {
+                self.usings.insert("System.Collections.Generic".into());
+                format!(
+                    "Dictionary<{}, {}>",
+                    self.format_type(rtype1, generic_types)?,
+                    self.format_type(rtype2, generic_types)?
+                )
+            }
+            // The unit type is serialized as `null`.
+            SpecialRustType::Unit => "object?".into(),
+            SpecialRustType::String => "string".into(),
+            SpecialRustType::Char => "char".into(),
+            SpecialRustType::DateTime => {
+                self.usings.insert("System".into());
+                "DateTimeOffset".into()
+            }
+            SpecialRustType::I8 => "sbyte".into(),
+            SpecialRustType::I16 => "short".into(),
+            SpecialRustType::I32 => "int".into(),
+            SpecialRustType::I54 | SpecialRustType::I64 | SpecialRustType::ISize => "long".into(),
+            SpecialRustType::U8 => "byte".into(),
+            SpecialRustType::U16 => "ushort".into(),
+            SpecialRustType::U32 => "uint".into(),
+            SpecialRustType::U53 | SpecialRustType::U64 | SpecialRustType::USize => "ulong".into(),
+            SpecialRustType::Bool => "bool".into(),
+            SpecialRustType::F32 => "float".into(),
+            SpecialRustType::F64 => "double".into(),
+        })
+    }
+
+    fn write_imports(
+        &mut self,
+        _w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        // Using directives are collected and written with the ones needed by the generated types.
+        // Aliases are replaced by the aliased type, so they never need one.
+        for (crate_name, types) in imports {
+            if types.iter().any(|ty| !self.aliases.contains_key(*ty)) {
+                self.usings.insert(self.namespace_for(crate_name.as_str()));
+            }
+        }
+        Ok(())
+    }
+
+    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
+        match c.expr {
+            RustConstExpr::Int(val) => {
+                let ty = self.format_type(&c.r#type, &[]).map_err(io::Error::other)?;
+                writeln!(
+                    w,
+                    "    public const {ty} {} = {val};",
+                    c.id.original.to_pascal_case()
+                )
+            }
+        }
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        let parameters = rs
+            .fields
+            .iter()
+            .map(|f| self.format_parameter(f, &rs.id.renamed, &rs.generic_types))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        write_summary(w, 0, &rs.comments)?;
+        for field in rs.fields.iter().filter(|f| !f.comments.is_empty()) {
+            writeln!(
+                w,
+                "/// <param name=\"{}\">{}</param>",
+                property_name(field, &rs.id.renamed),
+                xml_escape(&field.comments.join(" "))
+            )?;
+        }
+
+        let name = format!("{}{}", rs.id.renamed, generic_parameters(&rs.generic_types));
+        if parameters.is_empty() {
+            writeln!(w, "public sealed record {name}();\n")
+        } else {
+            writeln!(
+                w,
+                "public sealed record {name}(\n    {}\n);\n",
+                parameters.join(",\n    ")
+            )
+        }
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        // Generate named types for any anonymous struct variants of this enum
+        self.write_types_for_anonymous_structs(w, e, &|variant_name| {
+            format!("{}{}Inner", &e.shared().id.renamed, variant_name)
+        })?;
+
+        self.usings.insert("System.Text.Json.Serialization".into());
+        write_summary(w, 0, &e.shared().comments)?;
+
+        match e {
+            RustEnum::Unit(shared) => {
+                writeln!(w, "[JsonConverter(typeof(JsonStringEnumConverter))]")?;
+                writeln!(w, "public enum {}\n{{", shared.id.renamed)?;
+                let members = shared
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let mut member = Vec::new();
+                        write_summary(&mut member, 1, &v.shared().comments)?;
+                        let name = v.shared().id.original.to_pascal_case();
+                        if v.shared().id.renamed != name {
+                            writeln!(
+                                member,
+                                "    [JsonStringEnumMemberName({:?})]",
+                                v.shared().id.renamed
+                            )?;
+                        }
+                        write!(member, "    {name}")?;
+                        String::from_utf8(member).map_err(io::Error::other)
+                    })
+                    .collect::<io::Result<Vec<_>>>()?;
+                writeln!(w, "{}", members.join(",\n"))?;
+                writeln!(w, "}}\n")
+            }
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => {
+                let variants = shared
+                    .variants
+                    .iter()
+                    .map(|v| Ok((v, self.variant_content(v, shared)?)))
+                    .collect::<io::Result<Vec<_>>>()?;
+
+                let name = &shared.id.renamed;
+                let generics = generic_parameters(&shared.generic_types);
+                writeln!(w, "[JsonConverter(typeof({name}JsonConverter))]")?;
+                writeln!(w, "public abstract record {name}{generics}\n{{")?;
+                writeln!(w, "    private protected {name}() {{}}")?;
+                writeln!(w, "}}\n")?;
+                for (v, content) in &variants {
+                    let variant = variant_name(name, v);
+                    write_summary(w, 0, &v.shared().comments)?;
+                    writeln!(
+                        w,
+                        "public sealed record {variant}{generics}({}) : {name}{generics};\n",
+                        content
+                            .as_ref()
+                            .map(|ty| format!("{ty} {}", content_property(content_key, &variant)))
+                            .unwrap_or_default()
+                    )?;
+                }
+
+                self.write_converter(w, tag_key, content_key, shared, &variants)
+            }
+        }
+    }
+}
+
+impl CSharp {
+    /// The namespace of a crate's types in multi file mode.
+    fn namespace_for(&self, crate_name: &str) -> String {
+        let crate_namespace = crate_name.to_string().to_pascal_case();
+        if self.namespace.is_empty() {
+            crate_namespace
+        } else {
+            format!("{}.{crate_namespace}", self.namespace)
+        }
+    }
+
+    /// A record parameter for a field, like `[property: JsonPropertyName("id")] int Id`.
+    fn format_parameter(
+        &mut self,
+        field: &RustField,
+        record_name: &str,
+        generic_types: &[String],
+    ) -> io::Result<String> {
+        self.usings.insert("System.Text.Json.Serialization".into());
+
+        let ty = match field.type_override(SupportedLanguage::CSharp) {
+            Some(type_override) => type_override.to_owned(),
+            None => self
+                .format_type(&field.ty, generic_types)
+                .map_err(io::Error::other)?,
+        };
+        let ty = if field.has_default { nullable(ty) } else { ty };
+        // serde rejects `null` for fields that aren't optional, so missing defaults are left out.
+        let ignore = if field.has_default && !field.ty.is_optional() {
+            "[property: JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)] "
+        } else {
+            ""
+        };
+
+        Ok(format!(
+            "[property: JsonPropertyName({:?})] {ignore}{ty} {}",
+            field.id.renamed,
+            property_name(field, record_name)
+        ))
+    }
+
+    /// The type of a variant's content, if it has any.
+    fn variant_content(
+        &mut self,
+        variant: &RustEnumVariant,
+        e: &RustEnumShared,
+    ) -> io::Result<Option<String>> {
+        Ok(match variant {
+            RustEnumVariant::Unit(_) => None,
+            RustEnumVariant::Tuple { ty, .. } => Some(
+                self.format_type(ty, &e.generic_types)
+                    .map_err(io::Error::other)?,
+            ),
+            RustEnumVariant::AnonymousStruct {
+                fields,
+                shared: variant,
+            } => {
+                let inner_generics = e
+                    .generic_types
+                    .iter()
+                    .filter(|g| fields.iter().any(|f| f.ty.contains_type(g)))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                Some(format!(
+                    "{}{}Inner{}",
+                    e.id.renamed,
+                    variant.id.original,
+                    generic_parameters(&inner_generics)
+                ))
+            }
+        })
+    }
+
+    /// Write the `JsonConverter` for an algebraic enum, and for generic enums the
+    /// `JsonConverterFactory` that creates it.
+    fn write_converter(
+        &mut self,
+        w: &mut dyn Write,
+        tag_key: &str,
+        content_key: &str,
+        e: &RustEnumShared,
+        variants: &[(&RustEnumVariant, Option<String>)],
+    ) -> io::Result<()> {
+        self.usings.insert("System".into());
+        self.usings.insert("System.Text.Json".into());
+
+        let name = &e.id.renamed;
+        let generics = generic_parameters(&e.generic_types);
+        let ty = format!("{name}{generics}");
+
+        if !e.generic_types.is_empty() {
+            let open_generics = format!("<{}>", ",".repeat(e.generic_types.len() - 1));
+            writeln!(
+                w,
+                "public sealed class {name}JsonConverter : JsonConverterFactory\n{{"
+            )?;
+            writeln!(
+                w,
+                "    public override bool CanConvert(Type typeToConvert) =>\n        \
+                 typeToConvert.IsGenericType && typeToConvert.GetGenericTypeDefinition() == typeof({name}{open_generics});\n"
+            )?;
+            writeln!(
+                w,
+                "    public override JsonConverter CreateConverter(Type typeToConvert, JsonSerializerOptions options) =>\n        \
+                 (JsonConverter)Activator.CreateInstance(\n            \
+                 typeof({name}JsonConverter{open_generics}).MakeGenericType(typeToConvert.GetGenericArguments()))!;"
+            )?;
+            writeln!(w, "}}\n")?;
+        }
+
+        writeln!(
+            w,
+            "public sealed class {name}JsonConverter{generics} : JsonConverter<{ty}>\n{{"
+        )?;
+
+        writeln!(
+            w,
+            "    public override {ty} Read(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options)\n    {{"
+        )?;
+        writeln!(
+            w,
+            "        var element = JsonElement.ParseValue(ref reader);"
+        )?;
+        writeln!(
+            w,
+            "        var tag = element.GetProperty({tag_key:?}).GetString();"
+        )?;
+        writeln!(w, "        return tag switch\n        {{")?;
+        for (v, content) in variants {
+            let variant = format!("{}{generics}", variant_name(name, v));
+            match content {
+                Some(content) => writeln!(
+                    w,
+                    "            {:?} => new {variant}(element.GetProperty({content_key:?}).Deserialize<{content}>(options)!),",
+                    v.shared().id.renamed
+                )?,
+                None => writeln!(
+                    w,
+                    "            {:?} => new {variant}(),",
+                    v.shared().id.renamed
+                )?,
+            }
+        }
+        writeln!(
+            w,
+            "            _ => throw new JsonException($\"Unknown {name} variant: {{tag}}\"),"
+        )?;
+        writeln!(w, "        }};\n    }}\n")?;
+
+        writeln!(
+            w,
+            "    public override void Write(Utf8JsonWriter writer, {ty} value, JsonSerializerOptions options)\n    {{"
+        )?;
+        writeln!(w, "        writer.WriteStartObject();")?;
+        writeln!(w, "        switch (value)\n        {{")?;
+        for (v, content) in variants {
+            let variant = variant_name(name, v);
+            if content.is_some() {
+                writeln!(w, "            case {variant}{generics} variant:")?;
+            } else {
+                writeln!(w, "            case {variant}{generics}:")?;
+            }
+            writeln!(
+                w,
+                "                writer.WriteString({tag_key:?}, {:?});",
+                v.shared().id.renamed
+            )?;
+            if content.is_some() {
+                writeln!(
+                    w,
+                    "                writer.WritePropertyName({content_key:?});"
+                )?;
+                writeln!(
+                    w,
+                    "                JsonSerializer.Serialize(writer, variant.{}, options);",
+                    content_property(content_key, &variant)
+                )?;
+            }
+            writeln!(w, "                break;")?;
+        }
+        writeln!(w, "        }}")?;
+        writeln!(w, "        writer.WriteEndObject();\n    }}")?;
+        writeln!(w, "}}\n")
+    }
+}
+
+/// Make a type nullable, unless it already is.
+fn nullable(ty: String) -> String {
+    if ty.ends_with('?') {
+        ty
+    } else {
+        format!("{ty}?")
+    }
+}
+
+/// The name of a field's property.
+///
+/// Members can't have the name of the type that declares them, so those get an underscore.
+fn property_name(field: &RustField, record_name: &str) -> String {
+    let name = field
+        .name_override(SupportedLanguage::CSharp)
+        .map_or_else(|| field.id.original.to_pascal_case(), str::to_owned);
+    if name == record_name {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// The name of the record of an algebraic enum variant.
+fn variant_name(enum_name: &str, variant: &RustEnumVariant) -> String {
+    format!(
+        "{enum_name}{}",
+        variant.shared().id.original.to_pascal_case()
+    )
+}
+
+/// The name of the property holding a variant's content.
+fn content_property(content_key: &str, variant_name: &str) -> String {
+    let name = content_key.to_string().to_pascal_case();
+    if name == variant_name {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+fn generic_parameters(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generic_types.join(", "))
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn write_summary(w: &mut dyn Write, indent: usize, comments: &[String]) -> io::Result<()> {
+    if comments.is_empty() {
+        return Ok(());
+    }
+    let indent = "    ".repeat(indent);
+    writeln!(w, "{indent}/// <summary>")?;
+    writeln!(
+        w,
+        "{}",
+        comments
+            .iter()
+            .map(|comment| format!("{indent}/// {}", xml_escape(comment))
+                .trim_end()
+                .to_owned())
+            .join("\n")
+    )?;
+    writeln!(w, "{indent}/// </summary>")
+}
@@ -0,0 +1,760 @@
+use crate::{
+    language::{Language, SupportedLanguage},
+    parser::ParsedData,
+    rename::RenameExt,
+    rust_types::{
+        RustConst, RustConstExpr, RustEnum, RustEnumShared, RustEnumVariant, RustField, RustStruct,
+        RustType, RustTypeAlias, RustTypeFormatError, SpecialRustType,
+    },
+};
+use itertools::Itertools;
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use super::{generic_type_mapping, ScopedCrateTypes};
+
+/// Dart's reserved words, which can't be used as identifiers.
+const KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+/// Members that every generated enum declares.
+const ENUM_MEMBERS: &[&str] = &["index", "value", "values"];
+
+/// All information needed to generate Dart type-code
+///
+/// The generated classes are immutable and serialize themselves with `fromJson` factories and
+/// `toJson` methods, so that no code generation with `build_runner` is needed. Generic types
+/// take a conversion function for each of their type parameters, like `json_serializable`'s
+/// `genericArgumentFactories`. Unit enums become enhanced enums holding their serialized names,
+/// and algebraic enums become sealed classes that can be matched exhaustively with `switch`.
+#[derive(Default)]
+pub struct Dart {
+    /// Conversions from Rust type names to Dart type names.
+    pub type_mappings: HashMap<String, String>,
+    /// Whether or not to exclude the version header that normally appears at the top of generated code.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+    /// HashMap<Identifier, TypeAlias> of the type aliases in the file being generated and the
+    /// ones it imports from other crates, which are needed to convert their values from and to
+    /// JSON.
+    pub aliases: HashMap<String, RustTypeAlias>,
+}
+
+impl Language for Dart {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Dart
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        self.aliases = parsed_data
+            .aliases
+            .iter()
+            .chain(&parsed_data.foreign_aliases)
+            .map(|alias| (alias.id.original.to_string(), alias.clone()))
+            .collect();
+
+        if !self.no_version_header {
+            writeln!(w, "// Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()) {
+            return Ok(mapped.to_owned());
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype)
+            | SpecialRustType::Array(rtype, _)
+            | SpecialRustType::Slice(rtype) => {
+                format!("List<{}>", self.format_type(rtype, generic_types)?)
+            }
+            SpecialRustType::Option(rtype) => nullable(self.format_type(rtype, generic_types)?),
+            SpecialRustType::HashMap(rtype1, rtype2) => format!(
+                "Map<{}, {}>",
+                self.format_type(rtype1, generic_types)?,
+                self.format_type(rtype2, generic_types)?
+            ),
+            SpecialRustType::Unit => "Null".into(),
+            SpecialRustType::String | SpecialRustType::Char => "String".into(),
+            SpecialRustType::DateTime => "DateTime".into(),
+            SpecialRustType::I8
+            | SpecialRustType::I16
+            | SpecialRustType::I32
+            | SpecialRustType::I54
+            | SpecialRustType::I64
+            | SpecialRustType::ISize
+            | SpecialRustType::U8
+            | SpecialRustType::U16
+            | SpecialRustType::U32
+            | SpecialRustType::U53
+            | SpecialRustType::U64
+            | SpecialRustType::USize => "int".into(),
+            SpecialRustType::Bool => "bool".into(),
+            SpecialRustType::F32 | SpecialRustType::F64 => "double".into(),
+        })
+    }
+
+    fn write_imports(
+        &mut self,
+        w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        for crate_name in imports.keys() {
+            writeln!(w, "import '{crate_name}.dart';")?;
+        }
+        writeln!(w)
+    }
+
+    fn write_type_alias(&mut self, w: &mut dyn Write, ty: &RustTypeAlias) -> io::Result<()> {
+        let r#type = self
+            .format_type(&ty.r#type, &ty.generic_types)
+            .map_err(io::Error::other)?;
+
+        write_comments(w, 0, &ty.comments)?;
+        writeln!(
+            w,
+            "typedef {}{} = {type};\n",
+            ty.id.renamed,
+            generic_parameters(&ty.generic_types)
+        )
+    }
+
+    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
+        match c.expr {
+            RustConstExpr::Int(val) => {
+                let ty = self.format_type(&c.r#type, &[]).map_err(io::Error::other)?;
+                writeln!(
+                    w,
+                    "const {ty} {} = {val};\n",
+                    dart_identifier(c.id.original.to_camel_case())
+                )
+            }
+        }
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        let name = &rs.id.renamed;
+        let generics = &rs.generic_types;
+
+        write_comments(w, 0, &rs.comments)?;
+        writeln!(w, "class {name}{} {{", generic_parameters(generics))?;
+
+        if rs.fields.is_empty() {
+            writeln!(w, "  const {name}();\n")?;
+        } else {
+            writeln!(w, "  const {name}({{")?;
+            for field in &rs.fields {
+                let required = if is_nullable(field) { "" } else { "required " };
+                writeln!(w, "    {required}this.{},", field_name(field))?;
+            }
+            writeln!(w, "  }});\n")?;
+            for field in &rs.fields {
+                write_comments(w, 1, &field.comments)?;
+                writeln!(
+                    w,
+                    "  final {} {};",
+                    self.field_type(field, generics)?,
+                    field_name(field)
+                )?;
+            }
+            writeln!(w)?;
+        }
+
+        writeln!(
+            w,
+            "  factory {name}.fromJson({}) {{",
+            from_json_parameters(generics)
+        )?;
+        if rs.fields.is_empty() {
+            writeln!(w, "    return const {name}();")?;
+        } else {
+            writeln!(w, "    final map = json as Map<String, dynamic>;")?;
+            writeln!(w, "    return {name}(")?;
+            for field in &rs.fields {
+                writeln!(
+                    w,
+                    "      {}: {},",
+                    field_name(field),
+                    self.decode_field(
+                        field,
+                        &format!("map[{}]", dart_string(&field.id.renamed)),
+                        generics
+                    )?
+                )?;
+            }
+            writeln!(w, "    );")?;
+        }
+        writeln!(w, "  }}\n")?;
+
+        writeln!(
+            w,
+            "  Map<String, dynamic> toJson({}) {{",
+            to_json_parameters(generics)
+        )?;
+        if rs.fields.is_empty() {
+            writeln!(w, "    return {{}};")?;
+            writeln!(w, "  }}")?;
+            return writeln!(w, "}}\n");
+        }
+        writeln!(w, "    return {{")?;
+        for field in &rs.fields {
+            let name = field_name(field);
+            // serde rejects `null` for fields that aren't optional, so missing defaults are left out.
+            let condition = if field.has_default && !field.ty.is_optional() {
+                format!("if ({name} != null) ")
+            } else {
+                String::new()
+            };
+            writeln!(
+                w,
+                "      {condition}{}: {},",
+                dart_string(&field.id.renamed),
+                self.encode_field(field, &name, generics)?
+            )?;
+        }
+        writeln!(w, "    }};")?;
+        writeln!(w, "  }}")?;
+        writeln!(w, "}}\n")
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        // Generate named types for any anonymous struct variants of this enum
+        self.write_types_for_anonymous_structs(w, e, &|variant_name| {
+            format!("{}{}Inner", &e.shared().id.renamed, variant_name)
+        })?;
+
+        write_comments(w, 0, &e.shared().comments)?;
+        match e {
+            RustEnum::Unit(shared) => write_unit_enum(w, shared),
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => self.write_algebraic_enum(w, tag_key, content_key, shared),
+        }
+    }
+}
+
+impl Dart {
+    fn write_algebraic_enum(
+        &mut self,
+        w: &mut dyn Write,
+        tag_key: &str,
+        content_key: &str,
+        e: &RustEnumShared,
+    ) -> io::Result<()> {
+        let name = &e.id.renamed;
+        let generics = generic_parameters(&e.generic_types);
+        let content_name = dart_identifier(content_key.to_string().to_camel_case());
+
+        let variants = e
+            .variants
+            .iter()
+            .map(|v| Ok((v, self.variant_content(v, e)?)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        writeln!(w, "sealed class {name}{generics} {{")?;
+        writeln!(w, "  const {name}();\n")?;
+        writeln!(
+            w,
+            "  factory {name}.fromJson({}) {{",
+            from_json_parameters(&e.generic_types)
+        )?;
+        writeln!(w, "    final map = json as Map<String, dynamic>;")?;
+        writeln!(w, "    return switch (map[{}]) {{", dart_string(tag_key))?;
+        for (v, content) in &variants {
+            let content = match content {
+                Some(ty) => self
+                    .decode(
+                        ty,
+                        &format!("map[{}]", dart_string(content_key)),
+                        &e.generic_types,
+                    )
+                    .map_err(io::Error::other)?,
+                None => String::new(),
+            };
+            writeln!(
+                w,
+                "      {} => {}{generics}({content}),",
+                dart_string(&v.shared().id.renamed),
+                variant_class(name, v)
+            )?;
+        }
+        writeln!(
+            w,
+            "      final tag => throw ArgumentError.value(tag, {}, 'Unknown {name} variant'),",
+            dart_string(tag_key)
+        )?;
+        writeln!(w, "    }};")?;
+        writeln!(w, "  }}\n")?;
+        writeln!(
+            w,
+            "  Map<String, dynamic> toJson({});",
+            to_json_parameters(&e.generic_types)
+        )?;
+        writeln!(w, "}}\n")?;
+
+        for (v, content) in &variants {
+            let class = variant_class(name, v);
+            write_comments(w, 0, &v.shared().comments)?;
+            writeln!(w, "class {class}{generics} extends {name}{generics} {{")?;
+            match content {
+                Some(ty) => {
+                    writeln!(w, "  const {class}(this.{content_name});\n")?;
+                    writeln!(
+                        w,
+                        "  final {} {content_name};\n",
+                        self.format_type(ty, &e.generic_types)
+                            .map_err(io::Error::other)?
+                    )?;
+                }
+                None => writeln!(w, "  const {class}();\n")?,
+            }
+            writeln!(w, "  @override")?;
+            writeln!(
+                w,
+                "  Map<String, dynamic> toJson({}) {{",
+                to_json_parameters(&e.generic_types)
+            )?;
+            writeln!(w, "    return {{")?;
+            writeln!(
+                w,
+                "      {}: {},",
+                dart_string(tag_key),
+                dart_string(&v.shared().id.renamed)
+            )?;
+            if let Some(ty) = content {
+                writeln!(
+                    w,
+                    "      {}: {},",
+                    dart_string(content_key),
+                    self.encode(ty, &content_name, &e.generic_types)
+                        .map_err(io::Error::other)?
+                )?;
+            }
+            writeln!(w, "    }};")?;
+            writeln!(w, "  }}")?;
+            writeln!(w, "}}\n")?;
+        }
+        Ok(())
+    }
+
+    /// The type of a variant's content, if it has any.
+    fn variant_content(
+        &mut self,
+        variant: &RustEnumVariant,
+        e: &RustEnumShared,
+    ) -> io::Result<Option<RustType>> {
+        Ok(match variant {
+            RustEnumVariant::Unit(_) => None,
+            RustEnumVariant::Tuple { ty, .. } => Some(ty.clone()),
+            RustEnumVariant::AnonymousStruct {
+                fields,
+                shared: variant,
+            } => {
+                let id = format!("{}{}Inner", e.id.renamed, variant.id.original);
+                let parameters = e
+                    .generic_types
+                    .iter()
+                    .filter(|g| fields.iter().any(|f| f.ty.contains_type(g)))
+                    .map(|g| RustType::Simple { id: g.clone() })
+                    .collect::<Vec<_>>();
+                Some(if parameters.is_empty() {
+                    RustType::Simple { id }
+                } else {
+                    RustType::Generic { id, parameters }
+                })
+            }
+        })
+    }
+
+    /// The type of a field, which is nullable if the field is optional or has a default.
+    fn field_type(&mut self, field: &RustField, generic_types: &[String]) -> io::Result<String> {
+        let ty = match field.type_override(SupportedLanguage::Dart) {
+            Some(type_override) => type_override.to_owned(),
+            None => self
+                .format_type(&field.ty, generic_types)
+                .map_err(io::Error::other)?,
+        };
+        Ok(if field.has_default { nullable(ty) } else { ty })
+    }
+
+    fn decode_field(
+        &mut self,
+        field: &RustField,
+        json: &str,
+        generic_types: &[String],
+    ) -> io::Result<String> {
+        let decoded = match field.type_override(SupportedLanguage::Dart) {
+            Some(type_override) => format!("{json} as {type_override}"),
+            None => self
+                .decode(&field.ty, json, generic_types)
+                .map_err(io::Error::other)?,
+        };
+        Ok(if field.has_default && !field.ty.is_optional() {
+            format!("{json} == null ? null : {decoded}")
+        } else {
+            decoded
+        })
+    }
+
+    fn encode_field(
+        &mut self,
+        field: &RustField,
+        value: &str,
+        generic_types: &[String],
+    ) -> io::Result<String> {
+        if field.type_override(SupportedLanguage::Dart).is_some() {
+            return Ok(value.to_owned());
+        }
+        let ty = if field.has_default && !field.ty.is_optional() {
+            RustType::Special(SpecialRustType::Option(Box::new(field.ty.clone())))
+        } else {
+            field.ty.clone()
+        };
+        self.encode(&ty, value, generic_types)
+            .map_err(io::Error::other)
+    }
+
+    /// Whether a type is replaced by a configured type mapping, whose values are used as they are.
+    fn is_mapped(&self, ty: &RustType) -> bool {
+        match ty {
+            RustType::Simple { id } => self.type_mappings.contains_key(id),
+            RustType::Generic { id, parameters } => {
+                generic_type_mapping(&self.type_mappings, id, parameters.len()).is_some()
+            }
+            RustType::Special(special) => self.type_mappings.contains_key(&special.to_string()),
+        }
+    }
+
+    /// The aliased type of a type alias, with its generic parameters substituted.
+    fn resolve_alias(&self, ty: &RustType) -> Option<RustType> {
+        let alias = self.aliases.get(ty.id())?;
+        let arguments = match ty {
+            RustType::Generic { parameters, .. } => parameters.as_slice(),
+            _ => &[],
+        };
+        Some(alias.r#type.substitute(&alias.generic_types, arguments))
+    }
+
+    /// An expression converting the decoded JSON `json` to a value of type `ty`.
+    fn decode(
+        &mut self,
+        ty: &RustType,
+        json: &str,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if self.is_mapped(ty) {
+            return Ok(format!(
+                "{json} as {}",
+                self.format_type(ty, generic_types)?
+            ));
+        }
+        if let Some(aliased) = self.resolve_alias(ty) {
+            return self.decode(&aliased, json, generic_types);
+        }
+
+        Ok(match ty {
+            RustType::Simple { id } if generic_types.contains(id) => {
+                format!("fromJson{id}({json})")
+            }
+            RustType::Simple { id } => format!("{id}.fromJson({json})"),
+            RustType::Generic { parameters, .. } => {
+                let decoders = parameters
+                    .iter()
+                    .map(|p| Ok(format!("(e) => {}", self.decode(p, "e", generic_types)?)))
+                    .collect::<Result<Vec<_>, RustTypeFormatError>>()?;
+                format!(
+                    "{}.fromJson({json}, {})",
+                    self.format_type(ty, generic_types)?,
+                    decoders.join(", ")
+                )
+            }
+            RustType::Special(special) => match special {
+                SpecialRustType::Vec(rtype)
+                | SpecialRustType::Array(rtype, _)
+                | SpecialRustType::Slice(rtype) => format!(
+                    "({json} as List<dynamic>).map((e) => {}).toList()",
+                    self.decode(rtype, "e", generic_types)?
+                ),
+                SpecialRustType::HashMap(rtype1, rtype2) => format!(
+                    "({json} as Map<String, dynamic>).map((k, e) => MapEntry({}, {}))",
+                    self.decode_key(rtype1, "k", generic_types)?,
+                    self.decode(rtype2, "e", generic_types)?
+                ),
+                SpecialRustType::Option(rtype) => format!(
+                    "{json} == null ? null : {}",
+                    self.decode(rtype, json, generic_types)?
+                ),
+                SpecialRustType::Unit => "null".into(),
+                SpecialRustType::String | SpecialRustType::Char => format!("{json} as String"),
+                SpecialRustType::DateTime => format!("DateTime.parse({json} as String)"),
+                SpecialRustType::F32 | SpecialRustType::F64 => {
+                    format!("({json} as num).toDouble()")
+                }
+                SpecialRustType::Bool => format!("{json} as bool"),
+                _ => format!("{json} as int"),
+            },
+        })
+    }
+
+    /// An expression converting the JSON object key `key` to a value of type `ty`.
+    fn decode_key(
+        &mut self,
+        ty: &RustType,
+        key: &str,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(aliased) = self.resolve_alias(ty).filter(|_| !self.is_mapped(ty)) {
+            return self.decode_key(&aliased, key, generic_types);
+        }
+        Ok(match ty {
+            RustType::Special(SpecialRustType::String | SpecialRustType::Char) => key.to_owned(),
+            RustType::Special(SpecialRustType::F32 | SpecialRustType::F64) => {
+                format!("double.parse({key})")
+            }
+            RustType::Special(SpecialRustType::Bool) => format!("{key} == 'true'"),
+            RustType::Special(
+                SpecialRustType::Vec(_)
+                | SpecialRustType::Array(..)
+                | SpecialRustType::Slice(_)
+                | SpecialRustType::HashMap(..)
+                | SpecialRustType::Option(_)
+                | SpecialRustType::Unit
+                | SpecialRustType::DateTime,
+            ) => self.decode(ty, key, generic_types)?,
+            RustType::Special(_) => format!("int.parse({key})"),
+            _ => self.decode(ty, key, generic_types)?,
+        })
+    }
+
+    /// An expression converting `value` of type `ty` to a value that can be encoded as JSON.
+    fn encode(
+        &mut self,
+        ty: &RustType,
+        value: &str,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if self.is_mapped(ty) {
+            return Ok(value.to_owned());
+        }
+        if let Some(aliased) = self.resolve_alias(ty) {
+            return self.encode(&aliased, value, generic_types);
+        }
+
+        Ok(match ty {
+            RustType::Simple { id } if generic_types.contains(id) => format!("toJson{id}({value})"),
+            RustType::Simple { .. } => format!("{value}.toJson()"),
+            RustType::Generic { parameters, .. } => {
+                let encoders = parameters
+                    .iter()
+                    .map(|p| Ok(format!("(e) => {}", self.encode(p, "e", generic_types)?)))
+                    .collect::<Result<Vec<_>, RustTypeFormatError>>()?;
+                format!("{value}.toJson({})", encoders.join(", "))
+            }
+            RustType::Special(special) => match special {
+                SpecialRustType::Vec(rtype)
+                | SpecialRustType::Array(rtype, _)
+                | SpecialRustType::Slice(rtype) => match self.encode(rtype, "e", generic_types)? {
+                    e if e == "e" => value.to_owned(),
+                    e => format!("{value}.map((e) => {e}).toList()"),
+                },
+                SpecialRustType::HashMap(rtype1, rtype2) => {
+                    let k = self.encode_key(rtype1, "k", generic_types)?;
+                    let e = self.encode(rtype2, "e", generic_types)?;
+                    if k == "k" && e == "e" {
+                        value.to_owned()
+                    } else {
+                        format!("{value}.map((k, e) => MapEntry({k}, {e}))")
+                    }
+                }
+                SpecialRustType::Option(rtype) => {
+                    let encoded = self.encode(rtype, value, generic_types)?;
+                    if encoded == value {
+                        encoded
+                    } else if let Some(call) = encoded.strip_prefix(&format!("{value}.")) {
+                        // Null-aware calls short-circuit the rest of the chain.
+                        format!("{value}?.{call}")
+                    } else {
+                        let non_null =
+                            format!("({value} as {})", self.format_type(rtype, generic_types)?);
+                        format!(
+                            "{value} == null ? null : {}",
+                            self.encode(rtype, &non_null, generic_types)?
+                        )
+                    }
+                }
+                SpecialRustType::DateTime => format!("{value}.toIso8601String()"),
+                _ => value.to_owned(),
+            },
+        })
+    }
+
+    /// An expression converting the map key `key` of type `ty` to a JSON object key.
+    fn encode_key(
+        &mut self,
+        ty: &RustType,
+        key: &str,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(aliased) = self.resolve_alias(ty).filter(|_| !self.is_mapped(ty)) {
+            return self.encode_key(&aliased, key, generic_types);
+        }
+        Ok(match ty {
+            RustType::Special(SpecialRustType::String | SpecialRustType::Char) => key.to_owned(),
+            RustType::Special(
+                SpecialRustType::Vec(_)
+                | SpecialRustType::Array(..)
+                | SpecialRustType::Slice(_)
+                | SpecialRustType::HashMap(..)
+                | SpecialRustType::Option(_)
+                | SpecialRustType::Unit
+                | SpecialRustType::DateTime,
+            ) => self.encode(ty, key, generic_types)?,
+            RustType::Special(_) => format!("{key}.toString()"),
+            _ => self.encode(ty, key, generic_types)?,
+        })
+    }
+}
+
+fn write_unit_enum(w: &mut dyn Write, e: &RustEnumShared) -> io::Result<()> {
+    let name = &e.id.renamed;
+    writeln!(w, "enum {name} {{")?;
+    let values = e
+        .variants
+        .iter()
+        .map(|v| {
+            let mut value = Vec::new();
+            write_comments(&mut value, 1, &v.shared().comments)?;
+            write!(
+                value,
+                "  {}({})",
+                enum_value_name(v),
+                dart_string(&v.shared().id.renamed)
+            )?;
+            String::from_utf8(value).map_err(io::Error::other)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    writeln!(w, "{};\n", values.join(",\n"))?;
+    writeln!(w, "  const {name}(this.value);\n")?;
+    writeln!(w, "  final String value;\n")?;
+    writeln!(w, "  static {name} fromJson(Object? json) {{")?;
+    writeln!(w, "    return values.firstWhere(")?;
+    writeln!(w, "      (v) => v.value == json,")?;
+    writeln!(
+        w,
+        "      orElse: () => throw ArgumentError.value(json, 'json', 'Unknown {name} value'),"
+    )?;
+    writeln!(w, "    );")?;
+    writeln!(w, "  }}\n")?;
+    writeln!(w, "  String toJson() => value;")?;
+    writeln!(w, "}}\n")
+}
+
+/// Make a type nullable, unless it already is.
+fn nullable(ty: String) -> String {
+    if ty.ends_with('?') || ty == "Null" || ty == "dynamic" {
+        ty
+    } else {
+        format!("{ty}?")
+    }
+}
+
+fn is_nullable(field: &RustField) -> bool {
+    field.ty.is_optional() || field.has_default
+}
+
+fn field_name(field: &RustField) -> String {
+    field.name_override(SupportedLanguage::Dart).map_or_else(
+        || dart_identifier(field.id.original.to_camel_case()),
+        str::to_owned,
+    )
+}
+
+fn enum_value_name(variant: &RustEnumVariant) -> String {
+    let name = dart_identifier(variant.shared().id.original.to_camel_case());
+    if ENUM_MEMBERS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// The name of the class of an algebraic enum variant.
+fn variant_class(enum_name: &str, variant: &RustEnumVariant) -> String {
+    format!(
+        "{enum_name}{}",
+        variant.shared().id.original.to_pascal_case()
+    )
+}
+
+/// Make a name usable as an identifier, by adding an underscore to reserved words.
+fn dart_identifier(name: String) -> String {
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// A single quoted string literal.
+fn dart_string(s: &str) -> String {
+    format!(
+        "'{}'",
+        s.replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('$', "\\$")
+    )
+}
+
+fn generic_parameters(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generic_types.join(", "))
+    }
+}
+
+/// The parameters of `fromJson`, with a conversion function for each generic parameter.
+fn from_json_parameters(generic_types: &[String]) -> String {
+    std::iter::once("Object? json".to_owned())
+        .chain(
+            generic_types
+                .iter()
+                .map(|g| format!("{g} Function(Object? json) fromJson{g}")),
+        )
+        .join(", ")
+}
+
+/// The parameters of `toJson`, with a conversion function for each generic parameter.
+fn to_json_parameters(generic_types: &[String]) -> String {
+    generic_types
+        .iter()
+        .map(|g| format!("Object? Function({g} value) toJson{g}"))
+        .join(", ")
+}
+
+fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> io::Result<()> {
+    let indent = "  ".repeat(indent);
+    for comment in comments {
+        writeln!(w, "{}", format!("{indent}/// {comment}").trim_end())?;
+    }
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use std::io::Write;
 
 use crate::language::SupportedLanguage;
-use crate::parser::ParsedData;
+use crate::parser::{DecoratorKind, ParsedData};
 use crate::rename::RenameExt;
-use crate::rust_types::{RustConst, RustConstExpr, RustItem, RustTypeFormatError, SpecialRustType};
+use crate::rust_types::{
+    FieldDecorator, RustConst, RustConstExpr, RustItem, RustTypeFormatError, SpecialRustType,
+};
 use crate::{
     language::Language,
     rust_types::{RustEnum, RustEnumVariant, RustField, RustStruct, RustTypeAlias},
@@ -11,7 +13,7 @@ use crate::{
 };
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-use super::CrateTypes;
+use super::{filter_language, CrateTypes};
 
 /// All information needed to generate Go type-code
 #[derive(Default)]
@@ -45,9 +47,17 @@ pub struct Go {
     ///
     /// This, however, is rarely applicable in practice, and having this feature does not justify exposing an unintuitive user interface.
     pub no_pointer_slice: bool,
+    /// Struct tags added to every field after the `json` tag, e.g. `db:"{field}" yaml:"{name}"`.
+    /// `{name}` is replaced with the serialized name and `{field}` with the Rust field name.
+    /// Tags from `#[typeshare(go(tag = "..."))]` replace template tags with the same key.
+    pub tag_template: Option<String>,
 }
 
 impl Language for Go {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Go
+    }
+
     fn generate_types(
         &mut self,
         w: &mut dyn Write,
@@ -64,13 +74,15 @@ impl Language for Go {
             ..
         } = data;
 
-        let mut items = aliases
-            .into_iter()
-            .map(RustItem::Alias)
-            .chain(structs.into_iter().map(RustItem::Struct))
-            .chain(enums.into_iter().map(RustItem::Enum))
-            .chain(consts.into_iter().map(RustItem::Const))
-            .collect::<Vec<_>>();
+        let mut items = filter_language(
+            aliases
+                .into_iter()
+                .map(RustItem::Alias)
+                .chain(structs.into_iter().map(RustItem::Struct))
+                .chain(enums.into_iter().map(RustItem::Enum))
+                .chain(consts.into_iter().map(RustItem::Const)),
+            self.language(),
+        );
 
         topsort(&mut items);
 
@@ -238,6 +250,16 @@ impl Language for Go {
                 .unwrap_or_default()
         )?;
 
+        // Embedded structs, from `#[typeshare(go = "...")]`
+        for embed in rs
+            .decorators
+            .get(&DecoratorKind::Language(SupportedLanguage::Go))
+            .into_iter()
+            .flatten()
+        {
+            writeln!(w, "\t{embed}")?;
+        }
+
         rs.fields
             .iter()
             .try_for_each(|f| self.write_field(w, f, rs.generic_types.as_slice()))?;
@@ -492,14 +514,6 @@ func ({short_name} {full_name}) MarshalJSON() ([]byte, error) {{
         field: &RustField,
         generic_types: &[String],
     ) -> std::io::Result<()> {
-        fn option_symbol(optional: bool) -> &'static str {
-            if optional {
-                ",omitempty"
-            } else {
-                ""
-            }
-        }
-
         write_comments(w, 1, &field.comments)?;
 
         let type_name = match field.type_override(SupportedLanguage::Go) {
@@ -510,19 +524,61 @@ func ({short_name} {full_name}) MarshalJSON() ([]byte, error) {{
         };
 
         let go_type = self.acronyms_to_uppercase(&type_name);
+        let decorators = field
+            .decorators
+            .get(&SupportedLanguage::Go)
+            .into_iter()
+            .flatten();
+        let has_decorator = |word: &str| {
+            decorators
+                .clone()
+                .any(|d| matches!(d, FieldDecorator::Word(w) if w == word))
+        };
+
         let is_optional = field.ty.is_optional() || field.has_default;
         let formatted_renamed_id = format!("{:?}", &field.id.renamed);
         let renamed_id = &formatted_renamed_id[1..formatted_renamed_id.len() - 1];
+        let json_tag = format!(
+            "json:\"{}{}{}\"",
+            renamed_id,
+            if is_optional || has_decorator("omitempty") {
+                ",omitempty"
+            } else {
+                ""
+            },
+            if has_decorator("string") {
+                ",string"
+            } else {
+                ""
+            },
+        );
+        let template_tags = self.tag_template.as_ref().map(|template| {
+            template
+                .replace("{name}", renamed_id)
+                .replace("{field}", &field.id.original)
+        });
+        let field_tags = decorators.filter_map(|d| match d {
+            FieldDecorator::NameValue(name, tag) if name == "tag" => Some(tag.clone()),
+            _ => None,
+        });
+
+        let field_name = match field.name_override(SupportedLanguage::Go) {
+            Some(name) => name.to_owned(),
+            None => self.format_field_name(field.id.original.to_string(), true),
+        };
         writeln!(
             w,
-            "\t{} {}{} `json:\"{}{}\"`",
-            self.format_field_name(field.id.original.to_string(), true),
+            "\t{} {}{} `{}`",
+            field_name,
             (field.has_default && !field.ty.is_optional())
                 .then_some("*")
                 .unwrap_or_default(),
             go_type,
-            renamed_id,
-            option_symbol(is_optional),
+            merge_struct_tags(
+                std::iter::once(json_tag)
+                    .chain(template_tags)
+                    .chain(field_tags)
+            ),
         )?;
 
         Ok(())
@@ -576,6 +632,49 @@ fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> std:
         .try_for_each(|comment| write_comment(w, indent, comment))
 }
 
+/// Merge Go struct tags like `json:"id" db:"id"` into one, where a later tag replaces an
+/// earlier one with the same key.
+fn merge_struct_tags(tags: impl IntoIterator<Item = String>) -> String {
+    let mut merged: Vec<String> = Vec::new();
+
+    for tags in tags {
+        for tag in split_struct_tags(&tags) {
+            let key = |tag: &str| tag.split(':').next().unwrap_or_default().to_owned();
+            match merged.iter_mut().find(|existing| key(existing) == key(tag)) {
+                Some(existing) => *existing = tag.to_owned(),
+                None => merged.push(tag.to_owned()),
+            }
+        }
+    }
+
+    merged.join(" ")
+}
+
+/// Split a Go struct tag like `db:"id" yaml:"id,omitempty"` into its `key:"value"` parts.
+fn split_struct_tags(tags: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in tags.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                parts.extend(start.take().map(|start| &tags[start..i]));
+                continue;
+            }
+            _ => (),
+        }
+        start.get_or_insert(i);
+    }
+    parts.extend(start.map(|start| &tags[start..]));
+
+    parts
+}
+
 fn convert_acronyms_to_uppercase(uppercase_acronyms: Vec<String>, name: &str) -> String {
     let mut res = name.to_string();
     for a in &uppercase_acronyms {
@@ -620,4 +719,16 @@ mod test {
         let go_slice = super::Language::format_special_type(&mut go, &optional_slice, &[]).unwrap();
         assert_eq!(go_slice, "[]int");
     }
+
+    #[test]
+    fn merge_struct_tags() {
+        assert_eq!(
+            super::merge_struct_tags([
+                r#"json:"id,omitempty""#.to_string(),
+                r#"db:"id"  yaml:"id""#.to_string(),
+                r#"db:"user_id" validate:"required,oneof=a b""#.to_string(),
+            ]),
+            r#"json:"id,omitempty" db:"user_id" yaml:"id" validate:"required,oneof=a b""#
+        );
+    }
 }
@@ -0,0 +1,450 @@
+use crate::{
+    language::{Language, SupportedLanguage},
+    parser::{DecoratorKind, ParsedData},
+    rust_types::{
+        RustEnum, RustEnumVariant, RustField, RustItem, RustStruct, RustType, RustTypeFormatError,
+        SpecialRustType,
+    },
+};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    io::{self, Write},
+};
+
+use super::{
+    generated_items, generic_type_mapping, substitute_generic_parameters, used_imports, CrateTypes,
+    ScopedCrateTypes,
+};
+
+/// The struct decorator that mirrors a struct as an input type, `#[typeshare(graphql = "input")]`.
+const INPUT: &str = "input";
+
+/// The scalars built into GraphQL, which don't have to be declared.
+const BUILT_IN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// The scalar used for maps, which GraphQL has no type for.
+const MAP_SCALAR: &str = "JSON";
+
+/// The scalar used for dates and times.
+const DATE_TIME_SCALAR: &str = "DateTime";
+
+/// All information needed to generate GraphQL SDL.
+///
+/// Structs become object types, unit enums become enums and algebraic enums become unions of
+/// an object type per variant. Structs annotated with `#[typeshare(graphql = "input")]` are
+/// mirrored as an input type as well. GraphQL has neither type aliases nor constants, so
+/// references to aliases are replaced with the aliased type and constants are skipped.
+#[derive(Default)]
+pub struct GraphQL {
+    /// Mappings from Rust type names to GraphQL type names
+    pub type_mappings: HashMap<String, String>,
+    /// Whether or not to exclude the version header that normally appears at the top of generated code.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+    /// HashMap<Identifier, AliasedType> of the type aliases in the file being generated and
+    /// the ones it imports from other crates
+    pub aliases: HashMap<String, RustType>,
+    /// The custom scalars used by the file being generated
+    pub scalars: BTreeSet<String>,
+    /// The structs of the file being generated that are mirrored as input types
+    pub input_types: HashSet<String>,
+    /// The types of the file being generated that can't be used in input types
+    pub output_types: HashSet<String>,
+    /// Whether the fields of an input type are being written
+    pub writing_input: bool,
+}
+
+impl Language for GraphQL {
+    fn generate_types(
+        &mut self,
+        w: &mut dyn Write,
+        all_types: &CrateTypes,
+        data: ParsedData,
+    ) -> io::Result<()> {
+        self.begin_file(w, &data)?;
+        if data.multi_file {
+            self.write_imports(w, used_imports(&data, all_types))?;
+        }
+
+        // Scalars are declared before the types, but only known once the types are written.
+        let mut body = Vec::new();
+        for item in generated_items(data, self.language()) {
+            match &item {
+                RustItem::Struct(rs) => self.write_struct(&mut body, rs)?,
+                RustItem::Enum(e) => self.write_enum(&mut body, e)?,
+                RustItem::Alias(_) | RustItem::Const(_) => (),
+            }
+        }
+
+        for scalar in &self.scalars {
+            writeln!(w, "scalar {scalar}")?;
+        }
+        if !self.scalars.is_empty() {
+            writeln!(w)?;
+        }
+        let body = String::from_utf8(body).map_err(io::Error::other)?;
+        writeln!(w, "{}", body.trim_end())
+    }
+
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::GraphQL
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn format_simple_type(
+        &mut self,
+        base: &String,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(base).cloned() {
+            Ok(self.scalar(mapped))
+        } else if let Some(aliased) = self.aliases.get(base).cloned() {
+            self.format_type(&aliased, generic_types)
+        } else if generic_types.contains(base) {
+            Err(RustTypeFormatError::GenericsForbiddenInGraphQL(
+                base.to_owned(),
+            ))
+        } else if self.writing_input && self.input_types.contains(base) {
+            Ok(format!("{base}Input"))
+        } else if self.writing_input && self.output_types.contains(base) {
+            Err(RustTypeFormatError::OutputTypeInGraphQLInput(
+                base.to_owned(),
+            ))
+        } else {
+            Ok(base.to_owned())
+        }
+    }
+
+    fn format_generic_type(
+        &mut self,
+        base: &String,
+        parameters: &[RustType],
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        let mapped = generic_type_mapping(&self.type_mappings, base, parameters.len())
+            .cloned()
+            .ok_or_else(|| RustTypeFormatError::GenericsForbiddenInGraphQL(base.to_owned()))?;
+        substitute_generic_parameters(&mapped, parameters, |p| self.format_type(p, generic_types))
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()).cloned() {
+            return Ok(self.scalar(mapped));
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype)
+            | SpecialRustType::Array(rtype, _)
+            | SpecialRustType::Slice(rtype) => {
+                format!("[{}]", self.field_type(rtype, generic_types)?)
+            }
+            // Types are nullable unless marked with `!`, see `field_type`.
+            SpecialRustType::Option(rtype) => self.format_type(rtype, generic_types)?,
+            SpecialRustType::HashMap(_, _) => self.scalar(MAP_SCALAR.into()),
+            SpecialRustType::DateTime => self.scalar(DATE_TIME_SCALAR.into()),
+            SpecialRustType::String | SpecialRustType::Char => "String".into(),
+            SpecialRustType::I8
+            | SpecialRustType::I16
+            | SpecialRustType::I32
+            | SpecialRustType::U8
+            | SpecialRustType::U16 => "Int".into(),
+            // `Int` is a signed 32 bit integer, larger ones are only exact as a `Float`.
+            SpecialRustType::U32
+            | SpecialRustType::I54
+            | SpecialRustType::U53
+            | SpecialRustType::F32
+            | SpecialRustType::F64 => "Float".into(),
+            SpecialRustType::Bool => "Boolean".into(),
+            SpecialRustType::Unit
+            | SpecialRustType::I64
+            | SpecialRustType::U64
+            | SpecialRustType::ISize
+            | SpecialRustType::USize => {
+                return Err(RustTypeFormatError::UnsupportedSpecialType(
+                    special_ty.to_string(),
+                ))
+            }
+        })
+    }
+
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        self.scalars.clear();
+        self.aliases = parsed_data
+            .aliases
+            .iter()
+            .chain(&parsed_data.foreign_aliases)
+            .filter(|alias| alias.generic_types.is_empty())
+            .map(|alias| (alias.id.original.to_string(), alias.r#type.clone()))
+            .collect();
+        self.input_types = parsed_data
+            .structs
+            .iter()
+            .filter(|rs| is_input(rs))
+            .map(|rs| rs.id.original.to_string())
+            .collect();
+        // Input types can't reference output types or unions.
+        self.output_types = parsed_data
+            .structs
+            .iter()
+            .filter(|rs| !is_input(rs))
+            .map(|rs| rs.id.original.to_string())
+            .chain(
+                parsed_data
+                    .enums
+                    .iter()
+                    .filter(|e| matches!(e, RustEnum::Algebraic { .. }))
+                    .map(|e| e.shared().id.original.to_string()),
+            )
+            .collect();
+
+        if !self.no_version_header {
+            writeln!(
+                w,
+                "# Generated by typeshare {}\n",
+                env!("CARGO_PKG_VERSION")
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_imports(
+        &mut self,
+        w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        // The `# import` comments of graphql-import, SDL itself has no imports. Aliases are
+        // replaced with the aliased type, so they are never imported.
+        let mut imported = false;
+        for (crate_name, types) in &imports {
+            let types = types
+                .iter()
+                .filter(|ty| !self.aliases.contains_key(**ty))
+                .collect::<Vec<_>>();
+            if types.is_empty() {
+                continue;
+            }
+            writeln!(
+                w,
+                "# import {} from \"{crate_name}.graphql\"",
+                types.iter().join(", ")
+            )?;
+            imported = true;
+        }
+        if imported {
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        if let Some(generic) = rs.generic_types.first() {
+            return Err(io::Error::other(
+                RustTypeFormatError::GenericsForbiddenInGraphQL(generic.to_owned()),
+            ));
+        }
+
+        write_description(w, 0, &rs.comments)?;
+        writeln!(w, "type {} {{", rs.id.renamed)?;
+        self.write_fields(w, &rs.fields)?;
+        writeln!(w, "}}\n")?;
+
+        if self.input_types.contains(&rs.id.original) {
+            write_description(w, 0, &rs.comments)?;
+            writeln!(w, "input {}Input {{", rs.id.renamed)?;
+            self.writing_input = true;
+            let fields = self.write_fields(w, &rs.fields);
+            self.writing_input = false;
+            fields?;
+            writeln!(w, "}}\n")?;
+        }
+        Ok(())
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        let shared = e.shared();
+        if let Some(generic) = shared.generic_types.first() {
+            return Err(io::Error::other(
+                RustTypeFormatError::GenericsForbiddenInGraphQL(generic.to_owned()),
+            ));
+        }
+        write_description(w, 0, &shared.comments)?;
+
+        match e {
+            RustEnum::Unit(shared) => {
+                writeln!(w, "enum {} {{", shared.id.renamed)?;
+                for variant in &shared.variants {
+                    let variant = variant.shared();
+                    let value = &variant.id.renamed;
+                    if !is_name(value) || ["true", "false", "null"].contains(&value.as_str()) {
+                        return Err(invalid_name(value));
+                    }
+                    write_description(w, 1, &variant.comments)?;
+                    writeln!(w, "  {value}")?;
+                }
+                writeln!(w, "}}\n")
+            }
+            RustEnum::Algebraic {
+                content_key,
+                shared,
+                ..
+            } => {
+                let variant_type = |v: &RustEnumVariant| {
+                    format!("{}{}", shared.id.renamed, v.shared().id.original)
+                };
+                writeln!(
+                    w,
+                    "union {} = {}\n",
+                    shared.id.renamed,
+                    shared.variants.iter().map(variant_type).join(" | ")
+                )?;
+
+                for variant in &shared.variants {
+                    write_description(w, 0, &variant.shared().comments)?;
+                    writeln!(w, "type {} {{", variant_type(variant))?;
+                    match variant {
+                        RustEnumVariant::Unit(_) => write_placeholder_field(w)?,
+                        RustEnumVariant::Tuple { ty, .. } => {
+                            if !is_name(content_key) {
+                                return Err(invalid_name(content_key));
+                            }
+                            let ty = self.field_type(ty, &[]).map_err(io::Error::other)?;
+                            writeln!(w, "  {content_key}: {ty}")?;
+                        }
+                        RustEnumVariant::AnonymousStruct { fields, .. } => {
+                            self.write_fields(w, fields)?
+                        }
+                    }
+                    writeln!(w, "}}\n")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl GraphQL {
+    /// The type of a field, which is non-null unless the Rust type is an `Option`.
+    fn field_type(
+        &mut self,
+        ty: &RustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(aliased) = self.aliases.get(ty.id()).cloned() {
+            return self.field_type(&aliased, generic_types);
+        }
+
+        let formatted = self.format_type(ty, generic_types)?;
+        Ok(if ty.is_optional() {
+            formatted
+        } else {
+            format!("{formatted}!")
+        })
+    }
+
+    fn write_fields(&mut self, w: &mut dyn Write, fields: &[RustField]) -> io::Result<()> {
+        if fields.is_empty() {
+            return write_placeholder_field(w);
+        }
+
+        for field in fields {
+            let name = field
+                .name_override(SupportedLanguage::GraphQL)
+                .unwrap_or(&field.id.renamed);
+            if !is_name(name) {
+                return Err(invalid_name(name));
+            }
+            let mut ty = match field.type_override(SupportedLanguage::GraphQL) {
+                Some(type_override) => type_override.to_owned(),
+                None => self.field_type(&field.ty, &[]).map_err(io::Error::other)?,
+            };
+            // Inputs can leave out fields that serde fills with a default.
+            if self.writing_input && field.has_default {
+                ty = ty.trim_end_matches('!').to_owned();
+            }
+
+            write_description(w, 1, &field.comments)?;
+            writeln!(w, "  {name}: {ty}")?;
+        }
+        Ok(())
+    }
+
+    /// Declare a type as a custom scalar, unless it's a built in one.
+    fn scalar(&mut self, ty: String) -> String {
+        if is_name(&ty) && !BUILT_IN_SCALARS.contains(&ty.as_str()) {
+            self.scalars.insert(ty.clone());
+        }
+        ty
+    }
+}
+
+/// Whether a struct is mirrored as an input type.
+fn is_input(rs: &RustStruct) -> bool {
+    rs.decorators
+        .get(&DecoratorKind::Language(SupportedLanguage::GraphQL))
+        .is_some_and(|decorators| decorators.iter().any(|decorator| decorator == INPUT))
+}
+
+/// Whether a string is a GraphQL name, which are ASCII identifiers.
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+fn invalid_name(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("`{name}` is not a valid GraphQL name"),
+    )
+}
+
+/// GraphQL types need at least one field, so empty ones get an unused nullable field.
+fn write_placeholder_field(w: &mut dyn Write) -> io::Result<()> {
+    writeln!(w, "  _: Boolean")
+}
+
+fn write_description(w: &mut dyn Write, indent: usize, comments: &[String]) -> io::Result<()> {
+    let indent = "  ".repeat(indent);
+    let lines = comments
+        .iter()
+        .map(|comment| comment.replace(r#"""""#, r#"\""""#))
+        .collect::<Vec<_>>();
+
+    match lines.as_slice() {
+        [] => Ok(()),
+        [line] if !line.ends_with('"') => writeln!(w, "{indent}\"\"\"{line}\"\"\""),
+        lines => {
+            writeln!(w, "{indent}\"\"\"")?;
+            for line in lines {
+                writeln!(w, "{indent}{line}")?;
+            }
+            writeln!(w, "{indent}\"\"\"")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_name;
+
+    #[test]
+    fn names_are_ascii_identifiers() {
+        assert!(is_name("userId"));
+        assert!(is_name("_private"));
+        assert!(is_name("SCREAMING_CASE2"));
+        assert!(!is_name(""));
+        assert!(!is_name("2fa"));
+        assert!(!is_name("x-trace"));
+        assert!(!is_name("naïve"));
+    }
+}
@@ -0,0 +1,599 @@
+use crate::{
+    language::{CrateTypes, Language, SupportedLanguage},
+    parser::ParsedData,
+    rename::RenameExt,
+    rust_types::{
+        RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustItem, RustStruct,
+        RustType, RustTypeFormatError, SpecialRustType,
+    },
+};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, Write},
+};
+
+use super::{generated_items, used_imports, ScopedCrateTypes};
+
+/// The `@Nullable` annotation used when none is configured.
+const DEFAULT_NULLABLE_ANNOTATION: &str = "javax.annotation.Nullable";
+
+/// Java's reserved words, which can't be used as component names.
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+    "record",
+    "var",
+    "yield",
+    "sealed",
+    "permits",
+];
+
+/// All information needed to generate Java type-code
+///
+/// Java allows one public top-level class per file, so the generated types are nested in a
+/// class named after the crate in multi file mode, e.g. `AccountsTypes` for the `accounts`
+/// crate, or `class_name` otherwise. Structs become
+/// records, unit enums become enums and algebraic enums become sealed interfaces that Jackson
+/// serializes with `@JsonTypeInfo` and `@JsonSubTypes`. Type aliases have no Java equivalent,
+/// so references to them are replaced with the aliased type.
+#[derive(Default)]
+pub struct Java {
+    /// Name of the Java package. In multi file mode, each crate becomes a package within it.
+    pub package: String,
+    /// Name of the class that the generated types are nested in, in single file mode.
+    /// This has to match the name of the output file, and defaults to `Types`.
+    pub class_name: String,
+    /// The fully qualified `@Nullable` annotation, `javax.annotation.Nullable` by default.
+    pub nullable_annotation: String,
+    /// Conversions from Rust type names to Java type names.
+    pub type_mappings: HashMap<String, String>,
+    /// Whether or not to exclude the version header that normally appears at the top of generated code.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+    /// BTreeSet<QualifiedClassName> of the file being generated
+    pub imports: BTreeSet<String>,
+    /// HashMap<Identifier, AliasedType> of the type aliases in the file being generated and
+    /// the ones it imports from other crates
+    pub aliases: HashMap<String, RustType>,
+}
+
+impl Language for Java {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Java
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn generate_types(
+        &mut self,
+        w: &mut dyn Write,
+        all_types: &CrateTypes,
+        data: ParsedData,
+    ) -> io::Result<()> {
+        self.imports.clear();
+        self.aliases = data
+            .aliases
+            .iter()
+            .chain(&data.foreign_aliases)
+            .filter(|alias| alias.generic_types.is_empty())
+            .map(|alias| (alias.id.original.to_string(), alias.r#type.clone()))
+            .collect();
+
+        let (package, class_name) = if data.multi_file {
+            (
+                self.package_for(data.crate_name.as_str()),
+                Self::crate_class_name(data.crate_name.as_str()),
+            )
+        } else if self.class_name.is_empty() {
+            (self.package.clone(), "Types".to_owned())
+        } else {
+            (self.package.clone(), self.class_name.clone())
+        };
+        if data.multi_file {
+            self.write_imports(w, used_imports(&data, all_types))?;
+        }
+
+        let items = generated_items(data, self.language());
+        check_nested_names(&class_name, &items)?;
+
+        let mut body = Vec::new();
+        for item in items {
+            match &item {
+                RustItem::Struct(rs) => self.write_struct(&mut body, rs)?,
+                RustItem::Enum(e) => self.write_enum(&mut body, e)?,
+                // Aliases are replaced by the aliased type.
+                RustItem::Alias(_) => (),
+                RustItem::Const(c) => self.write_const(&mut body, c)?,
+            }
+        }
+        let body = String::from_utf8(body).map_err(io::Error::other)?;
+
+        if !self.no_version_header {
+            writeln!(w, "/**")?;
+            writeln!(w, " * Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
+            writeln!(w, " */")?;
+            writeln!(w)?;
+        }
+        if !package.is_empty() {
+            writeln!(w, "package {package};\n")?;
+        }
+        if !self.imports.is_empty() {
+            for import in &self.imports {
+                writeln!(w, "import {import};")?;
+            }
+            writeln!(w)?;
+        }
+
+        writeln!(w, "public final class {class_name} {{")?;
+        writeln!(w, "    private {class_name}() {{}}\n")?;
+        for line in body.trim_end().lines() {
+            if line.is_empty() {
+                writeln!(w)?;
+            } else {
+                writeln!(w, "    {line}")?;
+            }
+        }
+        writeln!(w, "}}")
+    }
+
+    fn format_simple_type(
+        &mut self,
+        base: &String,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(base) {
+            Ok(mapped.to_owned())
+        } else if generic_types.contains(base) {
+            Ok(base.to_owned())
+        } else if let Some(aliased) = self.aliases.get(base).cloned() {
+            self.format_type(&aliased, generic_types)
+        } else {
+            Ok(base.to_owned())
+        }
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()) {
+            return Ok(mapped.to_owned());
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype)
+            | SpecialRustType::Array(rtype, _)
+            | SpecialRustType::Slice(rtype) => {
+                self.imports.insert("java.util.List".into());
+                format!("List<{}>", self.format_type(rtype, generic_types)?)
+            }
+            // Nullability is marked on the field, see `write_struct`.
+            SpecialRustType::Option(rtype) => self.format_type(rtype, generic_types)?,
+            SpecialRustType::HashMap(rtype1, rtype2) => {
+                self.imports.insert("java.util.Map".into());
+                format!(
+                    "Map<{}, {}>",
+                    self.format_type(rtype1, generic_types)?,
+                    self.format_type(rtype2, generic_types)?
+                )
+            }
+            SpecialRustType::Unit => "Void".into(),
+            // Char in Java is 16 bits long, so we need to use String
+            SpecialRustType::String | SpecialRustType::Char => "String".into(),
+            SpecialRustType::DateTime => {
+                self.imports.insert("java.time.OffsetDateTime".into());
+                "OffsetDateTime".into()
+            }
+            // Java has no unsigned integers, so they use the next larger type.
+            SpecialRustType::I8 => "Byte".into(),
+            SpecialRustType::I16 | SpecialRustType::U8 => "Short".into(),
+            SpecialRustType::I32 | SpecialRustType::ISize | SpecialRustType::U16 => {
+                "Integer".into()
+            }
+            SpecialRustType::I54
+            | SpecialRustType::I64
+            | SpecialRustType::U32
+            | SpecialRustType::U53
+            | SpecialRustType::USize => "Long".into(),
+            SpecialRustType::U64 => {
+                self.imports.insert("java.math.BigInteger".into());
+                "BigInteger".into()
+            }
+            SpecialRustType::Bool => "Boolean".into(),
+            SpecialRustType::F32 => "Float".into(),
+            SpecialRustType::F64 => "Double".into(),
+        })
+    }
+
+    fn write_imports(
+        &mut self,
+        _w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        // Imports are collected and written with the ones needed by the generated types.
+        for (crate_name, types) in imports {
+            let class = format!(
+                "{}.{}",
+                self.package_for(crate_name.as_str()),
+                Self::crate_class_name(crate_name.as_str())
+            );
+            // Aliases are replaced by the aliased type, so they are never imported.
+            self.imports.extend(
+                types
+                    .into_iter()
+                    .filter(|ty| !self.aliases.contains_key(*ty))
+                    .map(|ty| format!("{class}.{ty}")),
+            );
+        }
+        Ok(())
+    }
+
+    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
+        match c.expr {
+            RustConstExpr::Int(val) => {
+                let ty = self.format_type(&c.r#type, &[]).map_err(io::Error::other)?;
+                writeln!(
+                    w,
+                    "public static final {} {} = {val}{};\n",
+                    unboxed(&ty).unwrap_or(&ty),
+                    c.id.original.to_screaming_snake_case(),
+                    if ty == "Long" { "L" } else { "" }
+                )
+            }
+        }
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        let components = rs
+            .fields
+            .iter()
+            .map(|f| self.format_component(f, &rs.generic_types))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let params = rs
+            .fields
+            .iter()
+            .filter(|f| !f.comments.is_empty())
+            .map(|f| format!("@param {} {}", component_name(f), f.comments.join(" ")))
+            .collect::<Vec<_>>();
+        let mut comments = rs.comments.clone();
+        if !comments.is_empty() && !params.is_empty() {
+            comments.push(String::new());
+        }
+        comments.extend(params);
+        write_comments(w, 0, &comments)?;
+
+        if components.is_empty() {
+            writeln!(
+                w,
+                "public record {}{}() {{}}\n",
+                rs.id.renamed,
+                generic_parameters(&rs.generic_types)
+            )
+        } else {
+            writeln!(
+                w,
+                "public record {}{}(\n    {}\n) {{}}\n",
+                rs.id.renamed,
+                generic_parameters(&rs.generic_types),
+                components.join(",\n    ")
+            )
+        }
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        // Generate named types for any anonymous struct variants of this enum
+        self.write_types_for_anonymous_structs(w, e, &|variant_name| {
+            format!("{}{}Inner", &e.shared().id.renamed, variant_name)
+        })?;
+
+        write_comments(w, 0, &e.shared().comments)?;
+        self.imports
+            .insert("com.fasterxml.jackson.annotation.JsonProperty".into());
+
+        match e {
+            RustEnum::Unit(shared) => {
+                writeln!(w, "public enum {} {{", shared.id.renamed)?;
+                let constants = shared
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let mut constant = Vec::new();
+                        write_comments(&mut constant, 1, &v.shared().comments)?;
+                        write!(
+                            constant,
+                            "    @JsonProperty({:?})\n    {}",
+                            v.shared().id.renamed,
+                            v.shared().id.original.to_screaming_snake_case()
+                        )?;
+                        String::from_utf8(constant).map_err(io::Error::other)
+                    })
+                    .collect::<io::Result<Vec<_>>>()?;
+                writeln!(w, "{}", constants.join(",\n"))?;
+            }
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => {
+                self.imports
+                    .insert("com.fasterxml.jackson.annotation.JsonSubTypes".into());
+                self.imports
+                    .insert("com.fasterxml.jackson.annotation.JsonTypeInfo".into());
+
+                let name = &shared.id.renamed;
+                let generics = generic_parameters(&shared.generic_types);
+                writeln!(
+                    w,
+                    "@JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.PROPERTY, property = {tag_key:?})"
+                )?;
+                writeln!(w, "@JsonSubTypes({{")?;
+                writeln!(
+                    w,
+                    "{}",
+                    shared
+                        .variants
+                        .iter()
+                        .map(|v| format!(
+                            "    @JsonSubTypes.Type(value = {name}.{}.class, name = {:?})",
+                            v.shared().id.original.to_pascal_case(),
+                            v.shared().id.renamed
+                        ))
+                        .join(",\n")
+                )?;
+                writeln!(w, "}})")?;
+                writeln!(w, "public sealed interface {name}{generics} {{")?;
+
+                for v in &shared.variants {
+                    let content = match v {
+                        RustEnumVariant::Unit(_) => None,
+                        RustEnumVariant::Tuple { ty, .. } => Some(
+                            self.format_type(ty, &shared.generic_types)
+                                .map_err(io::Error::other)?,
+                        ),
+                        RustEnumVariant::AnonymousStruct {
+                            fields,
+                            shared: variant,
+                        } => {
+                            let inner_generics = shared
+                                .generic_types
+                                .iter()
+                                .filter(|g| fields.iter().any(|f| f.ty.contains_type(g)))
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            Some(format!(
+                                "{name}{}Inner{}",
+                                variant.id.original,
+                                generic_parameters(&inner_generics)
+                            ))
+                        }
+                    };
+
+                    write_comments(w, 1, &v.shared().comments)?;
+                    writeln!(
+                        w,
+                        "    record {}{generics}({}) implements {name}{generics} {{}}",
+                        v.shared().id.original.to_pascal_case(),
+                        content
+                            .map(|ty| format!(
+                                "@JsonProperty({content_key:?}) {ty} {}",
+                                java_identifier(content_key.to_string().to_camel_case())
+                            ))
+                            .unwrap_or_default()
+                    )?;
+                }
+            }
+        }
+
+        writeln!(w, "}}\n")
+    }
+}
+
+/// Check that no nested class has the name of a class it is nested in, which Java forbids.
+fn check_nested_names(class_name: &str, items: &[RustItem]) -> io::Result<()> {
+    let clash = |nested: &str, enclosing: &str| {
+        if nested == enclosing {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the Java class `{nested}` can't be nested in a class of the same name"),
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    for item in items {
+        let name = match item {
+            RustItem::Struct(rs) => &rs.id.renamed,
+            RustItem::Enum(e) => &e.shared().id.renamed,
+            RustItem::Alias(_) | RustItem::Const(_) => continue,
+        };
+        clash(name, class_name)?;
+        if let RustItem::Enum(RustEnum::Algebraic { shared, .. }) = item {
+            for variant in &shared.variants {
+                let record = variant.shared().id.original.to_pascal_case();
+                clash(&record, class_name)?;
+                clash(&record, name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Java {
+    /// The class that a crate's types are nested in, and the name of its file, in multi file
+    /// mode.
+    pub fn crate_class_name(crate_name: &str) -> String {
+        format!("{}Types", crate_name.to_string().to_pascal_case())
+    }
+
+    /// The package of a crate's types in multi file mode.
+    fn package_for(&self, crate_name: &str) -> String {
+        if self.package.is_empty() {
+            crate_name.to_owned()
+        } else {
+            format!("{}.{crate_name}", self.package)
+        }
+    }
+
+    /// A record component for a field, like `@JsonProperty("id") int id`.
+    fn format_component(
+        &mut self,
+        field: &RustField,
+        generic_types: &[String],
+    ) -> io::Result<String> {
+        self.imports
+            .insert("com.fasterxml.jackson.annotation.JsonProperty".into());
+
+        let nullable = field.ty.is_optional() || field.has_default;
+        let ty = match field.type_override(SupportedLanguage::Java) {
+            Some(type_override) => type_override.to_owned(),
+            None => {
+                let ty = self
+                    .format_type(&field.ty, generic_types)
+                    .map_err(io::Error::other)?;
+                match (&field.ty, unboxed(&ty)) {
+                    // Required numbers and booleans use primitive types.
+                    (RustType::Special(_), Some(primitive)) if !nullable => primitive.to_owned(),
+                    _ => ty,
+                }
+            }
+        };
+
+        let nullable = if nullable {
+            let annotation = if self.nullable_annotation.is_empty() {
+                DEFAULT_NULLABLE_ANNOTATION
+            } else {
+                self.nullable_annotation.as_str()
+            };
+            self.imports.insert(annotation.to_owned());
+            format!("@{} ", annotation.rsplit('.').next().unwrap_or(annotation))
+        } else {
+            String::new()
+        };
+
+        // serde rejects `null` for fields that aren't optional, so missing defaults are left out.
+        let include = if field.has_default && !field.ty.is_optional() {
+            self.imports
+                .insert("com.fasterxml.jackson.annotation.JsonInclude".into());
+            "@JsonInclude(JsonInclude.Include.NON_NULL) "
+        } else {
+            ""
+        };
+
+        Ok(format!(
+            "@JsonProperty({:?}) {include}{nullable}{ty} {}",
+            field.id.renamed,
+            component_name(field)
+        ))
+    }
+}
+
+/// The primitive type for a boxed type, if there is one.
+fn unboxed(ty: &str) -> Option<&'static str> {
+    Some(match ty {
+        "Byte" => "byte",
+        "Short" => "short",
+        "Integer" => "int",
+        "Long" => "long",
+        "Float" => "float",
+        "Double" => "double",
+        "Boolean" => "boolean",
+        _ => return None,
+    })
+}
+
+/// The name of a field's record component.
+fn component_name(field: &RustField) -> String {
+    field.name_override(SupportedLanguage::Java).map_or_else(
+        || java_identifier(field.id.original.to_camel_case()),
+        str::to_owned,
+    )
+}
+
+/// Make a name usable as an identifier, by adding an underscore to reserved words.
+fn java_identifier(name: String) -> String {
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+fn generic_parameters(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generic_types.join(", "))
+    }
+}
+
+fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> io::Result<()> {
+    if comments.is_empty() {
+        return Ok(());
+    }
+    let indent = "    ".repeat(indent);
+    writeln!(w, "{indent}/**")?;
+    for comment in comments {
+        if comment.is_empty() {
+            writeln!(w, "{indent} *")?;
+        } else {
+            writeln!(w, "{indent} * {comment}")?;
+        }
+    }
+    writeln!(w, "{indent} */")
+}
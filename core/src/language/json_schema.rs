@@ -0,0 +1,395 @@
+use crate::{
+    language::{CrateTypes, Language, SupportedLanguage},
+    parser::ParsedData,
+    rust_types::{
+        RustEnum, RustEnumShared, RustEnumVariant, RustField, RustItem, RustStruct, RustType,
+        RustTypeAlias, RustTypeFormatError, SpecialRustType,
+    },
+};
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use super::{
+    generated_items, generic_type_mapping, substitute_generic_parameters, used_imports,
+    ScopedCrateTypes,
+};
+
+/// The JSON Schema dialect of the generated documents.
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// All information needed to generate a JSON Schema document.
+///
+/// Every typeshared type becomes an entry in `$defs`, and types reference each other with
+/// `$ref`. Type mappings whose value starts with `{` are used as schemas verbatim, any other
+/// value is used as the schema's `type`.
+#[derive(Default)]
+pub struct JsonSchema {
+    /// Mappings from Rust type names to JSON Schema types or schemas
+    pub type_mappings: HashMap<String, String>,
+    /// Whether or not to exclude the version comment that normally appears in generated documents.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+}
+
+impl Language for JsonSchema {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::JsonSchema
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn generate_types(
+        &mut self,
+        w: &mut dyn Write,
+        all_types: &CrateTypes,
+        data: ParsedData,
+    ) -> io::Result<()> {
+        let imports = if data.multi_file {
+            import_files(used_imports(&data, all_types), self.file_extension())
+        } else {
+            HashMap::new()
+        };
+        let builder = SchemaBuilder {
+            language: self.language(),
+            type_mappings: &self.type_mappings,
+            imports,
+            definitions_path: "#/$defs/",
+        };
+
+        let mut definitions = Map::new();
+        for item in generated_items(data, self.language()) {
+            let (id, schema) = match &item {
+                RustItem::Struct(rs) => (&rs.id, builder.struct_schema(rs)),
+                RustItem::Alias(ty) => (&ty.id, builder.alias_schema(ty)),
+                RustItem::Enum(e) => (
+                    &e.shared().id,
+                    match e {
+                        RustEnum::Unit(shared) => Ok(builder.unit_enum_schema(shared)),
+                        RustEnum::Algebraic {
+                            tag_key,
+                            content_key,
+                            shared,
+                        } => shared
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                builder.variant_schema(
+                                    v,
+                                    tag_key,
+                                    content_key,
+                                    &shared.generic_types,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                            .map(|variants| {
+                                with_description(json!({ "oneOf": variants }), &shared.comments)
+                            }),
+                    },
+                ),
+                // JSON Schema has no constants.
+                RustItem::Const(_) => continue,
+            };
+            definitions.insert(id.renamed.clone(), schema.map_err(io::Error::other)?);
+        }
+
+        let mut document = Map::new();
+        document.insert("$schema".to_owned(), SCHEMA_DIALECT.into());
+        if !self.no_version_header {
+            document.insert(
+                "$comment".to_owned(),
+                format!("Generated by typeshare {}", env!("CARGO_PKG_VERSION")).into(),
+            );
+        }
+        document.insert("$defs".to_owned(), Value::Object(definitions));
+
+        write_json(w, &Value::Object(document))
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        SchemaBuilder {
+            language: self.language(),
+            type_mappings: &self.type_mappings,
+            imports: HashMap::new(),
+            definitions_path: "#/$defs/",
+        }
+        .special_schema(special_ty, generic_types)
+        .map(|schema| schema.to_string())
+    }
+
+    fn write_imports(
+        &mut self,
+        _w: &mut dyn Write,
+        _imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        // Imported types are referenced by file name in `$ref`s instead.
+        Ok(())
+    }
+}
+
+/// Map each imported type to the generated file it is defined in.
+pub(super) fn import_files(
+    imports: ScopedCrateTypes<'_>,
+    extension: &str,
+) -> HashMap<String, String> {
+    imports
+        .into_iter()
+        .flat_map(|(crate_name, types)| {
+            let file_name = format!("{crate_name}.{extension}");
+            types
+                .into_iter()
+                .map(move |ty| (ty.to_owned(), file_name.clone()))
+        })
+        .collect()
+}
+
+/// A schema with only a `type`.
+fn of_type(ty: &str) -> Value {
+    json!({ "type": ty })
+}
+
+/// A schema for a type mapping.
+fn mapped(mapped: &str) -> Result<Value, RustTypeFormatError> {
+    if mapped.trim_start().starts_with('{') {
+        parse_mapping(mapped)
+    } else {
+        Ok(of_type(mapped))
+    }
+}
+
+/// Parse a type mapping that is a schema of its own.
+pub(super) fn parse_mapping(mapped: &str) -> Result<Value, RustTypeFormatError> {
+    serde_json::from_str(mapped)
+        .map_err(|err| RustTypeFormatError::InvalidJsonMapping(mapped.to_owned(), err.to_string()))
+}
+
+/// Add a `description` made from doc comments, if there are any.
+pub(super) fn with_description(schema: Value, comments: &[String]) -> Value {
+    match schema {
+        Value::Object(entries) if !comments.is_empty() => {
+            let mut described = Map::new();
+            described.insert("description".to_owned(), comments.join("\n").into());
+            described.extend(entries);
+            Value::Object(described)
+        }
+        schema => schema,
+    }
+}
+
+/// Write a JSON document, with nested values indented by two spaces per level.
+pub(super) fn write_json(w: &mut dyn Write, document: &Value) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *w, document)?;
+    writeln!(w)
+}
+
+/// Builds the schemas of Rust types, shared by the JSON Schema and OpenAPI generators.
+pub(super) struct SchemaBuilder<'a> {
+    /// The language used for `#[typeshare(lang(type = "..."))]` overrides.
+    pub language: SupportedLanguage,
+    pub type_mappings: &'a HashMap<String, String>,
+    /// The files that imported types are defined in.
+    pub imports: HashMap<String, String>,
+    /// The path that definitions are referenced by, such as `#/$defs/`.
+    pub definitions_path: &'static str,
+}
+
+impl SchemaBuilder<'_> {
+    /// The schema of a Rust type.
+    pub fn schema(
+        &self,
+        ty: &RustType,
+        generic_types: &[String],
+    ) -> Result<Value, RustTypeFormatError> {
+        match ty {
+            RustType::Simple { id } => {
+                if let Some(mapped_type) = self.type_mappings.get(id) {
+                    mapped(mapped_type)
+                } else if generic_types.contains(id) {
+                    // Generic parameters can be anything.
+                    Ok(json!({}))
+                } else {
+                    Ok(self.reference(id))
+                }
+            }
+            RustType::Generic { id, parameters } => {
+                if let Some(mapped_type) =
+                    generic_type_mapping(self.type_mappings, id, parameters.len())
+                {
+                    mapped(&substitute_generic_parameters(
+                        mapped_type,
+                        parameters,
+                        |p| {
+                            Ok::<_, RustTypeFormatError>(self.schema(p, generic_types)?.to_string())
+                        },
+                    )?)
+                } else {
+                    // Schemas have no generics, the parameters are left open by the definition.
+                    Ok(self.reference(id))
+                }
+            }
+            RustType::Special(special) => self.special_schema(special, generic_types),
+        }
+    }
+
+    pub fn special_schema(
+        &self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<Value, RustTypeFormatError> {
+        if let Some(mapped_type) = self.type_mappings.get(&special_ty.to_string()) {
+            return mapped(mapped_type);
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype) | SpecialRustType::Slice(rtype) => json!({
+                "type": "array",
+                "items": self.schema(rtype, generic_types)?,
+            }),
+            SpecialRustType::Array(rtype, len) => json!({
+                "type": "array",
+                "items": self.schema(rtype, generic_types)?,
+                "minItems": len,
+                "maxItems": len,
+            }),
+            SpecialRustType::Option(rtype) => json!({
+                "anyOf": [self.schema(rtype, generic_types)?, of_type("null")],
+            }),
+            SpecialRustType::HashMap(_, rtype) => json!({
+                "type": "object",
+                "additionalProperties": self.schema(rtype, generic_types)?,
+            }),
+            SpecialRustType::Unit => of_type("null"),
+            SpecialRustType::DateTime => json!({ "type": "string", "format": "date-time" }),
+            SpecialRustType::String => of_type("string"),
+            SpecialRustType::Char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+            SpecialRustType::I8 => integer(i8::MIN.into(), i8::MAX.into()),
+            SpecialRustType::I16 => integer(i16::MIN.into(), i16::MAX.into()),
+            SpecialRustType::I32 => integer(i32::MIN.into(), i32::MAX.into()),
+            SpecialRustType::U8 => integer(0, u8::MAX.into()),
+            SpecialRustType::U16 => integer(0, u16::MAX.into()),
+            SpecialRustType::U32 => integer(0, u32::MAX.into()),
+            // The integers that a double can represent exactly.
+            SpecialRustType::I54 => integer(-(1 << 53) + 1, (1 << 53) - 1),
+            SpecialRustType::U53 => integer(0, (1 << 53) - 1),
+            SpecialRustType::I64 | SpecialRustType::ISize => of_type("integer"),
+            SpecialRustType::U64 | SpecialRustType::USize => {
+                json!({ "type": "integer", "minimum": 0 })
+            }
+            SpecialRustType::F32 | SpecialRustType::F64 => of_type("number"),
+            SpecialRustType::Bool => of_type("boolean"),
+        })
+    }
+
+    /// A `$ref` to a typeshared type, which may be defined in another file.
+    pub fn reference(&self, id: &str) -> Value {
+        let file_name = self.imports.get(id).map(String::as_str).unwrap_or_default();
+        json!({ "$ref": format!("{file_name}{}{id}", self.definitions_path) })
+    }
+
+    /// The schema of a field, honouring per-language type overrides.
+    fn field_schema(
+        &self,
+        field: &RustField,
+        generic_types: &[String],
+    ) -> Result<Value, RustTypeFormatError> {
+        let schema = match field.type_override(self.language) {
+            Some(type_override) => mapped(type_override)?,
+            None => self.schema(&field.ty, generic_types)?,
+        };
+        Ok(with_description(schema, &field.comments))
+    }
+
+    /// The schema of an object with the given fields.
+    fn object_schema(
+        &self,
+        fields: &[RustField],
+        generic_types: &[String],
+    ) -> Result<Value, RustTypeFormatError> {
+        let properties = fields
+            .iter()
+            .map(|f| Ok((f.id.renamed.clone(), self.field_schema(f, generic_types)?)))
+            .collect::<Result<Map<_, _>, RustTypeFormatError>>()?;
+        let required = fields
+            .iter()
+            .filter(|f| !f.ty.is_optional() && !f.has_default)
+            .map(|f| f.id.renamed.as_str())
+            .collect::<Vec<_>>();
+
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = json!(required);
+        }
+        Ok(schema)
+    }
+
+    pub fn struct_schema(&self, rs: &RustStruct) -> Result<Value, RustTypeFormatError> {
+        Ok(with_description(
+            self.object_schema(&rs.fields, &rs.generic_types)?,
+            &rs.comments,
+        ))
+    }
+
+    pub fn alias_schema(&self, ty: &RustTypeAlias) -> Result<Value, RustTypeFormatError> {
+        Ok(with_description(
+            self.schema(&ty.r#type, &ty.generic_types)?,
+            &ty.comments,
+        ))
+    }
+
+    /// The schema of an enum serialized as the names of its variants.
+    pub fn unit_enum_schema(&self, shared: &RustEnumShared) -> Value {
+        let variants = shared
+            .variants
+            .iter()
+            .map(|v| v.shared().id.renamed.as_str())
+            .collect::<Vec<_>>();
+        with_description(
+            json!({ "type": "string", "enum": variants }),
+            &shared.comments,
+        )
+    }
+
+    /// The schema of an adjacently tagged variant.
+    pub fn variant_schema(
+        &self,
+        variant: &RustEnumVariant,
+        tag_key: &str,
+        content_key: &str,
+        generic_types: &[String],
+    ) -> Result<Value, RustTypeFormatError> {
+        let shared = variant.shared();
+        let content = match variant {
+            RustEnumVariant::Unit(_) => None,
+            RustEnumVariant::Tuple { ty, .. } => Some(self.schema(ty, generic_types)?),
+            RustEnumVariant::AnonymousStruct { fields, .. } => {
+                Some(self.object_schema(fields, generic_types)?)
+            }
+        };
+
+        let mut properties = Map::new();
+        properties.insert(tag_key.to_owned(), json!({ "const": shared.id.renamed }));
+        let mut required = vec![tag_key];
+        if let Some(content) = content {
+            properties.insert(content_key.to_owned(), content);
+            required.push(content_key);
+        }
+
+        Ok(with_description(
+            json!({ "type": "object", "properties": properties, "required": required }),
+            &shared.comments,
+        ))
+    }
+}
+
+/// An integer schema with the bounds of a Rust integer type.
+fn integer(minimum: i64, maximum: i64) -> Value {
+    json!({ "type": "integer", "minimum": minimum, "maximum": maximum })
+}
@@ -1,7 +1,7 @@
 use super::{Language, ScopedCrateTypes};
 use crate::language::SupportedLanguage;
 use crate::parser::{remove_dash_from_identifier, DecoratorKind, ParsedData};
-use crate::rust_types::{RustTypeFormatError, SpecialRustType};
+use crate::rust_types::{DecoratorMap, RustTypeFormatError, SpecialRustType};
 use crate::{
     rename::RenameExt,
     rust_types::{Id, RustConst, RustEnum, RustEnumVariant, RustField, RustStruct, RustTypeAlias},
@@ -9,7 +9,7 @@ use crate::{
 use itertools::Itertools;
 use joinery::JoinableIterator;
 use lazy_format::lazy_format;
-use std::{collections::BTreeSet, collections::HashMap, io::Write};
+use std::{collections::HashMap, io::Write};
 
 const INLINE: &str = "JvmInline";
 
@@ -30,6 +30,10 @@ pub struct Kotlin {
 }
 
 impl Language for Kotlin {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Kotlin
+    }
+
     fn type_map(&mut self) -> &HashMap<String, String> {
         &self.type_mappings
     }
@@ -141,6 +145,7 @@ impl Language for Kotlin {
                     comments: vec![],
                     has_default: false,
                     decorators: HashMap::new(),
+                    languages: Default::default(),
                 },
                 &[],
                 false,
@@ -203,12 +208,12 @@ impl Language for Kotlin {
 
             // Use @SerialName when writing the struct
             //
-            // As of right now this was only written to handle fields
-            // that get renamed to an ident with - in it
-            let requires_serial_name = rs
-                .fields
-                .iter()
-                .any(|f| f.id.renamed.chars().any(|c| c == '-'));
+            // This handles fields that get renamed to an ident with - in it,
+            // and fields with a `kotlin(name = "...")` override.
+            let requires_serial_name = rs.fields.iter().any(|f| {
+                f.id.renamed.chars().any(|c| c == '-')
+                    || f.name_override(SupportedLanguage::Kotlin).is_some()
+            });
 
             if let Some((last, elements)) = rs.fields.split_last() {
                 for f in elements.iter() {
@@ -318,7 +323,9 @@ impl Kotlin {
                     writeln!(
                         w,
                         "\t{}({:?}),",
-                        &v.shared().id.original,
+                        v.shared()
+                            .name_override(SupportedLanguage::Kotlin)
+                            .unwrap_or(&v.shared().id.original),
                         v.shared().id.renamed
                     )?;
                 }
@@ -335,7 +342,10 @@ impl Kotlin {
                     writeln!(w, "\t@SerialName({})", printed_value)?;
 
                     let variant_name = {
-                        let mut variant_name = v.shared().id.original.to_pascal_case();
+                        let mut variant_name = v
+                            .shared()
+                            .name_override(SupportedLanguage::Kotlin)
+                            .map_or_else(|| v.shared().id.original.to_pascal_case(), str::to_owned);
 
                         if variant_name
                             .chars()
@@ -448,11 +458,15 @@ impl Kotlin {
                 .map_err(std::io::Error::other)?,
         };
 
+        let name = f
+            .name_override(SupportedLanguage::Kotlin)
+            .map_or_else(|| remove_dash_from_identifier(&f.id.renamed), str::to_owned);
+
         match visibility {
             Visibility::Public => write!(
                 w,
                 "\tval {}: {}{}",
-                remove_dash_from_identifier(&f.id.renamed),
+                name,
                 ty,
                 (f.has_default && !f.ty.is_optional())
                     .then_some("? = null")
@@ -462,7 +476,7 @@ impl Kotlin {
             Visibility::Private => write!(
                 w,
                 "\tprivate val {}: {}{}",
-                remove_dash_from_identifier(&f.id.renamed),
+                name,
                 ty,
                 (f.has_default && !f.ty.is_optional())
                     .then_some("? = null")
@@ -493,8 +507,8 @@ impl Kotlin {
             .try_for_each(|comment| self.write_comment(w, indent, comment))
     }
 
-    fn is_inline(&self, decorators: &HashMap<DecoratorKind, BTreeSet<String>>) -> bool {
-        match decorators.get(&DecoratorKind::Kotlin) {
+    fn is_inline(&self, decorators: &DecoratorMap) -> bool {
+        match decorators.get(&DecoratorKind::Language(SupportedLanguage::Kotlin)) {
             Some(kotlin_decorators) => kotlin_decorators.iter().contains(&String::from(INLINE)),
             _ => false,
         }
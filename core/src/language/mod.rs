@@ -19,20 +19,38 @@ use std::{
     str::FromStr,
 };
 
+mod avro;
+mod csharp;
+mod dart;
 mod go;
+mod graphql;
+mod java;
+mod json_schema;
 mod kotlin;
+mod openapi;
+mod proto;
 mod python;
 mod scala;
 mod swift;
 mod typescript;
+mod zod;
 
+pub use avro::Avro;
+pub use csharp::CSharp;
+pub use dart::Dart;
 pub use go::Go;
+pub use graphql::GraphQL;
+pub use java::Java;
+pub use json_schema::JsonSchema;
 pub use kotlin::Kotlin;
+pub use openapi::OpenApi;
+pub use proto::Proto;
 pub use python::Python;
 pub use scala::Scala;
 pub use swift::GenericConstraints;
 pub use swift::Swift;
 pub use typescript::TypeScript;
+pub use zod::Zod;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 /// A crate name.
@@ -103,13 +121,45 @@ pub enum SupportedLanguage {
     Swift,
     TypeScript,
     Python,
+    JsonSchema,
+    OpenApi,
+    Proto,
+    Java,
+    CSharp,
+    Dart,
+    GraphQL,
+    Avro,
 }
 
 impl SupportedLanguage {
     /// Returns an iterator over all supported language variants.
     pub fn all_languages() -> impl Iterator<Item = Self> {
         use SupportedLanguage::*;
-        [Go, Kotlin, Scala, Swift, TypeScript, Python].into_iter()
+        [
+            Go, Kotlin, Scala, Swift, TypeScript, Python, JsonSchema, OpenApi, Proto, Java, CSharp,
+            Dart, GraphQL, Avro,
+        ]
+        .into_iter()
+    }
+
+    /// The name of this language as used in typeshare attributes, e.g. `typescript`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SupportedLanguage::Go => "go",
+            SupportedLanguage::Kotlin => "kotlin",
+            SupportedLanguage::Scala => "scala",
+            SupportedLanguage::Swift => "swift",
+            SupportedLanguage::TypeScript => "typescript",
+            SupportedLanguage::Python => "python",
+            SupportedLanguage::JsonSchema => "json_schema",
+            SupportedLanguage::OpenApi => "openapi",
+            SupportedLanguage::Proto => "proto",
+            SupportedLanguage::Java => "java",
+            SupportedLanguage::CSharp => "csharp",
+            SupportedLanguage::Dart => "dart",
+            SupportedLanguage::GraphQL => "graphql",
+            SupportedLanguage::Avro => "avro",
+        }
     }
 
     /// Get the file name extension for the supported language.
@@ -121,6 +171,14 @@ impl SupportedLanguage {
             SupportedLanguage::Swift => "swift",
             SupportedLanguage::TypeScript => "ts",
             SupportedLanguage::Python => "py",
+            SupportedLanguage::JsonSchema => "json",
+            SupportedLanguage::OpenApi => "yaml",
+            SupportedLanguage::Proto => "proto",
+            SupportedLanguage::Java => "java",
+            SupportedLanguage::CSharp => "cs",
+            SupportedLanguage::Dart => "dart",
+            SupportedLanguage::GraphQL => "graphql",
+            SupportedLanguage::Avro => "avsc",
         }
     }
 }
@@ -136,6 +194,14 @@ impl FromStr for SupportedLanguage {
             "swift" => Ok(Self::Swift),
             "typescript" => Ok(Self::TypeScript),
             "python" => Ok(Self::Python),
+            "json_schema" | "json-schema" => Ok(Self::JsonSchema),
+            "openapi" => Ok(Self::OpenApi),
+            "proto" => Ok(Self::Proto),
+            "java" => Ok(Self::Java),
+            "csharp" => Ok(Self::CSharp),
+            "dart" => Ok(Self::Dart),
+            "graphql" => Ok(Self::GraphQL),
+            "avro" => Ok(Self::Avro),
             _ => Err(ParseError::UnsupportedLanguage(s.into())),
         }
     }
@@ -169,26 +235,7 @@ pub trait Language {
             self.write_imports(writable, used_imports(&data, all_types))?;
         }
 
-        let ParsedData {
-            structs,
-            enums,
-            aliases,
-            consts,
-            ..
-        } = data;
-
-        let mut items = Vec::from_iter(
-            aliases
-                .into_iter()
-                .map(RustItem::Alias)
-                .chain(structs.into_iter().map(RustItem::Struct))
-                .chain(enums.into_iter().map(RustItem::Enum))
-                .chain(consts.into_iter().map(RustItem::Const)),
-        );
-
-        topsort(&mut items);
-
-        for thing in &items {
+        for thing in &generated_items(data, self.language()) {
             match thing {
                 RustItem::Enum(e) => self.write_enum(writable, e)?,
                 RustItem::Struct(s) => self.write_struct(writable, s)?,
@@ -200,6 +247,10 @@ pub trait Language {
         self.end_file(writable)
     }
 
+    /// The language generated by this implementation, used to filter items annotated
+    /// with `#[typeshare(lang = "...")]` or `#[typeshare(skip(...))]`.
+    fn language(&self) -> SupportedLanguage;
+
     /// Get the type mapping for this language `(Rust type name -> lang type name)`
     fn type_map(&mut self) -> &HashMap<String, String>;
 
@@ -245,8 +296,11 @@ pub trait Language {
         parameters: &[RustType],
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
-        if let Some(mapped) = self.type_map().get(base) {
-            Ok(mapped.into())
+        if let Some(mapped) = generic_type_mapping(self.type_map(), base, parameters.len()).cloned()
+        {
+            substitute_generic_parameters(&mapped, parameters, |p| {
+                self.format_type(p, generic_types)
+            })
         } else {
             let parameters: Result<Vec<String>, RustTypeFormatError> = parameters
                 .iter()
@@ -256,9 +310,11 @@ pub trait Language {
             Ok(format!(
                 "{}{}",
                 self.format_simple_type(base, generic_types)?,
-                (!parameters.is_empty())
-                    .then(|| self.format_generic_parameters(parameters))
-                    .unwrap_or_default()
+                if parameters.is_empty() {
+                    String::new()
+                } else {
+                    self.format_generic_parameters(parameters)
+                }
             ))
         }
     }
@@ -407,6 +463,7 @@ pub trait Language {
                     )],
                     decorators: e.shared().decorators.clone(),
                     is_redacted: e.shared().is_redacted,
+                    languages: e.shared().languages.clone(),
                 },
             )?;
         }
@@ -414,6 +471,11 @@ pub trait Language {
         Ok(())
     }
 
+    /// The file name extension of generated files, which may depend on the configuration.
+    fn file_extension(&self) -> &'static str {
+        self.language().language_extension()
+    }
+
     /// Types that are remapped will be excluded from import references.
     fn ignored_reference_types(&self) -> Vec<&str> {
         Vec::new()
@@ -426,6 +488,96 @@ pub trait Language {
     }
 }
 
+/// The items of a file that are generated for `language`, in dependency order.
+fn generated_items(data: ParsedData, language: SupportedLanguage) -> Vec<RustItem> {
+    let ParsedData {
+        structs,
+        enums,
+        aliases,
+        consts,
+        ..
+    } = data;
+
+    let mut items = filter_language(
+        aliases
+            .into_iter()
+            .map(RustItem::Alias)
+            .chain(structs.into_iter().map(RustItem::Struct))
+            .chain(enums.into_iter().map(RustItem::Enum))
+            .chain(consts.into_iter().map(RustItem::Const)),
+        language,
+    );
+
+    topsort(&mut items);
+    items
+}
+
+/// Drop the items, fields and variants that are not generated for `language`.
+fn filter_language(
+    items: impl IntoIterator<Item = RustItem>,
+    language: SupportedLanguage,
+) -> Vec<RustItem> {
+    items
+        .into_iter()
+        .filter(|item| item.languages().includes(language))
+        .map(|mut item| {
+            match &mut item {
+                RustItem::Struct(s) => s.fields.retain(|f| f.languages.includes(language)),
+                RustItem::Enum(e) => {
+                    let shared = e.shared_mut();
+                    shared
+                        .variants
+                        .retain(|v| v.shared().languages.includes(language));
+                    for variant in &mut shared.variants {
+                        if let RustEnumVariant::AnonymousStruct { fields, .. } = variant {
+                            fields.retain(|f| f.languages.includes(language));
+                        }
+                    }
+                }
+                RustItem::Alias(_) | RustItem::Const(_) => (),
+            }
+            item
+        })
+        .collect()
+}
+
+/// Find the type mapping for a generic type, keyed either by its base name, such as `"MyList"`,
+/// or by a template with the same number of parameters, such as `"NonEmpty<T>"`.
+fn generic_type_mapping<'a>(
+    type_map: &'a HashMap<String, String>,
+    base: &str,
+    arity: usize,
+) -> Option<&'a String> {
+    type_map.get(base).or_else(|| {
+        type_map.iter().find_map(|(key, mapped)| {
+            let (key_base, key_parameters) = key.trim().strip_suffix('>')?.split_once('<')?;
+            (key_base.trim() == base && key_parameters.split(',').count() == arity)
+                .then_some(mapped)
+        })
+    })
+}
+
+/// Substitute generic parameters into the `{0}`, `{1}`, ... placeholders of a type mapping.
+/// Only the parameters that have a placeholder are formatted, so a mapping like `"string"`
+/// applies whatever the parameters are.
+fn substitute_generic_parameters<T, E>(
+    mapped: &str,
+    parameters: &[T],
+    mut format: impl FnMut(&T) -> Result<String, E>,
+) -> Result<String, E> {
+    parameters
+        .iter()
+        .enumerate()
+        .try_fold(mapped.to_owned(), |mapped, (i, parameter)| {
+            let placeholder = format!("{{{i}}}");
+            Ok(if mapped.contains(&placeholder) {
+                mapped.replace(&placeholder, &format(parameter)?)
+            } else {
+                mapped
+            })
+        })
+}
+
 /// Lookup any refeferences to other typeshared types in order to build
 /// a list of imports for the generated module.
 fn used_imports<'a, 'b: 'a>(
@@ -496,11 +648,40 @@ fn used_imports<'a, 'b: 'a>(
 #[cfg(test)]
 mod test {
     use crate::language::CrateName;
-    use std::path::Path;
+    use std::{collections::HashMap, path::Path};
 
     #[test]
     fn test_crate_name() {
         let path = Path::new("/some/path/to/projects/core/foundation/op-proxy/src/android.rs");
         assert_eq!(Some("op_proxy".into()), CrateName::find_crate_name(path));
     }
+
+    #[test]
+    fn test_generic_type_mapping() {
+        let type_map = HashMap::from([
+            ("MyList".to_string(), "List".to_string()),
+            ("NonEmpty<T>".to_string(), "Array<{0}>".to_string()),
+            ("Pair< A, B >".to_string(), "[{0}, {1}]".to_string()),
+        ]);
+
+        assert_eq!(
+            super::generic_type_mapping(&type_map, "MyList", 1).map(String::as_str),
+            Some("List")
+        );
+        assert_eq!(
+            super::generic_type_mapping(&type_map, "NonEmpty", 1).map(String::as_str),
+            Some("Array<{0}>")
+        );
+        assert_eq!(super::generic_type_mapping(&type_map, "NonEmpty", 2), None);
+        assert_eq!(
+            super::substitute_generic_parameters("[{0}, {1}]", &["string", "Array<number>"], |p| {
+                Ok::<_, ()>(p.to_string())
+            }),
+            Ok("[string, Array<number>]".to_string())
+        );
+        assert_eq!(
+            super::substitute_generic_parameters("string", &["unsupported"], |_| Err(())),
+            Ok("string".to_string())
+        );
+    }
 }
@@ -0,0 +1,259 @@
+use crate::{
+    language::{CrateTypes, Language, SupportedLanguage},
+    parser::ParsedData,
+    rust_types::{RustEnum, RustItem, RustTypeFormatError, SpecialRustType},
+};
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use super::{
+    generated_items,
+    json_schema::{import_files, with_description, write_json, SchemaBuilder},
+    used_imports, ScopedCrateTypes,
+};
+
+/// The path that component schemas are referenced by.
+const SCHEMAS_PATH: &str = "#/components/schemas/";
+
+/// All information needed to generate the `components.schemas` of an OpenAPI 3.1 document.
+///
+/// The schemas are the same as those of the JSON Schema generator, except that each variant of
+/// an algebraic enum becomes a schema of its own, named after the enum and the variant, so that
+/// the enum can use a `discriminator` object.
+#[derive(Default)]
+pub struct OpenApi {
+    /// Mappings from Rust type names to OpenAPI types or schemas
+    pub type_mappings: HashMap<String, String>,
+    /// Write JSON instead of YAML.
+    pub json: bool,
+    /// Whether or not to exclude the version header that normally appears at the top of generated YAML.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+}
+
+impl Language for OpenApi {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::OpenApi
+    }
+
+    fn file_extension(&self) -> &'static str {
+        if self.json {
+            "json"
+        } else {
+            self.language().language_extension()
+        }
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn generate_types(
+        &mut self,
+        w: &mut dyn Write,
+        all_types: &CrateTypes,
+        data: ParsedData,
+    ) -> io::Result<()> {
+        let imports = if data.multi_file {
+            import_files(used_imports(&data, all_types), self.file_extension())
+        } else {
+            HashMap::new()
+        };
+        let builder = SchemaBuilder {
+            language: self.language(),
+            type_mappings: &self.type_mappings,
+            imports,
+            definitions_path: SCHEMAS_PATH,
+        };
+
+        let mut schemas = Map::new();
+        let mut variant_schemas = HashMap::new();
+        for item in generated_items(data, self.language()) {
+            match &item {
+                RustItem::Struct(rs) => {
+                    insert_schema(
+                        &mut schemas,
+                        &variant_schemas,
+                        rs.id.renamed.clone(),
+                        builder.struct_schema(rs).map_err(io::Error::other)?,
+                    )?;
+                }
+                RustItem::Alias(ty) => {
+                    insert_schema(
+                        &mut schemas,
+                        &variant_schemas,
+                        ty.id.renamed.clone(),
+                        builder.alias_schema(ty).map_err(io::Error::other)?,
+                    )?;
+                }
+                RustItem::Enum(RustEnum::Unit(shared)) => {
+                    insert_schema(
+                        &mut schemas,
+                        &variant_schemas,
+                        shared.id.renamed.clone(),
+                        builder.unit_enum_schema(shared),
+                    )?;
+                }
+                RustItem::Enum(RustEnum::Algebraic {
+                    tag_key,
+                    content_key,
+                    shared,
+                }) => {
+                    let mut variants = Vec::new();
+                    for variant in &shared.variants {
+                        let name = format!("{}{}", shared.id.renamed, variant.shared().id.original);
+                        let schema = builder
+                            .variant_schema(variant, tag_key, content_key, &shared.generic_types)
+                            .map_err(io::Error::other)?;
+                        variants.push((variant.shared().id.renamed.clone(), name, schema));
+                    }
+
+                    let one_of = variants
+                        .iter()
+                        .map(|(_, name, _)| builder.reference(name))
+                        .collect::<Vec<_>>();
+                    let mapping = variants
+                        .iter()
+                        .map(|(tag, name, _)| (tag.clone(), json!(SCHEMAS_PATH.to_owned() + name)))
+                        .collect::<Map<_, _>>();
+                    insert_schema(
+                        &mut schemas,
+                        &variant_schemas,
+                        shared.id.renamed.clone(),
+                        with_description(
+                            json!({
+                                "oneOf": one_of,
+                                "discriminator": { "propertyName": tag_key, "mapping": mapping },
+                            }),
+                            &shared.comments,
+                        ),
+                    )?;
+                    for (variant, (_, name, schema)) in shared.variants.iter().zip(variants) {
+                        variant_schemas.insert(
+                            name.clone(),
+                            (
+                                shared.id.original.clone(),
+                                variant.shared().id.original.clone(),
+                            ),
+                        );
+                        insert_schema(&mut schemas, &variant_schemas, name, schema)?;
+                    }
+                }
+                // OpenAPI has no constants.
+                RustItem::Const(_) => (),
+            }
+        }
+
+        let document = json!({ "components": { "schemas": Value::Object(schemas) } });
+
+        if self.json {
+            write_json(w, &document)
+        } else {
+            if !self.no_version_header {
+                writeln!(w, "# Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
+            }
+            serde_yaml_ng::to_writer(w, &document).map_err(io::Error::other)
+        }
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        SchemaBuilder {
+            language: self.language(),
+            type_mappings: &self.type_mappings,
+            imports: HashMap::new(),
+            definitions_path: SCHEMAS_PATH,
+        }
+        .special_schema(special_ty, generic_types)
+        .map(|schema| schema.to_string())
+    }
+
+    fn write_imports(
+        &mut self,
+        _w: &mut dyn Write,
+        _imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        // Imported types are referenced by file name in `$ref`s instead.
+        Ok(())
+    }
+}
+
+/// Add a schema under a name that must not be taken yet. The schemas generated for the
+/// variants of algebraic enums are named after the enum and the variant, so they can clash
+/// with a type, like a `ShapeCircle` struct and the `Circle` variant of `Shape`.
+fn insert_schema(
+    schemas: &mut Map<String, Value>,
+    variant_schemas: &HashMap<String, (String, String)>,
+    name: String,
+    schema: Value,
+) -> io::Result<()> {
+    if schemas.contains_key(&name) {
+        let reason = match variant_schemas.get(&name) {
+            Some((enum_name, variant)) => format!(
+                "the schema `{name}` of the `{variant}` variant of `{enum_name}` has the same name as another type, rename one of them"
+            ),
+            None => format!("two schemas are named `{name}`"),
+        };
+        return Err(io::Error::new(io::ErrorKind::InvalidData, reason));
+    }
+    schemas.insert(name, schema);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::OpenApi;
+    use crate::{
+        context::{ParseContext, ParseFileContext},
+        language::{Language, SINGLE_FILE_CRATE_NAME},
+        parser::parse,
+    };
+    use std::{collections::HashMap, io};
+
+    fn generate(source_code: &str) -> io::Result<String> {
+        let data = parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source_code.into(),
+                crate_name: SINGLE_FILE_CRATE_NAME,
+                file_name: "file_name".into(),
+                file_path: "file_path".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        let mut out = Vec::new();
+        OpenApi::default().generate_types(&mut out, &HashMap::new(), data)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn variant_schemas_must_not_replace_types() {
+        let shape = r#"
+            #[typeshare]
+            #[serde(tag = "type", content = "content")]
+            pub enum Shape {
+                Circle { radius: f64 },
+            }
+        "#;
+        let circle = "#[typeshare] pub struct ShapeCircle { pub diameter: f64 }";
+
+        for source_code in [format!("{shape}{circle}"), format!("{circle}{shape}")] {
+            let error = generate(&source_code).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert!(
+                error
+                    .to_string()
+                    .contains("`ShapeCircle` of the `Circle` variant of `Shape`"),
+                "{error}"
+            );
+        }
+        assert!(generate(shape).is_ok());
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    language::{Language, SupportedLanguage},
+    parser::ParsedData,
+    rename::RenameExt,
+    rust_types::{
+        FieldDecorator, FieldDecoratorMap, Id, RustEnum, RustEnumVariant, RustField, RustStruct,
+        RustType, RustTypeFormatError, SpecialRustType,
+    },
+};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use super::{generic_type_mapping, substitute_generic_parameters, ScopedCrateTypes};
+
+/// The largest field number protobuf allows.
+const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// Field numbers reserved by the protobuf implementation.
+const RESERVED_FIELD_NUMBERS: std::ops::RangeInclusive<u32> = 19_000..=19_999;
+
+/// Field numbers derived from serialized names, which are above the reserved numbers and small
+/// enough to be encoded in three bytes.
+const HASHED_FIELD_NUMBERS: std::ops::RangeInclusive<u32> = 20_000..=(1 << 18) - 1;
+
+/// All information needed to generate proto3 definitions.
+///
+/// Structs become messages, unit enums become enums and algebraic enums become messages with
+/// a `oneof`. Type aliases have no proto equivalent, so references to them are replaced with
+/// the aliased type.
+#[derive(Default)]
+pub struct Proto {
+    /// Name of the proto package. In multi file mode, each crate becomes a package within it.
+    pub package: String,
+    /// Mappings from Rust type names to proto type names
+    pub type_mappings: HashMap<String, String>,
+    /// Require every field and variant to set its number with `proto(tag = N)`, instead of
+    /// deriving the numbers of the ones without a tag from their serialized names.
+    pub strict_tags: bool,
+    /// Whether or not to exclude the version header that normally appears at the top of generated code.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+    /// HashMap<Identifier, AliasedType> of the type aliases in the file being generated and
+    /// the ones it imports from other crates
+    pub aliases: HashMap<String, RustType>,
+    /// HashMap<Identifier, QualifiedIdentifier> of the types imported from other packages
+    pub imports: HashMap<String, String>,
+}
+
+impl Language for Proto {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Proto
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn format_simple_type(
+        &mut self,
+        base: &String,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(base) {
+            Ok(mapped.to_owned())
+        } else if let Some(aliased) = self.aliases.get(base).cloned() {
+            self.format_type(&aliased, generic_types)
+        } else if generic_types.contains(base) {
+            Err(RustTypeFormatError::GenericsForbiddenInProto(
+                base.to_owned(),
+            ))
+        } else if let Some(qualified) = self.imports.get(base) {
+            Ok(qualified.to_owned())
+        } else {
+            Ok(base.to_owned())
+        }
+    }
+
+    fn format_generic_type(
+        &mut self,
+        base: &String,
+        parameters: &[RustType],
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        let mapped = generic_type_mapping(&self.type_mappings, base, parameters.len())
+            .cloned()
+            .ok_or_else(|| RustTypeFormatError::GenericsForbiddenInProto(base.to_owned()))?;
+        substitute_generic_parameters(&mapped, parameters, |p| self.format_type(p, generic_types))
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        _generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()) {
+            return Ok(mapped.to_owned());
+        }
+
+        Ok(match special_ty {
+            // Labels like `repeated` only apply to fields, see `field_type`.
+            SpecialRustType::Vec(_)
+            | SpecialRustType::Array(_, _)
+            | SpecialRustType::Slice(_)
+            | SpecialRustType::Option(_)
+            | SpecialRustType::HashMap(_, _)
+            | SpecialRustType::Unit => {
+                return Err(RustTypeFormatError::UnsupportedSpecialType(
+                    special_ty.to_string(),
+                ))
+            }
+            SpecialRustType::String | SpecialRustType::Char | SpecialRustType::DateTime => {
+                "string".into()
+            }
+            SpecialRustType::I8 | SpecialRustType::I16 | SpecialRustType::I32 => "int32".into(),
+            SpecialRustType::U8 | SpecialRustType::U16 | SpecialRustType::U32 => "uint32".into(),
+            SpecialRustType::I54 | SpecialRustType::I64 | SpecialRustType::ISize => "int64".into(),
+            SpecialRustType::U53 | SpecialRustType::U64 | SpecialRustType::USize => "uint64".into(),
+            SpecialRustType::F32 => "float".into(),
+            SpecialRustType::F64 => "double".into(),
+            SpecialRustType::Bool => "bool".into(),
+        })
+    }
+
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        self.imports.clear();
+        self.aliases = parsed_data
+            .aliases
+            .iter()
+            .chain(&parsed_data.foreign_aliases)
+            .filter(|alias| alias.generic_types.is_empty())
+            .map(|alias| (alias.id.original.to_string(), alias.r#type.clone()))
+            .collect();
+
+        if !self.no_version_header {
+            writeln!(w, "// Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
+        }
+        writeln!(w, "syntax = \"proto3\";\n")?;
+
+        let package = if parsed_data.multi_file {
+            self.package_for(parsed_data.crate_name.as_str())
+        } else {
+            self.package.clone()
+        };
+        if !package.is_empty() {
+            writeln!(w, "package {package};\n")?;
+        }
+        Ok(())
+    }
+
+    fn write_imports(
+        &mut self,
+        w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        let mut imported = false;
+        for (crate_name, types) in &imports {
+            // Aliases are replaced with the aliased type, so they are never imported.
+            let types = types
+                .iter()
+                .filter(|ty| !self.aliases.contains_key(**ty))
+                .collect::<Vec<_>>();
+            if types.is_empty() {
+                continue;
+            }
+            writeln!(w, "import \"{crate_name}.proto\";")?;
+            let package = self.package_for(crate_name.as_str());
+            self.imports.extend(
+                types
+                    .iter()
+                    .map(|ty| (ty.to_string(), format!("{package}.{ty}"))),
+            );
+            imported = true;
+        }
+        if imported {
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        write_comments(w, 0, &rs.comments)?;
+        writeln!(w, "message {} {{", rs.id.renamed)?;
+        self.write_fields(w, 1, &rs.id.original, &rs.fields, &rs.generic_types)?;
+        writeln!(w, "}}\n")
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        let shared = e.shared();
+        write_comments(w, 0, &shared.comments)?;
+
+        match e {
+            RustEnum::Unit(shared) => {
+                // Enum values are scoped to the package, so they are prefixed with the enum's name.
+                let prefix = shared.id.renamed.to_screaming_snake_case();
+                writeln!(w, "enum {} {{", shared.id.renamed)?;
+                writeln!(w, "  {prefix}_UNSPECIFIED = 0;")?;
+                let numbers = self.variant_numbers(&shared.id.original, &shared.variants)?;
+                for (variant, number) in shared.variants.iter().zip(numbers) {
+                    let variant = variant.shared();
+                    write_comments(w, 1, &variant.comments)?;
+                    writeln!(
+                        w,
+                        "  {prefix}_{} = {number};",
+                        variant.id.original.to_screaming_snake_case(),
+                    )?;
+                }
+            }
+            RustEnum::Algebraic {
+                tag_key, shared, ..
+            } => {
+                writeln!(w, "message {} {{", shared.id.renamed)?;
+
+                // Variants without a single plain type get a message of their own.
+                let mut variant_types = Vec::new();
+                for variant in &shared.variants {
+                    let name = variant.shared().id.original.to_string();
+                    let ty = match variant {
+                        RustEnumVariant::Tuple { ty, .. } => Some(
+                            self.field_type(ty, &shared.generic_types)
+                                .map_err(io::Error::other)?,
+                        )
+                        .filter(|ty| !is_labelled(ty)),
+                        _ => None,
+                    };
+                    if ty.is_none() {
+                        write_comments(w, 1, &variant.shared().comments)?;
+                        match variant {
+                            RustEnumVariant::Unit(_) => writeln!(w, "  message {name} {{}}")?,
+                            RustEnumVariant::Tuple { ty, .. } => writeln!(
+                                w,
+                                "  message {name} {{\n    {} value = 1;\n  }}",
+                                self.field_type(ty, &shared.generic_types)
+                                    .map_err(io::Error::other)?
+                            )?,
+                            RustEnumVariant::AnonymousStruct { fields, .. } => {
+                                writeln!(w, "  message {name} {{")?;
+                                self.write_fields(
+                                    w,
+                                    2,
+                                    &format!("{}::{name}", shared.id.original),
+                                    fields,
+                                    &shared.generic_types,
+                                )?;
+                                writeln!(w, "  }}")?;
+                            }
+                        }
+                    }
+                    variant_types.push(ty.unwrap_or(name));
+                }
+
+                writeln!(w, "  oneof {} {{", tag_key.to_snake_case())?;
+                let numbers = self.variant_numbers(&shared.id.original, &shared.variants)?;
+                for ((variant, ty), number) in
+                    shared.variants.iter().zip(variant_types).zip(numbers)
+                {
+                    let variant = variant.shared();
+                    writeln!(
+                        w,
+                        "    {ty} {} = {number}{};",
+                        variant.id.original.to_snake_case(),
+                        json_name(&variant.id.original.to_snake_case(), &variant.id.renamed),
+                    )?;
+                }
+                writeln!(w, "  }}")?;
+            }
+        }
+
+        writeln!(w, "}}\n")
+    }
+}
+
+impl Proto {
+    /// The package of a crate's types in multi file mode.
+    fn package_for(&self, crate_name: &str) -> String {
+        if self.package.is_empty() {
+            crate_name.to_owned()
+        } else {
+            format!("{}.{crate_name}", self.package)
+        }
+    }
+
+    /// The type of a field, including its `repeated` or `optional` label.
+    fn field_type(
+        &mut self,
+        ty: &RustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(aliased) = self.aliases.get(ty.id()).cloned() {
+            return self.field_type(&aliased, generic_types);
+        }
+
+        match ty {
+            RustType::Special(SpecialRustType::Option(inner)) => {
+                let inner = self.field_type(inner, generic_types)?;
+                // Repeated and map fields can't be optional, they are just left empty.
+                Ok(if is_labelled(&inner) {
+                    inner
+                } else {
+                    format!("optional {inner}")
+                })
+            }
+            RustType::Special(
+                SpecialRustType::Vec(inner)
+                | SpecialRustType::Slice(inner)
+                | SpecialRustType::Array(inner, _),
+            ) => Ok(format!(
+                "repeated {}",
+                self.format_type(inner, generic_types)?
+            )),
+            RustType::Special(SpecialRustType::HashMap(key, value)) => Ok(format!(
+                "map<{}, {}>",
+                self.format_type(key, generic_types)?,
+                self.format_type(value, generic_types)?
+            )),
+            _ => self.format_type(ty, generic_types),
+        }
+    }
+
+    fn write_fields(
+        &mut self,
+        w: &mut dyn Write,
+        indent: usize,
+        message: &str,
+        fields: &[RustField],
+        generic_types: &[String],
+    ) -> io::Result<()> {
+        if let Some(generic) = generic_types.first() {
+            return Err(io::Error::other(
+                RustTypeFormatError::GenericsForbiddenInProto(generic.to_owned()),
+            ));
+        }
+
+        let members = fields
+            .iter()
+            .map(|field| (&field.id, &field.decorators))
+            .collect::<Vec<_>>();
+        for (field, number) in fields
+            .iter()
+            .zip(tag_numbers(message, self.strict_tags, &members)?)
+        {
+            let ty = match field.type_override(SupportedLanguage::Proto) {
+                Some(type_override) => type_override.to_owned(),
+                None => self
+                    .field_type(&field.ty, generic_types)
+                    .map_err(io::Error::other)?,
+            };
+            let name = field.id.original.to_snake_case();
+
+            write_comments(w, indent, &field.comments)?;
+            writeln!(
+                w,
+                "{}{ty} {name} = {number}{};",
+                "  ".repeat(indent),
+                json_name(&name, &field.id.renamed)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The numbers of an enum's values or an algebraic enum's `oneof` members.
+    fn variant_numbers(&self, message: &str, variants: &[RustEnumVariant]) -> io::Result<Vec<u32>> {
+        let members = variants
+            .iter()
+            .map(|variant| {
+                let shared = variant.shared();
+                (&shared.id, &shared.decorators)
+            })
+            .collect::<Vec<_>>();
+        tag_numbers(message, self.strict_tags, &members)
+    }
+}
+
+/// Whether a field type has a label that rules out another one.
+fn is_labelled(ty: &str) -> bool {
+    ty.starts_with("repeated ") || ty.starts_with("map<") || ty.starts_with("optional ")
+}
+
+/// A `json_name` option for a field whose serialized name differs from protobuf's default.
+fn json_name(name: &str, renamed: &str) -> String {
+    if name.to_string().to_camel_case() == renamed {
+        String::new()
+    } else {
+        format!(" [json_name = {renamed:?}]")
+    }
+}
+
+/// The numbers of a message's fields or an enum's variants, set with
+/// `#[typeshare(proto(tag = N))]`. Unless tags are strict, the others are derived from their
+/// serialized names, so they don't change when fields are added, removed or reordered.
+fn tag_numbers(
+    message: &str,
+    strict: bool,
+    members: &[(&Id, &FieldDecoratorMap)],
+) -> io::Result<Vec<u32>> {
+    let invalid = |reason: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid proto field numbers in `{message}`: {reason}"),
+        )
+    };
+
+    let mut numbers = Vec::with_capacity(members.len());
+    let mut taken = HashMap::new();
+    for (id, decorators) in members {
+        let name = &id.original;
+        let tag = decorators
+            .get(&SupportedLanguage::Proto)
+            .into_iter()
+            .flatten()
+            .find_map(|decorator| match decorator {
+                FieldDecorator::NameValue(key, tag) if key == "tag" => Some(tag),
+                _ => None,
+            });
+        let number = match tag {
+            Some(tag) => tag
+                .parse::<u32>()
+                .ok()
+                .filter(|n| (1..=MAX_FIELD_NUMBER).contains(n))
+                .filter(|n| !RESERVED_FIELD_NUMBERS.contains(n))
+                .ok_or_else(|| {
+                    invalid(format!("`{tag}` on `{name}` is not a usable field number"))
+                })?,
+            None if strict => return Err(invalid(format!("`{name}` has no `proto(tag = N)`"))),
+            None => hashed_number(&id.renamed),
+        };
+        if let Some(other) = taken.insert(number, name) {
+            return Err(invalid(format!(
+                "`{other}` and `{name}` both have number {number}, set a different one with `proto(tag = N)`"
+            )));
+        }
+        numbers.push(number);
+    }
+    Ok(numbers)
+}
+
+/// The number of a field or variant without a tag, from the FNV-1a hash of its serialized name.
+fn hashed_number(name: &str) -> u32 {
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    let range = HASHED_FIELD_NUMBERS.end() - HASHED_FIELD_NUMBERS.start() + 1;
+    HASHED_FIELD_NUMBERS.start() + hash % range
+}
+
+fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> io::Result<()> {
+    comments
+        .iter()
+        .try_for_each(|comment| writeln!(w, "{}// {comment}", "  ".repeat(indent)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn field(name: &str, tag: Option<&str>) -> RustField {
+        RustField {
+            id: Id {
+                original: name.into(),
+                renamed: name.into(),
+                serde_rename: false,
+            },
+            ty: RustType::Special(SpecialRustType::String),
+            comments: Vec::new(),
+            has_default: false,
+            decorators: tag
+                .map(|tag| {
+                    (
+                        SupportedLanguage::Proto,
+                        [FieldDecorator::NameValue("tag".into(), tag.into())].into(),
+                    )
+                })
+                .into_iter()
+                .collect(),
+            languages: Default::default(),
+        }
+    }
+
+    fn field_numbers(message: &str, fields: &[RustField]) -> io::Result<Vec<u32>> {
+        numbers(message, false, fields)
+    }
+
+    fn numbers(message: &str, strict: bool, fields: &[RustField]) -> io::Result<Vec<u32>> {
+        let members = fields
+            .iter()
+            .map(|field| (&field.id, &field.decorators))
+            .collect::<Vec<_>>();
+        tag_numbers(message, strict, &members)
+    }
+
+    #[test]
+    fn field_numbers_keep_explicit_tags() {
+        let fields = [
+            field("a", Some("1")),
+            field("b", None),
+            field("c", Some("3")),
+        ];
+        let numbers = field_numbers("M", &fields).unwrap();
+        assert_eq!(numbers[0], 1);
+        assert!(HASHED_FIELD_NUMBERS.contains(&numbers[1]));
+        assert_eq!(numbers[2], 3);
+    }
+
+    #[test]
+    fn field_numbers_stay_when_fields_are_inserted() {
+        let before = field_numbers("M", &[field("a", None), field("c", None)]).unwrap();
+        let after =
+            field_numbers("M", &[field("b", None), field("a", None), field("c", None)]).unwrap();
+        assert_eq!(before, [after[1], after[2]]);
+        assert!(!before.contains(&after[0]));
+    }
+
+    #[test]
+    fn field_numbers_reject_invalid_tags() {
+        assert!(field_numbers("M", &[field("a", Some("0"))]).is_err());
+        assert!(field_numbers("M", &[field("a", Some("19000"))]).is_err());
+        assert!(field_numbers("M", &[field("a", Some("one"))]).is_err());
+        assert!(field_numbers("M", &[field("a", Some("2")), field("b", Some("2"))]).is_err());
+        let hashed = hashed_number("b").to_string();
+        assert!(field_numbers("M", &[field("a", Some(&hashed)), field("b", None)]).is_err());
+    }
+
+    #[test]
+    fn strict_tags_require_every_tag() {
+        let tagged = [field("a", Some("2")), field("b", Some("1"))];
+        assert_eq!(numbers("M", true, &tagged).unwrap(), vec![2, 1]);
+        assert!(numbers("M", true, &[field("a", Some("1")), field("b", None)]).is_err());
+    }
+}
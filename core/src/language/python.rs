@@ -1,9 +1,9 @@
-use crate::parser::ParsedData;
+use crate::parser::{DecoratorKind, ParsedData};
 use crate::rust_types::{RustEnumShared, RustItem, RustType, RustTypeFormatError, SpecialRustType};
 use crate::topsort::topsort;
 use crate::RenameExt;
 use crate::{
-    language::Language,
+    language::{Language, SupportedLanguage},
     rust_types::{
         RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustStruct, RustTypeAlias,
     },
@@ -13,7 +13,7 @@ use std::hash::Hash;
 use std::sync::OnceLock;
 use std::{collections::HashMap, io::Write};
 
-use super::CrateTypes;
+use super::{filter_language, generic_type_mapping, substitute_generic_parameters, CrateTypes};
 
 use convert_case::{Case, Casing};
 use itertools::Itertools;
@@ -97,6 +97,10 @@ pub struct Python {
 }
 
 impl Language for Python {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Python
+    }
+
     fn type_map(&mut self) -> &HashMap<String, String> {
         &self.type_mappings
     }
@@ -117,13 +121,15 @@ impl Language for Python {
             ..
         } = data;
 
-        let mut items = aliases
-            .into_iter()
-            .map(RustItem::Alias)
-            .chain(structs.into_iter().map(RustItem::Struct))
-            .chain(enums.into_iter().map(RustItem::Enum))
-            .chain(consts.into_iter().map(RustItem::Const))
-            .collect::<Vec<_>>();
+        let mut items = filter_language(
+            aliases
+                .into_iter()
+                .map(RustItem::Alias)
+                .chain(structs.into_iter().map(RustItem::Struct))
+                .chain(enums.into_iter().map(RustItem::Enum))
+                .chain(consts.into_iter().map(RustItem::Const)),
+            self.language(),
+        );
 
         topsort(&mut items);
 
@@ -167,8 +173,11 @@ impl Language for Python {
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
         self.add_imports(base);
-        if let Some(mapped) = self.type_map().get(base) {
-            Ok(mapped.into())
+        if let Some(mapped) = generic_type_mapping(self.type_map(), base, parameters.len()).cloned()
+        {
+            substitute_generic_parameters(&mapped, parameters, |p| {
+                self.format_type(p, generic_types)
+            })
         } else {
             let parameters: Result<Vec<String>, RustTypeFormatError> = parameters
                 .iter()
@@ -315,6 +324,16 @@ impl Language for Python {
                 .cloned()
                 .for_each(|v| self.add_type_var(v))
         }
+        // `#[typeshare(python = "...")]` lists extra base classes and `model_config` options,
+        // e.g. `python = "Auditable, frozen=True"`. The extra bases come first, in the order
+        // they were written, so mixins that subclass `BaseModel` keep a valid MRO.
+        let (config, extra_bases): (Vec<&str>, Vec<&str>) = rs
+            .decorators
+            .get(&DecoratorKind::Language(SupportedLanguage::Python))
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .partition(|decorator| is_config_option(decorator));
         let bases = match rs.generic_types.is_empty() {
             true => "BaseModel".to_string(),
             false => {
@@ -322,11 +341,16 @@ impl Language for Python {
                 format!("BaseModel, Generic[{}]", rs.generic_types.join(", "))
             }
         };
-        writeln!(w, "class {}({}):", rs.id.renamed, bases,)?;
+        writeln!(
+            w,
+            "class {}({}):",
+            rs.id.renamed,
+            extra_bases.into_iter().chain([bases.as_str()]).join(", ")
+        )?;
 
         self.write_comments(w, true, &rs.comments, 1)?;
 
-        handle_model_config(w, self, &rs.fields);
+        handle_model_config(w, self, &rs.fields, &config);
 
         rs.fields
             .iter()
@@ -444,9 +468,12 @@ impl Python {
         let is_optional = field.ty.is_optional() || field.has_default;
         // currently, if a field has a serde default value, it must be an Option
         let not_optional_but_default = !field.ty.is_optional() && field.has_default;
-        let python_type = self
-            .format_type(&field.ty, generic_types)
-            .map_err(std::io::Error::other)?;
+        let python_type = match field.type_override(SupportedLanguage::Python) {
+            Some(type_override) => type_override.to_owned(),
+            None => self
+                .format_type(&field.ty, generic_types)
+                .map_err(std::io::Error::other)?,
+        };
         let python_field_name = python_property_aware_rename(&field.id.original);
         let is_aliased = python_field_name != field.id.renamed;
         let custom_translations = json_translation_for_type(&python_type);
@@ -743,17 +770,36 @@ fn python_property_aware_rename(name: &str) -> String {
 }
 
 // If at least one field from within a class is changed when the serde rename is used (a.k.a the field has 2 words) then we must use aliasing and we must also use a config dict at the top level of the class.
-fn handle_model_config(w: &mut dyn Write, python_module: &mut Python, fields: &[RustField]) {
+fn handle_model_config(
+    w: &mut dyn Write,
+    python_module: &mut Python,
+    fields: &[RustField],
+    config: &[&str],
+) {
     let visibly_renamed_field = fields.iter().find(|f| {
         let python_field_name = python_property_aware_rename(&f.id.original);
         python_field_name != f.id.renamed
     });
-    if visibly_renamed_field.is_some() {
+    let options = visibly_renamed_field
+        .map(|_| "populate_by_name=True")
+        .into_iter()
+        .chain(config.iter().copied())
+        .collect::<Vec<_>>();
+    if !options.is_empty() {
         python_module.add_import("pydantic".to_string(), "ConfigDict".to_string());
-        let _ = writeln!(w, "    model_config = ConfigDict(populate_by_name=True)\n");
+        let _ = writeln!(w, "    model_config = ConfigDict({})\n", options.join(", "));
     };
 }
 
+/// Whether a Python decorator is a `model_config` option like `frozen=True`, rather than a
+/// base class like `Mixin(frozen=True)`.
+fn is_config_option(decorator: &str) -> bool {
+    decorator.split_once('=').is_some_and(|(key, _)| {
+        let key = key.trim();
+        !key.is_empty() && key.chars().all(|c| c == '_' || c.is_alphanumeric())
+    })
+}
+
 /// acquires custom translation function names if custom serialize/deserialize functions are needed
 fn json_translation_for_type(python_type: &str) -> Option<CustomJsonTranslationFunctions> {
     // if more custom serialization/deserialization is needed, we can add it here and in the hashmap below
@@ -835,6 +881,7 @@ mod test {
             has_default: true,
             comments: Default::default(),
             decorators: Default::default(),
+            languages: Default::default(),
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(
@@ -859,6 +906,7 @@ mod test {
             has_default: false,
             comments: Default::default(),
             decorators: Default::default(),
+            languages: Default::default(),
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(
@@ -885,6 +933,7 @@ mod test {
             has_default: true,
             comments: Default::default(),
             decorators: Default::default(),
+            languages: Default::default(),
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(
@@ -909,6 +958,7 @@ mod test {
             has_default: false,
             comments: Default::default(),
             decorators: Default::default(),
+            languages: Default::default(),
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(String::from_utf8_lossy(mock_writer), "    field: str\n");
@@ -1,8 +1,8 @@
-use super::{CrateTypes, Language};
+use super::{filter_language, CrateTypes, Language};
 use crate::language::SupportedLanguage;
-use crate::parser::{remove_dash_from_identifier, ParsedData};
+use crate::parser::{remove_dash_from_identifier, DecoratorKind, ParsedData};
 use crate::rust_types::{
-    RustConst, RustEnum, RustEnumVariant, RustField, RustStruct, RustType, RustTypeAlias,
+    RustConst, RustEnum, RustEnumVariant, RustField, RustItem, RustStruct, RustType, RustTypeAlias,
     RustTypeFormatError, SpecialRustType,
 };
 use itertools::Itertools;
@@ -26,6 +26,10 @@ pub struct Scala {
 }
 
 impl Language for Scala {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Scala
+    }
+
     fn generate_types(
         &mut self,
         writable: &mut dyn Write,
@@ -34,25 +38,51 @@ impl Language for Scala {
     ) -> std::io::Result<()> {
         self.begin_file(writable, &data)?;
 
+        let ParsedData {
+            structs,
+            enums,
+            aliases,
+            ..
+        } = data;
+
+        let items = filter_language(
+            aliases
+                .into_iter()
+                .map(RustItem::Alias)
+                .chain(structs.into_iter().map(RustItem::Struct))
+                .chain(enums.into_iter().map(RustItem::Enum)),
+            self.language(),
+        );
+
+        let (mut aliases, mut structs, mut enums) = (Vec::new(), Vec::new(), Vec::new());
+        for item in items {
+            match item {
+                RustItem::Alias(a) => aliases.push(a),
+                RustItem::Struct(s) => structs.push(s),
+                RustItem::Enum(e) => enums.push(e),
+                RustItem::Const(_) => (),
+            }
+        }
+
         // Package object to hold type aliases: aliases must be in class or object in Scala 2)
-        let unsigned_used = self.unsigned_integer_used(&data);
-        if unsigned_used || !data.aliases.is_empty() {
+        let unsigned_used = self.unsigned_integer_used(&aliases, &structs, &enums);
+        if unsigned_used || !aliases.is_empty() {
             self.begin_package_object(writable)?;
             if unsigned_used {
                 self.write_unsigned_aliases(writable)?;
             }
-            for a in data.aliases.iter() {
+            for a in aliases.iter() {
                 self.write_type_alias(writable, a)?;
             }
             self.end_package_object(writable)?;
         }
 
-        if !data.structs.is_empty() || !data.enums.is_empty() {
+        if !structs.is_empty() || !enums.is_empty() {
             self.begin_package(writable)?;
-            for s in data.structs.iter() {
+            for s in structs.iter() {
                 self.write_struct(writable, s)?;
             }
-            for e in data.enums.iter() {
+            for e in enums.iter() {
                 self.write_enum(writable, e)?;
             }
             self.end_package(writable)?;
@@ -164,6 +194,14 @@ impl Language for Scala {
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
         self.write_comments(w, 0, &rs.comments)?;
 
+        // Traits to mix in, from `#[typeshare(scala = "...")]`
+        let mixins = rs
+            .decorators
+            .get(&DecoratorKind::Language(SupportedLanguage::Scala))
+            .into_iter()
+            .flatten()
+            .collect_vec();
+
         if !rs.fields.is_empty() {
             writeln!(
                 w,
@@ -182,9 +220,21 @@ impl Language for Scala {
                 self.write_element(w, last, rs.generic_types.as_slice())?;
                 writeln!(w)?;
             }
-            writeln!(w, ")\n")?;
+            match mixins.split_first() {
+                Some((first, rest)) => writeln!(
+                    w,
+                    ") extends {first}{}\n",
+                    rest.iter().map(|mixin| format!(" with {mixin}")).join("")
+                )?,
+                None => writeln!(w, ")\n")?,
+            }
         } else {
-            writeln!(w, "class {} extends Serializable\n", rs.id.renamed)?;
+            writeln!(
+                w,
+                "class {} extends Serializable{}\n",
+                rs.id.renamed,
+                mixins.iter().map(|mixin| format!(" with {mixin}")).join("")
+            )?;
         }
         Ok(())
     }
@@ -447,16 +497,19 @@ impl Scala {
         Ok(())
     }
 
-    fn unsigned_integer_used(&mut self, data: &ParsedData) -> bool {
-        let types_in_aliases = data.aliases.iter().map(|f| f.r#type.clone()).collect_vec();
-        let types_in_structs = data
-            .structs
+    fn unsigned_integer_used(
+        &mut self,
+        aliases: &[RustTypeAlias],
+        structs: &[RustStruct],
+        enums: &[RustEnum],
+    ) -> bool {
+        let types_in_aliases = aliases.iter().map(|f| f.r#type.clone()).collect_vec();
+        let types_in_structs = structs
             .iter()
             .flat_map(|f| f.fields.clone())
             .map(|f| f.ty)
             .collect_vec();
-        let types_in_enum = data
-            .enums
+        let types_in_enum = enums
             .iter()
             .flat_map(|e| {
                 e.shared().variants.iter().flat_map(|v| match v {
@@ -3,7 +3,7 @@ use crate::{
     parser::{remove_dash_from_identifier, DecoratorKind, ParsedData},
     rename::RenameExt,
     rust_types::{
-        DecoratorMap, RustConst, RustEnum, RustEnumVariant, RustStruct, RustTypeAlias,
+        DecoratorMap, RustConst, RustEnum, RustEnumVariant, RustField, RustStruct, RustTypeAlias,
         RustTypeFormatError, SpecialRustType,
     },
     GenerationError,
@@ -157,6 +157,10 @@ pub struct Swift {
 }
 
 impl Language for Swift {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::Swift
+    }
+
     fn type_map(&mut self) -> &HashMap<String, String> {
         &self.type_mappings
     }
@@ -279,7 +283,10 @@ impl Language for Swift {
 
         // If there are no decorators found for this struct, still write `Codable` and default decorators for structs
         // Check if this struct's decorators contains swift in the hashmap
-        let decs = if let Some(swift_decs) = rs.decorators.get(&DecoratorKind::Swift) {
+        let decs = if let Some(swift_decs) = rs
+            .decorators
+            .get(&DecoratorKind::Language(SupportedLanguage::Swift))
+        {
             // For reach item in the received decorators in the typeshared struct add it to the original vector
             // this avoids duplicated of `Codable` without needing to `.sort()` then `.dedup()`
             // Note: the list received from `rs.decorators` is already deduped
@@ -288,7 +295,8 @@ impl Language for Swift {
                     swift_decs
                         .iter()
                         .filter(|d| d.as_str() != CODABLE)
-                        .map(|s| s.as_str()),
+                        .map(|s| s.as_str())
+                        .sorted(),
                 ),
             )
         } else {
@@ -313,12 +321,14 @@ impl Language for Swift {
 
             // Create coding keys for serialization / deserialization
             //
-            // As of right now this was only written to handle fields
-            // that get renamed to an ident with - in it
-            if f.id.renamed.chars().any(|c| c == '-') {
+            // This handles fields that get renamed to an ident with - in it,
+            // and fields with a `swift(name = "...")` override.
+            if f.id.renamed.chars().any(|c| c == '-')
+                || f.name_override(SupportedLanguage::Swift).is_some()
+            {
                 coding_keys.push(format!(
                     r##"{} = "{}""##,
-                    remove_dash_from_identifier(swift_keyword_aware_rename(&f.id.renamed).as_ref()),
+                    swift_keyword_aware_rename(field_name(f)),
                     &f.id.renamed
                 ));
 
@@ -341,7 +351,7 @@ impl Language for Swift {
             writeln!(
                 w,
                 "\tpublic let {}: {}{}",
-                remove_dash_from_identifier(swift_keyword_aware_rename(&f.id.renamed).as_ref()),
+                swift_keyword_aware_rename(field_name(f)),
                 case_type,
                 (f.has_default && !f.ty.is_optional())
                     .then_some("?")
@@ -375,7 +385,7 @@ impl Language for Swift {
 
             init_params.push(format!(
                 "{}: {}{}",
-                remove_dash_from_identifier(&f.id.renamed),
+                field_name(f),
                 swift_ty,
                 (f.has_default && !f.ty.is_optional())
                     .then_some("?")
@@ -388,8 +398,8 @@ impl Language for Swift {
             write!(
                 w,
                 "\n\t\tself.{} = {}",
-                remove_dash_from_identifier(&f.id.renamed),
-                remove_dash_from_identifier(swift_keyword_aware_rename(&f.id.renamed).as_ref())
+                field_name(f),
+                swift_keyword_aware_rename(field_name(f))
             )?;
         }
         if !rs.fields.is_empty() {
@@ -409,7 +419,11 @@ impl Language for Swift {
             e: &'a RustEnum,
         ) -> impl Iterator<Item = &'a str> {
             always_present.iter().copied().chain(
-                if let Some(swift_decs) = e.shared().decorators.get(&DecoratorKind::Swift) {
+                if let Some(swift_decs) = e
+                    .shared()
+                    .decorators
+                    .get(&DecoratorKind::Language(SupportedLanguage::Swift))
+                {
                     // Add any decorators from the typeshared enum
                     // Note: `swift_decs` is already deduped
                     Either::Left(
@@ -417,7 +431,8 @@ impl Language for Swift {
                             .iter()
                             .map(|s| s.as_str())
                             // Avoids needing to sort / dedup
-                            .filter(|d| !always_present.contains(d)),
+                            .filter(|d| !always_present.contains(d))
+                            .sorted(),
                     )
                 } else {
                     Either::Right(std::iter::empty())
@@ -556,7 +571,7 @@ impl Swift {
         match e {
             RustEnum::Unit(shared) => {
                 for v in &shared.variants {
-                    let variant_name = v.shared().id.original.to_camel_case();
+                    let variant_name = variant_name(v);
 
                     self.write_comments(w, 1, &v.shared().comments)?;
                     if v.shared().id.renamed == variant_name {
@@ -583,7 +598,7 @@ impl Swift {
                     self.write_comments(w, 1, &v.shared().comments)?;
 
                     let variant_name = {
-                        let mut variant_name = v.shared().id.original.to_camel_case();
+                        let mut variant_name = variant_name(v);
 
                         if variant_name
                             .chars()
@@ -846,6 +861,19 @@ impl Swift {
     }
 }
 
+/// The Swift property name of a field, without keyword escaping.
+fn field_name(f: &RustField) -> String {
+    f.name_override(SupportedLanguage::Swift)
+        .map_or_else(|| remove_dash_from_identifier(&f.id.renamed), str::to_owned)
+}
+
+/// The Swift case name of an enum variant, without keyword escaping.
+fn variant_name(v: &RustEnumVariant) -> String {
+    v.shared()
+        .name_override(SupportedLanguage::Swift)
+        .map_or_else(|| v.shared().id.original.to_camel_case(), str::to_owned)
+}
+
 fn swift_keyword_aware_rename<'a, T>(name: T) -> Cow<'a, str>
 where
     T: Into<Cow<'a, str>>,
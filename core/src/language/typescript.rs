@@ -1,7 +1,7 @@
 use crate::RenameExt;
 use crate::{
     language::{Language, SupportedLanguage},
-    parser::ParsedData,
+    parser::{DecoratorKind, ParsedData},
     rust_types::{
         RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustStruct, RustType,
         RustTypeAlias, RustTypeFormatError, SpecialRustType,
@@ -27,6 +27,8 @@ pub struct TypeScript {
     pub no_version_header: bool,
     /// Carries the unique set of types for custom json translation
     pub types_for_custom_json_translation: BTreeMap<String, BTreeSet<String>>,
+    /// Generate `isFoo` type guards and `assertFoo` assertion functions for every type.
+    pub type_guards: bool,
 }
 
 #[derive(Clone)]
@@ -36,6 +38,10 @@ pub struct CustomJsonTranslationContent {
 }
 
 impl Language for TypeScript {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::TypeScript
+    }
+
     fn type_map(&mut self) -> &HashMap<String, String> {
         &self.type_mappings
     }
@@ -168,6 +174,22 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
                 .unwrap_or_default(),
         )?;
 
+        if self.type_guards {
+            let check = self
+                .type_check(&ty.r#type, "value", &ty.generic_types)
+                .map_err(io::Error::other)?;
+            let check = match (check, ty.r#type.is_optional()) {
+                (Some(check), true) => format!("value === undefined || {check}"),
+                (check, _) => check.unwrap_or_else(|| "true".to_owned()),
+            };
+            self.write_type_guard(
+                w,
+                &ty.id.renamed,
+                &ty.generic_types,
+                &format!("\treturn {check};\n"),
+            )?;
+        }
+
         Ok(())
     }
 
@@ -192,10 +214,15 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
         self.write_comments(w, 0, &rs.comments)?;
         writeln!(
             w,
-            "export interface {}{} {{",
+            "export interface {}{}{} {{",
             rs.id.renamed,
             (!rs.generic_types.is_empty())
                 .then(|| format!("<{}>", rs.generic_types.join(", ")))
+                .unwrap_or_default(),
+            // Interfaces to extend, from `#[typeshare(typescript = "...")]`
+            rs.decorators
+                .get(&DecoratorKind::Language(SupportedLanguage::TypeScript))
+                .map(|bases| format!(" extends {}", bases.iter().join(", ")))
                 .unwrap_or_default()
         )?;
 
@@ -203,7 +230,13 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
             .iter()
             .try_for_each(|f| self.write_field(w, f, rs.generic_types.as_slice()))?;
 
-        writeln!(w, "}}\n")
+        writeln!(w, "}}\n")?;
+
+        if self.type_guards {
+            let body = self.object_checks(&rs.fields, "value", &rs.generic_types, 1)?;
+            self.write_type_guard(w, &rs.id.renamed, &rs.generic_types, &body)?;
+        }
+        Ok(())
     }
 
     fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
@@ -223,7 +256,7 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
 
                 self.write_enum_variants(w, e)?;
 
-                writeln!(w, "\n}}\n")
+                writeln!(w, "\n}}\n")?;
             }
             RustEnum::Algebraic { shared, .. } => {
                 write!(
@@ -236,9 +269,16 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
 
                 write!(w, ";")?;
                 writeln!(w)?;
-                writeln!(w)
+                writeln!(w)?;
             }
         }
+
+        if self.type_guards {
+            let body = self.enum_checks(e)?;
+            let shared = e.shared();
+            self.write_type_guard(w, &shared.id.renamed, &shared.generic_types, &body)?;
+        }
+        Ok(())
     }
 
     fn write_imports(
@@ -248,7 +288,11 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
     ) -> std::io::Result<()> {
         for (path, ty) in imports {
             write!(w, "import {{ ")?;
-            let ty_list = ty.iter().join(", ");
+            let ty_list = if self.type_guards {
+                ty.iter().map(|t| format!("{t}, is{t}")).join(", ")
+            } else {
+                ty.iter().join(", ")
+            };
             write!(w, "{ty_list}")?;
             writeln!(w, " }} from \"./{path}\";")?;
         }
@@ -354,16 +398,269 @@ impl TypeScript {
         writeln!(
             w,
             "\t{}{}{}: {}{};",
-            is_readonly.then_some("readonly ").unwrap_or_default(),
+            if is_readonly { "readonly " } else { "" },
             typescript_property_aware_rename(&field.id.renamed),
-            optional.then_some("?").unwrap_or_default(),
+            if optional { "?" } else { "" },
             ts_ty,
-            double_optional.then_some(" | null").unwrap_or_default()
+            if double_optional { " | null" } else { "" }
         )?;
 
         Ok(())
     }
 
+    /// Write the `isFoo` type guard and `assertFoo` assertion function of a type, given the
+    /// body of the type guard.
+    fn write_type_guard(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        generic_types: &[String],
+        body: &str,
+    ) -> io::Result<()> {
+        let generic_parameters = if generic_types.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", generic_types.join(", "))
+        };
+        let guard_parameters = generic_types
+            .iter()
+            .map(|g| format!(", is{g}: (value: unknown) => value is {g}"))
+            .join("");
+        let guard_arguments = generic_types.iter().map(|g| format!(", is{g}")).join("");
+
+        writeln!(
+            w,
+            "export function is{name}{generic_parameters}(value: unknown{guard_parameters}): value is {name}{generic_parameters} {{"
+        )?;
+        write!(w, "{body}")?;
+        writeln!(w, "}}\n")?;
+
+        writeln!(
+            w,
+            "export function assert{name}{generic_parameters}(value: unknown{guard_parameters}): asserts value is {name}{generic_parameters} {{"
+        )?;
+        writeln!(w, "\tif (!is{name}(value{guard_arguments})) {{")?;
+        writeln!(
+            w,
+            "\t\tthrow new TypeError(\"Expected a value of type {name}\");"
+        )?;
+        writeln!(w, "\t}}")?;
+        writeln!(w, "}}\n")
+    }
+
+    /// Statements checking that the variable `value` is an object with the given fields, indented
+    /// by `indent` tabs.
+    fn object_checks(
+        &mut self,
+        fields: &[RustField],
+        value: &str,
+        generic_types: &[String],
+        indent: usize,
+    ) -> io::Result<String> {
+        let tabs = "\t".repeat(indent);
+        let object = format!("{value}Object");
+        let mut checks = Vec::new();
+        for field in fields {
+            let property = format!("{object}{}", property_access(&field.id.renamed));
+            let check = match field.type_override(SupportedLanguage::TypeScript) {
+                Some(_) => None,
+                None => {
+                    let ty = match &field.ty {
+                        // Optionality is checked below.
+                        RustType::Special(SpecialRustType::Option(rtype)) => rtype.as_ref(),
+                        ty => ty,
+                    };
+                    self.type_check(ty, &property, generic_types)
+                        .map_err(io::Error::other)?
+                }
+            };
+            checks.push(match check {
+                Some(check) if field.ty.is_optional() => format!("({property} == null || {check})"),
+                Some(check) if field.has_default => {
+                    format!("({property} === undefined || {check})")
+                }
+                Some(check) => check,
+                None if field.ty.is_optional() || field.has_default => continue,
+                None => format!("{:?} in {object}", field.id.renamed),
+            });
+        }
+
+        let mut body = format!(
+            "{tabs}if (typeof {value} !== \"object\" || {value} === null) {{\n{tabs}\treturn false;\n{tabs}}}\n"
+        );
+        if checks.is_empty() {
+            body.push_str(&format!("{tabs}return true;\n"));
+        } else {
+            body.push_str(&format!(
+                "{tabs}const {object} = {value} as Record<string, unknown>;\n{tabs}return {};\n",
+                checks.join(&format!("\n{tabs}\t&& "))
+            ));
+        }
+        Ok(body)
+    }
+
+    /// Statements checking that `value` is one of the variants of an enum.
+    fn enum_checks(&mut self, e: &RustEnum) -> io::Result<String> {
+        match e {
+            RustEnum::Unit(shared) => Ok(format!(
+                "\treturn {};\n",
+                shared
+                    .variants
+                    .iter()
+                    .map(|v| format!("value === {:?}", v.shared().id.renamed))
+                    .join(" || ")
+            )),
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => {
+                let mut body = String::from(
+                    "\tif (typeof value !== \"object\" || value === null) {\n\t\treturn false;\n\t}\n",
+                );
+                body.push_str("\tconst valueObject = value as Record<string, unknown>;\n");
+                body.push_str(&format!(
+                    "\tswitch (valueObject{}) {{\n",
+                    property_access(tag_key)
+                ));
+                let content = format!("valueObject{}", property_access(content_key));
+                for v in &shared.variants {
+                    body.push_str(&format!("\t\tcase {:?}:", v.shared().id.renamed));
+                    match v {
+                        RustEnumVariant::Unit(_) => body.push_str(" {\n\t\t\treturn true;\n"),
+                        RustEnumVariant::Tuple { ty, .. } => {
+                            let check = self
+                                .type_check(ty, &content, &shared.generic_types)
+                                .map_err(io::Error::other)?;
+                            let check = match check {
+                                Some(check) if ty.is_optional() => {
+                                    format!("{content} == null || {check}")
+                                }
+                                Some(check) => check,
+                                None => "true".to_owned(),
+                            };
+                            body.push_str(&format!(" {{\n\t\t\treturn {check};\n"));
+                        }
+                        RustEnumVariant::AnonymousStruct { fields, .. } => {
+                            body.push_str(&format!(" {{\n\t\t\tconst content = {content};\n"));
+                            body.push_str(&self.object_checks(
+                                fields,
+                                "content",
+                                &shared.generic_types,
+                                3,
+                            )?);
+                        }
+                    }
+                    body.push_str("\t\t}\n");
+                }
+                body.push_str("\t\tdefault: {\n\t\t\treturn false;\n\t\t}\n\t}\n");
+                Ok(body)
+            }
+        }
+    }
+
+    /// An expression checking that `value` has the type `ty`, unless the type can't be checked.
+    fn type_check(
+        &mut self,
+        ty: &RustType,
+        value: &str,
+        generic_types: &[String],
+    ) -> Result<Option<String>, RustTypeFormatError> {
+        let mapped = match ty {
+            RustType::Special(special) => self.type_mappings.get(&special.to_string()),
+            ty => self.type_mappings.get(ty.id()),
+        };
+        if let Some(mapped) = mapped {
+            return Ok(primitive_check(mapped, value));
+        }
+
+        Ok(Some(match ty {
+            RustType::Simple { id } => format!("is{id}({value})"),
+            RustType::Generic { id, parameters } => {
+                let guards = parameters
+                    .iter()
+                    .map(|p| self.guard_function(p, generic_types))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("is{id}({value}, {})", guards.join(", "))
+            }
+            RustType::Special(special) => match special {
+                SpecialRustType::Vec(rtype) | SpecialRustType::Slice(rtype) => {
+                    match self.type_check(rtype, "e", generic_types)? {
+                        Some(check) => {
+                            format!("Array.isArray({value}) && {value}.every((e) => {check})")
+                        }
+                        None => format!("Array.isArray({value})"),
+                    }
+                }
+                SpecialRustType::Array(rtype, len) => {
+                    match self.type_check(rtype, "e", generic_types)? {
+                        Some(check) => format!(
+                            "Array.isArray({value}) && {value}.length === {len} && {value}.every((e) => {check})"
+                        ),
+                        None => format!("Array.isArray({value}) && {value}.length === {len}"),
+                    }
+                }
+                SpecialRustType::Option(rtype) => {
+                    match self.type_check(rtype, value, generic_types)? {
+                        Some(check) => format!("({value} == null || {check})"),
+                        None => return Ok(None),
+                    }
+                }
+                SpecialRustType::HashMap(rtype1, rtype2) => {
+                    // Object keys are always strings, so only keys of shared types are checked.
+                    let key_check = match rtype1.as_ref() {
+                        RustType::Special(_) => None,
+                        key => self.type_check(key, "k", generic_types)?,
+                    };
+                    let value_check = self.type_check(rtype2, "e", generic_types)?;
+                    let object = format!(
+                        "typeof {value} === \"object\" && {value} !== null && !Array.isArray({value})"
+                    );
+                    match (key_check, value_check) {
+                        (None, None) => object,
+                        (Some(key), None) => format!(
+                            "{object} && Object.keys({value}).every((k) => {key})"
+                        ),
+                        (None, Some(check)) => format!(
+                            "{object} && Object.values({value}).every((e) => {check})"
+                        ),
+                        (Some(key), Some(check)) => format!(
+                            "{object} && Object.entries({value}).every(([k, e]) => {key} && {check})"
+                        ),
+                    }
+                }
+                SpecialRustType::Unit => format!("{value} == null"),
+                SpecialRustType::DateTime => format!("{value} instanceof Date"),
+                SpecialRustType::String | SpecialRustType::Char => {
+                    format!("typeof {value} === \"string\"")
+                }
+                SpecialRustType::Bool => format!("typeof {value} === \"boolean\""),
+                _ => format!("typeof {value} === \"number\""),
+            },
+        }))
+    }
+
+    /// A type guard function for `ty`, to pass to the type guard of a generic type.
+    fn guard_function(
+        &mut self,
+        ty: &RustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        match ty {
+            RustType::Simple { id } if generic_types.contains(id) => Ok(format!("is{id}")),
+            RustType::Simple { id } if !self.type_mappings.contains_key(id) => {
+                Ok(format!("is{id}"))
+            }
+            ty => {
+                let ts_type = self.format_type(ty, generic_types)?;
+                let check = self
+                    .type_check(ty, "value", generic_types)?
+                    .unwrap_or_else(|| "true".to_owned());
+                Ok(format!("(value: unknown): value is {ts_type} => {check}"))
+            }
+        }
+    }
+
     fn write_comments(
         &mut self,
         w: &mut dyn Write,
@@ -435,6 +732,26 @@ impl TypeScript {
     }
 }
 
+/// A check for mapped types whose values can be recognized, like `string` or `Date`.
+fn primitive_check(ts_type: &str, value: &str) -> Option<String> {
+    match ts_type {
+        "string" | "number" | "boolean" | "bigint" => {
+            Some(format!("typeof {value} === {ts_type:?}"))
+        }
+        "Date" | "Uint8Array" => Some(format!("{value} instanceof {ts_type}")),
+        _ => None,
+    }
+}
+
+/// Access a property of an object, quoting names that aren't identifiers.
+fn property_access(name: &str) -> String {
+    if name.chars().any(|c| c == '-') {
+        format!("[{name:?}]")
+    } else {
+        format!(".{name}")
+    }
+}
+
 fn typescript_property_aware_rename(name: &str) -> String {
     if name.chars().any(|c| c == '-') {
         return format!("{:?}", name);
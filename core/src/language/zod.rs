@@ -0,0 +1,771 @@
+use crate::{
+    language::{Language, SupportedLanguage},
+    parser::ParsedData,
+    rename::RenameExt,
+    rust_types::{
+        FieldDecorator, RustConst, RustConstExpr, RustEnum, RustEnumShared, RustEnumVariant,
+        RustField, RustStruct, RustType, RustTypeAlias, RustTypeFormatError, SpecialRustType,
+    },
+};
+use itertools::Itertools;
+use log::warn;
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, Write},
+};
+
+use super::{generic_type_mapping, substitute_generic_parameters, ScopedCrateTypes, TypeScript};
+
+/// All information needed to generate zod schemas and the TypeScript types inferred from them
+///
+/// This is an output mode of the TypeScript generator, so it uses the TypeScript attributes,
+/// such as `#[typeshare(typescript(type = "..."))]`. Every type becomes a schema named
+/// `{Type}Schema`, and a type of the same name inferred with `z.infer`. Generic types become
+/// functions that take a schema for each type parameter. TypeScript can't infer the type of a
+/// recursive schema, so types that reference themselves are written out instead.
+#[derive(Default)]
+pub struct Zod {
+    /// Mappings from Rust type names to TypeScript type names
+    pub type_mappings: HashMap<String, String>,
+    /// Mappings from Rust type names to zod schemas. Types that are only mapped to TypeScript
+    /// types are validated with `z.custom`, which accepts any value.
+    pub zod_type_mappings: HashMap<String, String>,
+    /// Whether or not to exclude the version header that normally appears at the top of generated code.
+    /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
+    pub no_version_header: bool,
+    /// The schemas that have been declared in the file being generated, or imported into it.
+    /// Other schemas are referenced lazily, since they are declared further down.
+    pub declared: HashSet<String>,
+    /// The types of the file being generated that reference themselves, directly or through
+    /// other types.
+    pub recursive: HashSet<String>,
+    /// The mapped types that have already been warned about having no zod schema.
+    pub warned: HashSet<String>,
+}
+
+impl Language for Zod {
+    fn language(&self) -> SupportedLanguage {
+        SupportedLanguage::TypeScript
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn ignored_reference_types(&self) -> Vec<&str> {
+        self.type_mappings.keys().map(|s| s.as_str()).collect()
+    }
+
+    fn format_simple_type(
+        &mut self,
+        base: &String,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        Ok(if let Some(mapped) = self.zod_type_mappings.get(base) {
+            mapped.to_owned()
+        } else if let Some(mapped) = self.type_mappings.get(base).cloned() {
+            self.custom(base, &mapped)
+        } else if generic_types.contains(base) {
+            parameter_name(base)
+        } else {
+            self.reference(base, format!("{base}Schema"))
+        })
+    }
+
+    fn format_generic_type(
+        &mut self,
+        base: &String,
+        parameters: &[RustType],
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        let zod_mapped =
+            generic_type_mapping(&self.zod_type_mappings, base, parameters.len()).cloned();
+        if zod_mapped.is_none() {
+            if let Some(mapped) =
+                generic_type_mapping(&self.type_mappings, base, parameters.len()).cloned()
+            {
+                // The parameters of a TypeScript type are TypeScript types.
+                let mut typescript = TypeScript {
+                    type_mappings: self.type_mappings.clone(),
+                    ..Default::default()
+                };
+                let mapped = substitute_generic_parameters(&mapped, parameters, |p| {
+                    typescript.format_type(p, generic_types)
+                })?;
+                return Ok(self.custom(base, &mapped));
+            }
+        }
+
+        if let Some(mapped) = zod_mapped {
+            return substitute_generic_parameters(&mapped, parameters, |p| {
+                self.format_type(p, generic_types)
+            });
+        }
+        let parameters = parameters
+            .iter()
+            .map(|p| self.format_type(p, generic_types))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.reference(base, format!("{base}Schema({})", parameters.join(", "))))
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        if let Some(mapped) = self.zod_type_mappings.get(&special_ty.to_string()) {
+            return Ok(mapped.to_owned());
+        }
+        if let Some(mapped) = self.type_mappings.get(&special_ty.to_string()).cloned() {
+            return Ok(self.custom(&special_ty.to_string(), &mapped));
+        }
+
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype) | SpecialRustType::Slice(rtype) => {
+                format!("z.array({})", self.format_type(rtype, generic_types)?)
+            }
+            SpecialRustType::Array(rtype, len) => {
+                let schema = self.format_type(rtype, generic_types)?;
+                format!(
+                    "z.tuple([{}])",
+                    std::iter::repeat_n(schema, *len).join(", ")
+                )
+            }
+            // Optional fields are handled by `field_schema`, this is for options in containers.
+            SpecialRustType::Option(rtype) => {
+                format!("{}.nullable()", self.format_type(rtype, generic_types)?)
+            }
+            SpecialRustType::HashMap(rtype1, rtype2) => format!(
+                "z.record({}, {})",
+                match rtype1.as_ref() {
+                    RustType::Simple { id } if generic_types.contains(id) => {
+                        return Err(RustTypeFormatError::GenericKeyForbiddenInTS(id.clone()));
+                    }
+                    // Object keys are always strings in JSON.
+                    RustType::Special(special) if is_number(special) => {
+                        "z.coerce.number()".to_owned()
+                    }
+                    _ => self.format_type(rtype1, generic_types)?,
+                },
+                self.format_type(rtype2, generic_types)?
+            ),
+            SpecialRustType::Unit => "z.undefined()".into(),
+            SpecialRustType::DateTime => "z.coerce.date()".into(),
+            SpecialRustType::String | SpecialRustType::Char => "z.string()".into(),
+            SpecialRustType::F32 | SpecialRustType::F64 => "z.number()".into(),
+            SpecialRustType::I8
+            | SpecialRustType::U8
+            | SpecialRustType::I16
+            | SpecialRustType::U16
+            | SpecialRustType::I32
+            | SpecialRustType::U32
+            | SpecialRustType::I54
+            | SpecialRustType::U53 => "z.number().int()".into(),
+            SpecialRustType::Bool => "z.boolean()".into(),
+            SpecialRustType::U64
+            | SpecialRustType::I64
+            | SpecialRustType::ISize
+            | SpecialRustType::USize => {
+                panic!("64 bit types not allowed in Typeshare")
+            }
+        })
+    }
+
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        self.declared.clear();
+        self.recursive = recursive_types(parsed_data);
+        if !self.no_version_header {
+            writeln!(w, "/*")?;
+            writeln!(w, " Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
+            writeln!(w, "*/")?;
+            writeln!(w)?;
+        }
+        writeln!(w, "import {{ z }} from \"zod\";\n")
+    }
+
+    fn write_imports(
+        &mut self,
+        w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        for (path, types) in imports {
+            self.declared.extend(types.iter().map(|ty| ty.to_string()));
+            writeln!(
+                w,
+                "import {{ {} }} from \"./{path}\";",
+                types.iter().map(|ty| format!("{ty}Schema")).join(", ")
+            )?;
+        }
+        writeln!(w)
+    }
+
+    fn write_type_alias(&mut self, w: &mut dyn Write, ty: &RustTypeAlias) -> io::Result<()> {
+        let schema = self
+            .format_type(&ty.r#type, &ty.generic_types)
+            .map_err(io::Error::other)?;
+        let schema = if ty.r#type.is_optional() {
+            nullish(schema)
+        } else {
+            schema
+        };
+        let output = self
+            .is_recursive(&ty.id.renamed)
+            .then(|| {
+                let output = self.output_type(&ty.r#type, &ty.generic_types)?;
+                Ok::<_, RustTypeFormatError>(if ty.r#type.is_optional() {
+                    format!("{output} | undefined")
+                } else {
+                    output
+                })
+            })
+            .transpose()
+            .map_err(io::Error::other)?;
+        self.write_schema(
+            w,
+            &ty.id.renamed,
+            &ty.generic_types,
+            &ty.comments,
+            &schema,
+            output,
+        )
+    }
+
+    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
+        match c.expr {
+            RustConstExpr::Int(val) => writeln!(
+                w,
+                "export const {}: number = {val};",
+                c.id.renamed.to_snake_case().to_uppercase()
+            ),
+        }
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        let schema = self.object_schema(&rs.fields, &rs.generic_types, 0)?;
+        let output = self
+            .is_recursive(&rs.id.renamed)
+            .then(|| self.object_type(&rs.fields, &rs.generic_types, 0))
+            .transpose()?;
+        self.write_schema(
+            w,
+            &rs.id.renamed,
+            &rs.generic_types,
+            &rs.comments,
+            &schema,
+            output,
+        )
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        let shared = e.shared();
+        let schema = match e {
+            RustEnum::Unit(shared) => {
+                let mut values = Vec::new();
+                for v in &shared.variants {
+                    write_comments(&mut values, 1, &v.shared().comments)?;
+                    writeln!(values, "\t{:?},", v.shared().id.renamed)?;
+                }
+                format!(
+                    "z.enum([\n{}])",
+                    String::from_utf8(values).map_err(io::Error::other)?
+                )
+            }
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => self.union_schema(tag_key, content_key, shared)?,
+        };
+        let output = self
+            .is_recursive(&shared.id.renamed)
+            .then(|| self.enum_type(e))
+            .transpose()?;
+        self.write_schema(
+            w,
+            &shared.id.renamed,
+            &shared.generic_types,
+            &shared.comments,
+            &schema,
+            output,
+        )
+    }
+}
+
+impl Zod {
+    /// Reference the schema of a type, lazily if it hasn't been declared yet.
+    fn reference(&self, base: &str, schema: String) -> String {
+        if self.declared.contains(base) {
+            schema
+        } else {
+            format!("z.lazy(() => {schema})")
+        }
+    }
+
+    /// Whether a type of the file being generated references itself.
+    fn is_recursive(&self, name: &str) -> bool {
+        self.recursive.contains(name)
+    }
+
+    /// Validate a type with only a TypeScript mapping with `z.custom`, which accepts any value.
+    /// Bytes are sent by serde as an array of numbers, which is validated and turned into the
+    /// `Uint8Array` they are mapped to instead.
+    fn custom(&mut self, base: &str, mapped: &str) -> String {
+        if mapped == "Uint8Array" {
+            return "z.array(z.number().int().min(0).max(255)).transform((bytes) => new Uint8Array(bytes))".to_owned();
+        }
+        if self.warned.insert(base.to_owned()) {
+            warn!("`{base}` is mapped to the TypeScript type `{mapped}` but has no zod type mapping, so it is validated with `z.custom`, which accepts any value");
+        }
+        format!("z.custom<{mapped}>()")
+    }
+
+    /// Write a schema and its type, which is inferred from the schema unless the type is
+    /// recursive and `output` is written out.
+    fn write_schema(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        generic_types: &[String],
+        comments: &[String],
+        schema: &str,
+        output: Option<String>,
+    ) -> io::Result<()> {
+        write_comments(w, 0, comments)?;
+        let parameters = generic_parameters(generic_types);
+        if let Some(output) = &output {
+            writeln!(w, "export type {name}{parameters} = {output};")?;
+        }
+        // Schemas that use transforms, like `z.coerce.date()`, take other inputs.
+        let annotation = |output: &str| format!(": z.ZodType<{output}, z.ZodTypeDef, unknown>");
+
+        if generic_types.is_empty() {
+            let annotation = output
+                .as_ref()
+                .map(|_| annotation(name))
+                .unwrap_or_default();
+            writeln!(w, "export const {name}Schema{annotation} = {schema};")?;
+            if output.is_none() {
+                writeln!(w, "export type {name} = z.infer<typeof {name}Schema>;")?;
+            }
+        } else {
+            let annotation = output
+                .as_ref()
+                .map(|_| {
+                    annotation(&format!(
+                        "{name}<{}>",
+                        generic_types
+                            .iter()
+                            .map(|g| format!("z.output<{g}>"))
+                            .join(", ")
+                    ))
+                })
+                .unwrap_or_default();
+            writeln!(
+                w,
+                "export const {name}Schema = <{}>({}){annotation} =>\n\t{};",
+                generic_types
+                    .iter()
+                    .map(|g| format!("{g} extends z.ZodTypeAny"))
+                    .join(", "),
+                generic_types
+                    .iter()
+                    .map(|g| format!("{}: {g}", parameter_name(g)))
+                    .join(", "),
+                schema.replace('\n', "\n\t")
+            )?;
+            if output.is_none() {
+                writeln!(
+                    w,
+                    "export type {name}{parameters} = z.infer<ReturnType<typeof {name}Schema<{}>>>;",
+                    generic_types
+                        .iter()
+                        .map(|g| format!("z.ZodType<{g}>"))
+                        .join(", ")
+                )?;
+            }
+        }
+        writeln!(w)?;
+        self.declared.insert(name.to_owned());
+        Ok(())
+    }
+
+    /// A `z.object` schema, indented by `indent` tabs.
+    fn object_schema(
+        &mut self,
+        fields: &[RustField],
+        generic_types: &[String],
+        indent: usize,
+    ) -> io::Result<String> {
+        if fields.is_empty() {
+            return Ok("z.object({})".to_owned());
+        }
+        let mut object = Vec::new();
+        writeln!(object, "z.object({{")?;
+        for field in fields {
+            write_comments(&mut object, indent + 1, &field.comments)?;
+            writeln!(
+                object,
+                "{}{}: {},",
+                "\t".repeat(indent + 1),
+                property_name(&field.id.renamed),
+                self.field_schema(field, generic_types)?
+            )?;
+        }
+        write!(object, "{}}})", "\t".repeat(indent))?;
+        String::from_utf8(object).map_err(io::Error::other)
+    }
+
+    fn field_schema(&mut self, field: &RustField, generic_types: &[String]) -> io::Result<String> {
+        let schema = if let Some(schema) = zod_override(field) {
+            schema.to_owned()
+        } else if let Some(type_override) = field.type_override(SupportedLanguage::TypeScript) {
+            format!("z.custom<{type_override}>()")
+        } else {
+            match &field.ty {
+                // Optionality is written by the property, and an inner option is nullable.
+                RustType::Special(SpecialRustType::Option(rtype)) => {
+                    self.format_type(rtype, generic_types)
+                }
+                ty => self.format_type(ty, generic_types),
+            }
+            .map_err(io::Error::other)?
+        };
+
+        // serde writes `None` as `null`, and fields with defaults can be left out.
+        Ok(if field.ty.is_optional() {
+            nullish(schema)
+        } else if field.has_default {
+            optional(schema)
+        } else {
+            schema
+        })
+    }
+
+    /// A `z.discriminatedUnion` of the variants of an algebraic enum.
+    fn union_schema(
+        &mut self,
+        tag_key: &str,
+        content_key: &str,
+        e: &RustEnumShared,
+    ) -> io::Result<String> {
+        let mut union = Vec::new();
+        writeln!(union, "z.discriminatedUnion({tag_key:?}, [")?;
+        for v in &e.variants {
+            write_comments(&mut union, 1, &v.shared().comments)?;
+            let tag = format!(
+                "{}: z.literal({:?})",
+                property_name(tag_key),
+                v.shared().id.renamed
+            );
+            match v {
+                RustEnumVariant::Unit(_) => writeln!(union, "\tz.object({{ {tag} }}),")?,
+                RustEnumVariant::Tuple { ty, .. } => {
+                    let mut content = self
+                        .format_type(ty, &e.generic_types)
+                        .map_err(io::Error::other)?;
+                    if ty.is_optional() {
+                        content = nullish(content);
+                    }
+                    writeln!(
+                        union,
+                        "\tz.object({{ {tag}, {}: {content} }}),",
+                        property_name(content_key)
+                    )?
+                }
+                RustEnumVariant::AnonymousStruct { fields, .. } => writeln!(
+                    union,
+                    "\tz.object({{ {tag}, {}: {} }}),",
+                    property_name(content_key),
+                    self.object_schema(fields, &e.generic_types, 1)?
+                )?,
+            }
+        }
+        write!(union, "])")?;
+        String::from_utf8(union).map_err(io::Error::other)
+    }
+
+    /// The type of the values a schema outputs. Types with a zod type mapping are `unknown`,
+    /// since the type of their schema isn't known.
+    fn output_type(
+        &mut self,
+        ty: &RustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        Ok(match ty {
+            RustType::Simple { id } if generic_types.contains(id) => id.to_owned(),
+            RustType::Simple { id } => {
+                if self.zod_type_mappings.contains_key(id) {
+                    "unknown".to_owned()
+                } else if let Some(mapped) = self.type_mappings.get(id) {
+                    mapped.to_owned()
+                } else {
+                    id.to_owned()
+                }
+            }
+            RustType::Generic { id, parameters } => {
+                if generic_type_mapping(&self.zod_type_mappings, id, parameters.len()).is_some() {
+                    "unknown".to_owned()
+                } else if let Some(mapped) =
+                    generic_type_mapping(&self.type_mappings, id, parameters.len()).cloned()
+                {
+                    let mut typescript = TypeScript {
+                        type_mappings: self.type_mappings.clone(),
+                        ..Default::default()
+                    };
+                    substitute_generic_parameters(&mapped, parameters, |p| {
+                        typescript.format_type(p, generic_types)
+                    })?
+                } else {
+                    let parameters = parameters
+                        .iter()
+                        .map(|p| self.output_type(p, generic_types))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    format!("{id}<{}>", parameters.join(", "))
+                }
+            }
+            RustType::Special(special) => {
+                if self.zod_type_mappings.contains_key(&special.to_string()) {
+                    return Ok("unknown".to_owned());
+                }
+                if let Some(mapped) = self.type_mappings.get(&special.to_string()) {
+                    return Ok(mapped.to_owned());
+                }
+                match special {
+                    SpecialRustType::Vec(rtype) | SpecialRustType::Slice(rtype) => {
+                        format!("Array<{}>", self.output_type(rtype, generic_types)?)
+                    }
+                    SpecialRustType::Array(rtype, len) => {
+                        let element = self.output_type(rtype, generic_types)?;
+                        format!("[{}]", std::iter::repeat_n(element, *len).join(", "))
+                    }
+                    SpecialRustType::Option(rtype) => {
+                        format!("{} | null", self.output_type(rtype, generic_types)?)
+                    }
+                    SpecialRustType::HashMap(rtype1, rtype2) => format!(
+                        "Record<{}, {}>",
+                        match rtype1.as_ref() {
+                            RustType::Special(special) if is_number(special) => "number".to_owned(),
+                            _ => self.output_type(rtype1, generic_types)?,
+                        },
+                        self.output_type(rtype2, generic_types)?
+                    ),
+                    SpecialRustType::Unit => "undefined".into(),
+                    SpecialRustType::DateTime => "Date".into(),
+                    SpecialRustType::String | SpecialRustType::Char => "string".into(),
+                    SpecialRustType::Bool => "boolean".into(),
+                    _ => "number".into(),
+                }
+            }
+        })
+    }
+
+    /// The output type of a `z.object` schema, indented by `indent` tabs.
+    fn object_type(
+        &mut self,
+        fields: &[RustField],
+        generic_types: &[String],
+        indent: usize,
+    ) -> io::Result<String> {
+        if fields.is_empty() {
+            return Ok("{}".to_owned());
+        }
+        let mut object = Vec::new();
+        writeln!(object, "{{")?;
+        for field in fields {
+            let ty = if zod_override(field).is_some() {
+                "unknown".to_owned()
+            } else if let Some(type_override) = field.type_override(SupportedLanguage::TypeScript) {
+                type_override.to_owned()
+            } else {
+                self.output_type(&field.ty, generic_types)
+                    .map_err(io::Error::other)?
+            };
+            writeln!(
+                object,
+                "{}{}{}: {ty};",
+                "\t".repeat(indent + 1),
+                property_name(&field.id.renamed),
+                if field.ty.is_optional() || field.has_default {
+                    "?"
+                } else {
+                    ""
+                }
+            )?;
+        }
+        write!(object, "{}}}", "\t".repeat(indent))?;
+        String::from_utf8(object).map_err(io::Error::other)
+    }
+
+    /// The output type of the schema of an enum.
+    fn enum_type(&mut self, e: &RustEnum) -> io::Result<String> {
+        let variants = match e {
+            RustEnum::Unit(shared) => shared
+                .variants
+                .iter()
+                .map(|v| format!("{:?}", v.shared().id.renamed))
+                .collect::<Vec<_>>(),
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => {
+                let mut variants = Vec::new();
+                for v in &shared.variants {
+                    let tag = format!("{}: {:?}", property_name(tag_key), v.shared().id.renamed);
+                    let content = match v {
+                        RustEnumVariant::Unit(_) => None,
+                        RustEnumVariant::Tuple { ty, .. } => Some(format!(
+                            "{}{}: {}",
+                            property_name(content_key),
+                            if ty.is_optional() { "?" } else { "" },
+                            self.output_type(ty, &shared.generic_types)
+                                .map_err(io::Error::other)?
+                        )),
+                        RustEnumVariant::AnonymousStruct { fields, .. } => Some(format!(
+                            "{}: {}",
+                            property_name(content_key),
+                            self.object_type(fields, &shared.generic_types, 1)?
+                        )),
+                    };
+                    variants.push(match content {
+                        Some(content) => format!("{{ {tag}; {content} }}"),
+                        None => format!("{{ {tag} }}"),
+                    });
+                }
+                variants
+            }
+        };
+        Ok(variants
+            .iter()
+            .map(|variant| format!("\n\t| {variant}"))
+            .collect())
+    }
+}
+
+/// The types of a file that reference themselves, directly or through other types of the file.
+fn recursive_types(data: &ParsedData) -> HashSet<String> {
+    let field_references = |fields: &[RustField]| {
+        fields
+            .iter()
+            .filter(|f| zod_override(f).is_none())
+            .filter(|f| f.type_override(SupportedLanguage::TypeScript).is_none())
+            .flat_map(|f| f.ty.all_reference_type_names().map(ToOwned::to_owned))
+            .collect::<Vec<_>>()
+    };
+    let mut references = HashMap::<String, Vec<String>>::new();
+    for rs in &data.structs {
+        references.insert(rs.id.renamed.clone(), field_references(&rs.fields));
+    }
+    for e in &data.enums {
+        let variants = e.shared().variants.iter().flat_map(|v| match v {
+            RustEnumVariant::Unit(_) => Vec::new(),
+            RustEnumVariant::Tuple { ty, .. } => ty
+                .all_reference_type_names()
+                .map(ToOwned::to_owned)
+                .collect(),
+            RustEnumVariant::AnonymousStruct { fields, .. } => field_references(fields),
+        });
+        references.insert(e.shared().id.renamed.clone(), variants.collect());
+    }
+    for alias in &data.aliases {
+        references.insert(
+            alias.id.renamed.clone(),
+            alias
+                .r#type
+                .all_reference_type_names()
+                .map(ToOwned::to_owned)
+                .collect(),
+        );
+    }
+
+    references
+        .keys()
+        .filter(|name| {
+            let mut seen = HashSet::new();
+            let mut stack = references[*name].iter().collect::<Vec<_>>();
+            while let Some(next) = stack.pop() {
+                if next == *name {
+                    return true;
+                }
+                if seen.insert(next) {
+                    stack.extend(references.get(next).into_iter().flatten());
+                }
+            }
+            false
+        })
+        .cloned()
+        .collect()
+}
+
+/// The type parameters of a generic type, if any.
+fn generic_parameters(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generic_types.join(", "))
+    }
+}
+
+/// The zod schema override of a field, from `#[typeshare(typescript(zod = "..."))]`.
+fn zod_override(field: &RustField) -> Option<&str> {
+    field
+        .decorators
+        .get(&SupportedLanguage::TypeScript)?
+        .iter()
+        .find_map(|decorator| match decorator {
+            FieldDecorator::NameValue(name, value) if name == "zod" => Some(value.as_str()),
+            _ => None,
+        })
+}
+
+fn optional(schema: String) -> String {
+    format!("{schema}.optional()")
+}
+
+fn nullish(schema: String) -> String {
+    format!("{schema}.nullish()")
+}
+
+fn is_number(special: &SpecialRustType) -> bool {
+    matches!(
+        special,
+        SpecialRustType::I8
+            | SpecialRustType::U8
+            | SpecialRustType::I16
+            | SpecialRustType::U16
+            | SpecialRustType::I32
+            | SpecialRustType::U32
+            | SpecialRustType::I54
+            | SpecialRustType::U53
+            | SpecialRustType::F32
+            | SpecialRustType::F64
+    )
+}
+
+/// The name of the parameter holding the schema of a generic parameter.
+fn parameter_name(generic: &str) -> String {
+    format!("{}Schema", generic.to_string().to_camel_case())
+}
+
+fn property_name(name: &str) -> String {
+    if name.chars().any(|c| c == '-') {
+        format!("{name:?}")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> io::Result<()> {
+    let indent = "\t".repeat(indent);
+    match comments {
+        [] => Ok(()),
+        [comment] => writeln!(w, "{indent}/** {comment} */"),
+        comments => {
+            writeln!(w, "{indent}/**")?;
+            for comment in comments {
+                writeln!(w, "{indent} * {comment}")?;
+            }
+            writeln!(w, "{indent} */")
+        }
+    }
+}
@@ -2,6 +2,9 @@
 //! Contains the parser and language converters.
 use thiserror::Error;
 
+mod attribute_check;
+pub mod cfg;
+pub mod compat;
 pub mod context;
 /// Implementations for each language converter
 pub mod language;
@@ -11,8 +14,8 @@ pub mod reconcile;
 mod rename;
 /// Codifying Rust types and how they convert to various languages.
 pub mod rust_types;
-mod target_os_check;
 mod topsort;
+pub mod validate;
 mod visitors;
 
 pub use rename::RenameExt;
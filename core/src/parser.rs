@@ -1,51 +1,60 @@
 use crate::{
+    cfg::{accept_cfg, CfgOptions},
     context::{ParseContext, ParseFileContext},
     language::{CrateName, SupportedLanguage},
     rename::RenameExt,
     rust_types::{
-        DecoratorMap, FieldDecorator, Id, RustConst, RustConstExpr, RustEnum, RustEnumShared,
-        RustEnumVariant, RustEnumVariantShared, RustField, RustItem, RustStruct, RustType,
-        RustTypeAlias, RustTypeParseError, SpecialRustType,
+        DecoratorMap, FieldDecorator, FieldDecoratorMap, Id, LanguageFilter, RustConst,
+        RustConstExpr, RustEnum, RustEnumShared, RustEnumVariant, RustEnumVariantShared, RustField,
+        RustItem, RustStruct, RustType, RustTypeAlias, RustTypeParseError, SpecialRustType,
     },
-    target_os_check::accept_target_os,
     visitors::{ImportedType, TypeShareVisitor},
 };
 use itertools::Either;
 use log::debug;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, Span};
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{HashMap, HashSet},
     convert::TryFrom,
+    fmt::{self, Display},
     ops::AddAssign,
 };
 use syn::{
-    ext::IdentExt, parse::ParseBuffer, punctuated::Punctuated, visit::Visit, Attribute, Expr,
-    ExprLit, Fields, GenericParam, ItemConst, ItemEnum, ItemStruct, ItemType, Lit, LitStr, Meta,
-    MetaList, MetaNameValue, Token,
+    ext::IdentExt, parse::ParseBuffer, punctuated::Punctuated, spanned::Spanned, visit::Visit,
+    Attribute, Expr, ExprLit, Fields, GenericParam, ItemConst, ItemEnum, ItemStruct, ItemType, Lit,
+    LitStr, Meta, MetaList, MetaNameValue, Token,
 };
 use thiserror::Error;
 
-const TYPESHARE: &str = "typeshare";
+pub(crate) const TYPESHARE: &str = "typeshare";
 const SERDE: &str = "serde";
 
 /// Supported typeshare type level decorator attributes.
+///
+/// Every supported language has a decorator named after it, like
+/// `#[typeshare(swift = "Equatable")]` or `#[typeshare(typescript = "Timestamped")]`,
+/// which is interpreted by that language's backend.
 #[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
 pub enum DecoratorKind {
-    /// The typeshare attribute for swift type constraints "swift"
-    Swift,
+    /// The typeshare attribute named after a language, e.g. "swift" or "kotlin"
+    Language(SupportedLanguage),
     /// The typeshare attribute for swift generic constraints "swiftGenericConstraints"
     SwiftGenericConstraints,
-    /// The typeshare attribute for kotlin "kotlin"
-    Kotlin,
 }
 
 impl DecoratorKind {
+    /// Returns an iterator over all decorator kinds.
+    pub fn all() -> impl Iterator<Item = Self> {
+        SupportedLanguage::all_languages()
+            .map(DecoratorKind::Language)
+            .chain([DecoratorKind::SwiftGenericConstraints])
+    }
+
     /// This decorator as a str.
-    fn as_str(&self) -> &str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
-            DecoratorKind::Swift => "swift",
+            DecoratorKind::Language(language) => language.as_str(),
             DecoratorKind::SwiftGenericConstraints => "swiftGenericConstraints",
-            DecoratorKind::Kotlin => "kotlin",
         }
     }
 }
@@ -82,6 +91,50 @@ pub enum ParseError {
     SerdeFlattenNotAllowed,
     #[error("IO error: {0}")]
     IOError(String),
+    #[error("duplicate type name `{name}`, first defined at {first_defined}")]
+    DuplicateTypeName {
+        name: String,
+        first_defined: TypeLocation,
+    },
+    #[error("unknown typeshare attribute `{key}`")]
+    UnknownAttribute { key: String },
+    #[error("typeshare attribute `{key}` is not supported on {item_kind}")]
+    MisplacedAttribute { key: String, item_kind: String },
+    #[error("malformed typeshare attribute `{key}`, expected `{expected}`")]
+    MalformedAttribute { key: String, expected: String },
+    #[error("`{name}` references `{referenced}`, which is not generated for {language:?}")]
+    ExcludedTypeReference {
+        name: String,
+        referenced: String,
+        language: SupportedLanguage,
+    },
+}
+
+impl ParseError {
+    /// The name of this kind of error, suitable for machine readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ParseError::SynError(_) => "SynError",
+            ParseError::RustTypeParseError(_) => "RustTypeParseError",
+            ParseError::UnsupportedLanguage(_) => "UnsupportedLanguage",
+            ParseError::UnsupportedType(_) => "UnsupportedType",
+            ParseError::ComplexTupleStruct => "ComplexTupleStruct",
+            ParseError::MultipleUnnamedAssociatedTypes => "MultipleUnnamedAssociatedTypes",
+            ParseError::SerdeTagNotAllowed { .. } => "SerdeTagNotAllowed",
+            ParseError::SerdeContentNotAllowed { .. } => "SerdeContentNotAllowed",
+            ParseError::SerdeTagRequired { .. } => "SerdeTagRequired",
+            ParseError::SerdeContentRequired { .. } => "SerdeContentRequired",
+            ParseError::RustConstExprInvalid => "RustConstExprInvalid",
+            ParseError::RustConstTypeInvalid => "RustConstTypeInvalid",
+            ParseError::SerdeFlattenNotAllowed => "SerdeFlattenNotAllowed",
+            ParseError::IOError(_) => "IOError",
+            ParseError::DuplicateTypeName { .. } => "DuplicateTypeName",
+            ParseError::UnknownAttribute { .. } => "UnknownAttribute",
+            ParseError::MisplacedAttribute { .. } => "MisplacedAttribute",
+            ParseError::MalformedAttribute { .. } => "MalformedAttribute",
+            ParseError::ExcludedTypeReference { .. } => "ExcludedTypeReference",
+        }
+    }
 }
 
 /// Error with it's related data.
@@ -89,10 +142,93 @@ pub enum ParseError {
 pub struct ErrorInfo {
     /// The file name being parsed.
     pub file_name: String,
+    /// The typeshared item that failed to parse.
+    pub item_name: Option<String>,
+    /// Where in the source file the error was triggered.
+    pub location: Option<SourceLocation>,
     /// The parse error.
     pub error: ParseError,
 }
 
+/// A location in a rust source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number, starting at 1.
+    pub column: usize,
+    /// Column one past the last character of the offending source, on the same line.
+    pub end_column: usize,
+    /// The full source line that contains the error.
+    pub source_line: String,
+}
+
+impl SourceLocation {
+    /// Resolve the location of a span in the given source code.
+    pub fn from_span(span: Span, source_code: &str) -> Self {
+        let start = span.start();
+        let end = span.end();
+        let source_line = source_code
+            .lines()
+            .nth(start.line.saturating_sub(1))
+            .unwrap_or_default()
+            .to_string();
+        // Spans covering several lines are highlighted until the end of the first line.
+        let end_column = if end.line == start.line {
+            end.column
+        } else {
+            source_line.chars().count()
+        };
+
+        Self {
+            line: start.line,
+            column: start.column + 1,
+            end_column: end_column.max(start.column + 1) + 1,
+            source_line,
+        }
+    }
+}
+
+/// Where a typeshared item is defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeLocation {
+    /// The source file the item is defined in.
+    pub file_name: String,
+    /// The location of the item's name in the source file.
+    pub location: SourceLocation,
+}
+
+impl Display for TypeLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.file_name, self.location.line, self.location.column
+        )
+    }
+}
+
+/// A parse error along with the span of the source that triggered it.
+#[derive(Debug)]
+pub(crate) struct SpannedError {
+    pub(crate) error: ParseError,
+    pub(crate) span: Span,
+}
+
+/// Attach the span of the offending source to a parse error.
+trait WithSpan<T> {
+    fn with_span(self, span: Span) -> Result<T, SpannedError>;
+}
+
+impl<T, E: Into<ParseError>> WithSpan<T> for Result<T, E> {
+    fn with_span(self, span: Span) -> Result<T, SpannedError> {
+        self.map_err(|error| SpannedError {
+            error: error.into(),
+            span,
+        })
+    }
+}
+
 /// The results of parsing Rust source input.
 #[derive(Default, Debug)]
 pub struct ParsedData {
@@ -106,14 +242,22 @@ pub struct ParsedData {
     pub consts: Vec<RustConst>,
     /// Imports used by this file
     pub import_types: HashSet<ImportedType>,
+    /// Type aliases imported from other crates, for generators that inline aliases.
+    pub foreign_aliases: Vec<RustTypeAlias>,
+    /// Enums imported from other crates, for generators that expand enums where they are used.
+    pub foreign_enums: Vec<RustEnum>,
     /// Crate this belongs to.
     pub crate_name: CrateName,
     /// File name to write to for generated type.
     pub file_name: String,
     /// All type names
     pub type_names: HashSet<String>,
+    /// Where each type name was defined.
+    pub type_locations: HashMap<String, Vec<TypeLocation>>,
     /// Failures during parsing.
     pub errors: Vec<ErrorInfo>,
+    /// Problems that don't stop generation, like unknown typeshare attributes.
+    pub warnings: Vec<ErrorInfo>,
     /// Using multi file support.
     pub multi_file: bool,
 }
@@ -127,8 +271,17 @@ impl AddAssign<ParsedData> for ParsedData {
         self.aliases.append(&mut rhs.aliases);
         self.consts.append(&mut rhs.consts);
         self.import_types.extend(rhs.import_types);
+        self.foreign_aliases.append(&mut rhs.foreign_aliases);
+        self.foreign_enums.append(&mut rhs.foreign_enums);
         self.type_names.extend(rhs.type_names);
+        for (name, mut locations) in rhs.type_locations {
+            self.type_locations
+                .entry(name)
+                .or_default()
+                .append(&mut locations);
+        }
         self.errors.append(&mut rhs.errors);
+        self.warnings.append(&mut rhs.warnings);
 
         self.file_name = rhs.file_name;
         self.crate_name = rhs.crate_name;
@@ -147,25 +300,35 @@ impl ParsedData {
         }
     }
 
-    pub(crate) fn push(&mut self, rust_thing: RustItem) {
-        match rust_thing {
+    pub(crate) fn push(&mut self, rust_thing: RustItem, location: TypeLocation) {
+        let name = match rust_thing {
             RustItem::Struct(s) => {
-                self.type_names.insert(s.id.renamed.clone());
+                let name = s.id.renamed.clone();
                 self.structs.push(s);
+                name
             }
             RustItem::Enum(e) => {
-                self.type_names.insert(e.shared().id.renamed.clone());
+                let name = e.shared().id.renamed.clone();
                 self.enums.push(e);
+                name
             }
             RustItem::Alias(a) => {
-                self.type_names.insert(a.id.renamed.clone());
+                let name = a.id.renamed.clone();
                 self.aliases.push(a);
+                name
             }
             RustItem::Const(c) => {
-                self.type_names.insert(c.id.renamed.clone());
+                let name = c.id.renamed.clone();
                 self.consts.push(c);
+                name
             }
-        }
+        };
+
+        self.type_locations
+            .entry(name.clone())
+            .or_default()
+            .push(location);
+        self.type_names.insert(name);
     }
 
     /// If this file was skipped by the visitor.
@@ -183,12 +346,15 @@ pub fn parse(
     parse_context: &ParseContext,
     parse_file_context: ParseFileContext,
 ) -> Result<Option<ParsedData>, ParseError> {
-    // We will only produce output for files that contain the `#[typeshare]`
-    // attribute, so this is a quick and easy performance win
-    if !parse_file_context.source_code.contains("#[typeshare") {
-        return Ok(None);
-    }
+    parse_borrowed(parse_context, &parse_file_context)
+}
 
+/// Parse the given Rust source string into `ParsedData`, leaving the source with the caller
+/// so that it can still be used afterwards, for example to locate a syntax error.
+pub fn parse_borrowed(
+    parse_context: &ParseContext,
+    parse_file_context: &ParseFileContext,
+) -> Result<Option<ParsedData>, ParseError> {
     let ParseFileContext {
         source_code,
         crate_name,
@@ -196,11 +362,24 @@ pub fn parse(
         file_path,
     } = parse_file_context;
 
+    // We will only produce output for files that contain the `#[typeshare]`
+    // attribute, possibly wrapped in `#[cfg_attr(...)]`, so this is a quick
+    // and easy performance win
+    if !source_code.contains("typeshare") {
+        return Ok(None);
+    }
+
     debug!("parsing {file_path:?}");
     // Parse and process the input, ensuring we parse only items marked with
     // `#[typeshare]`
-    let mut import_visitor = TypeShareVisitor::new(parse_context, crate_name, file_name, file_path);
-    import_visitor.visit_file(&syn::parse_file(&source_code)?);
+    let mut import_visitor = TypeShareVisitor::new(
+        parse_context,
+        crate_name.clone(),
+        file_name.clone(),
+        file_path.clone(),
+        source_code,
+    );
+    import_visitor.visit_file(&syn::parse_file(source_code)?);
 
     Ok(import_visitor.parsed_data())
 }
@@ -210,7 +389,7 @@ pub fn parse(
 ///
 /// This function can currently return something other than a struct, which is a
 /// hack.
-pub(crate) fn parse_struct(s: &ItemStruct, target_os: &[String]) -> Result<RustItem, ParseError> {
+pub(crate) fn parse_struct(s: &ItemStruct, cfg: &CfgOptions) -> Result<RustItem, SpannedError> {
     let serde_rename_all = serde_rename_all(&s.attrs);
 
     let generic_types = s
@@ -229,46 +408,47 @@ pub(crate) fn parse_struct(s: &ItemStruct, target_os: &[String]) -> Result<RustI
     if let Some(ty) = get_serialized_as_type(&s.attrs) {
         return Ok(RustItem::Alias(RustTypeAlias {
             id: get_ident(Some(&s.ident), &s.attrs, &None),
-            r#type: ty.parse()?,
+            r#type: ty
+                .parse()
+                .with_span(serialized_as_span(&s.attrs, s.ident.span()))?,
             comments: parse_comment_attrs(&s.attrs),
             generic_types,
             decorators: get_decorators(&s.attrs),
             is_redacted: is_redacted(&s.attrs),
+            languages: get_language_filter(&s.attrs)?,
         }));
     }
 
     Ok(match &s.fields {
         // Structs
         Fields::Named(f) => {
-            let fields = f
-                .named
-                .iter()
-                .inspect(|field| debug!("\t\tChecking field {:?}", field.ident))
-                .filter(|field| !is_skipped(&field.attrs, target_os))
-                .inspect(|field| debug!("\t\tAccepted field {:?}", field.ident))
-                .map(|f| {
-                    let ty = if let Some(ty) = get_field_type_override(&f.attrs) {
-                        ty.parse()?
-                    } else {
-                        RustType::try_from(&f.ty)?
-                    };
-
-                    if serde_flatten(&f.attrs) {
-                        return Err(ParseError::SerdeFlattenNotAllowed);
-                    }
-
-                    let has_default = serde_default(&f.attrs);
-                    let decorators = get_field_decorators(&f.attrs);
+            let fields =
+                f.named
+                    .iter()
+                    .inspect(|field| debug!("\t\tChecking field {:?}", field.ident))
+                    .filter(|field| !is_skipped(&field.attrs, cfg))
+                    .inspect(|field| debug!("\t\tAccepted field {:?}", field.ident))
+                    .map(|f| {
+                        let ty = parse_field_type(f)?;
+
+                        if serde_flatten(&f.attrs) {
+                            return Err(ParseError::SerdeFlattenNotAllowed)
+                                .with_span(meta_item_span(&f.attrs, "flatten", SERDE, f.span()));
+                        }
 
-                    Ok(RustField {
-                        id: get_ident(f.ident.as_ref(), &f.attrs, &serde_rename_all),
-                        ty,
-                        comments: parse_comment_attrs(&f.attrs),
-                        has_default,
-                        decorators,
+                        let has_default = serde_default(&f.attrs);
+                        let decorators = get_field_decorators(&f.attrs);
+
+                        Ok(RustField {
+                            id: get_ident(f.ident.as_ref(), &f.attrs, &serde_rename_all),
+                            ty,
+                            comments: parse_comment_attrs(&f.attrs),
+                            has_default,
+                            decorators,
+                            languages: get_language_filter(&f.attrs)?,
+                        })
                     })
-                })
-                .collect::<Result<_, ParseError>>()?;
+                    .collect::<Result<_, SpannedError>>()?;
 
             RustItem::Struct(RustStruct {
                 id: get_ident(Some(&s.ident), &s.attrs, &None),
@@ -277,20 +457,17 @@ pub(crate) fn parse_struct(s: &ItemStruct, target_os: &[String]) -> Result<RustI
                 comments: parse_comment_attrs(&s.attrs),
                 decorators: get_decorators(&s.attrs),
                 is_redacted: is_redacted(&s.attrs),
+                languages: get_language_filter(&s.attrs)?,
             })
         }
         // Tuple structs
         Fields::Unnamed(f) => {
             if f.unnamed.len() > 1 {
-                return Err(ParseError::ComplexTupleStruct);
+                return Err(ParseError::ComplexTupleStruct).with_span(f.span());
             }
             let f = &f.unnamed[0];
 
-            let ty = if let Some(ty) = get_field_type_override(&f.attrs) {
-                ty.parse()?
-            } else {
-                RustType::try_from(&f.ty)?
-            };
+            let ty = parse_field_type(f)?;
 
             RustItem::Alias(RustTypeAlias {
                 id: get_ident(Some(&s.ident), &s.attrs, &None),
@@ -299,6 +476,7 @@ pub(crate) fn parse_struct(s: &ItemStruct, target_os: &[String]) -> Result<RustI
                 generic_types,
                 decorators: get_decorators(&s.attrs),
                 is_redacted: is_redacted(&s.attrs),
+                languages: get_language_filter(&s.attrs)?,
             })
         }
         // Unit structs or `None`
@@ -309,6 +487,7 @@ pub(crate) fn parse_struct(s: &ItemStruct, target_os: &[String]) -> Result<RustI
             comments: parse_comment_attrs(&s.attrs),
             decorators: get_decorators(&s.attrs),
             is_redacted: is_redacted(&s.attrs),
+            languages: get_language_filter(&s.attrs)?,
         }),
     })
 }
@@ -318,7 +497,7 @@ pub(crate) fn parse_struct(s: &ItemStruct, target_os: &[String]) -> Result<RustI
 ///
 /// This function can currently return something other than an enum, which is a
 /// hack.
-pub(crate) fn parse_enum(e: &ItemEnum, target_os: &[String]) -> Result<RustItem, ParseError> {
+pub(crate) fn parse_enum(e: &ItemEnum, cfg: &CfgOptions) -> Result<RustItem, SpannedError> {
     let generic_types = e
         .generics
         .params
@@ -336,11 +515,14 @@ pub(crate) fn parse_enum(e: &ItemEnum, target_os: &[String]) -> Result<RustItem,
     if let Some(ty) = get_serialized_as_type(&e.attrs) {
         return Ok(RustItem::Alias(RustTypeAlias {
             id: get_ident(Some(&e.ident), &e.attrs, &None),
-            r#type: ty.parse()?,
+            r#type: ty
+                .parse()
+                .with_span(serialized_as_span(&e.attrs, e.ident.span()))?,
             comments: parse_comment_attrs(&e.attrs),
             generic_types,
             decorators: get_decorators(&e.attrs),
             is_redacted: is_redacted(&e.attrs),
+            languages: get_language_filter(&e.attrs)?,
         }));
     }
 
@@ -356,9 +538,9 @@ pub(crate) fn parse_enum(e: &ItemEnum, target_os: &[String]) -> Result<RustItem,
         .iter()
         .inspect(|v| debug!("\t\tChecking variant {}", v.ident))
         // Filter out variants we've been told to skip
-        .filter(|v| !is_skipped(&v.attrs, target_os))
+        .filter(|v| !is_skipped(&v.attrs, cfg))
         .inspect(|v| debug!("\t\taccepted variant {}", v.ident))
-        .map(|v| parse_enum_variant(v, &serde_rename_all, target_os))
+        .map(|v| parse_enum_variant(v, &serde_rename_all, cfg))
         .collect::<Result<Vec<_>, _>>()?;
 
     // Check if the enum references itself recursively in any of its variants
@@ -378,6 +560,7 @@ pub(crate) fn parse_enum(e: &ItemEnum, target_os: &[String]) -> Result<RustItem,
         generic_types,
         is_recursive,
         is_redacted: is_redacted(&e.attrs),
+        languages: get_language_filter(&e.attrs)?,
     };
 
     // Figure out if we're dealing with a unit enum or an algebraic enum
@@ -390,24 +573,30 @@ pub(crate) fn parse_enum(e: &ItemEnum, target_os: &[String]) -> Result<RustItem,
         if maybe_tag_key.is_some() {
             return Err(ParseError::SerdeTagNotAllowed {
                 enum_ident: original_enum_ident,
-            });
+            })
+            .with_span(meta_item_span(&e.attrs, "tag", SERDE, e.ident.span()));
         }
         if maybe_content_key.is_some() {
             return Err(ParseError::SerdeContentNotAllowed {
                 enum_ident: original_enum_ident,
-            });
+            })
+            .with_span(meta_item_span(&e.attrs, "content", SERDE, e.ident.span()));
         }
 
         Ok(RustItem::Enum(RustEnum::Unit(shared)))
     } else {
         // At least one enum variant is either a tuple or an anonymous struct
 
-        let tag_key = maybe_tag_key.ok_or_else(|| ParseError::SerdeTagRequired {
-            enum_ident: original_enum_ident.clone(),
-        })?;
-        let content_key = maybe_content_key.ok_or_else(|| ParseError::SerdeContentRequired {
-            enum_ident: original_enum_ident.clone(),
-        })?;
+        let tag_key = maybe_tag_key
+            .ok_or_else(|| ParseError::SerdeTagRequired {
+                enum_ident: original_enum_ident.clone(),
+            })
+            .with_span(e.ident.span())?;
+        let content_key = maybe_content_key
+            .ok_or_else(|| ParseError::SerdeContentRequired {
+                enum_ident: original_enum_ident.clone(),
+            })
+            .with_span(e.ident.span())?;
 
         Ok(RustItem::Enum(RustEnum::Algebraic {
             tag_key,
@@ -421,11 +610,13 @@ pub(crate) fn parse_enum(e: &ItemEnum, target_os: &[String]) -> Result<RustItem,
 fn parse_enum_variant(
     v: &syn::Variant,
     enum_serde_rename_all: &Option<String>,
-    target_os: &[String],
-) -> Result<RustEnumVariant, ParseError> {
+    cfg: &CfgOptions,
+) -> Result<RustEnumVariant, SpannedError> {
     let shared = RustEnumVariantShared {
         id: get_ident(Some(&v.ident), &v.attrs, enum_serde_rename_all),
         comments: parse_comment_attrs(&v.attrs),
+        decorators: get_field_decorators(&v.attrs),
+        languages: get_language_filter(&v.attrs)?,
     };
 
     // Get the value of `#[serde(rename_all)]` for this specific variant rather
@@ -439,16 +630,13 @@ fn parse_enum_variant(
         syn::Fields::Unit => Ok(RustEnumVariant::Unit(shared)),
         syn::Fields::Unnamed(associated_type) => {
             if associated_type.unnamed.len() > 1 {
-                return Err(ParseError::MultipleUnnamedAssociatedTypes);
+                return Err(ParseError::MultipleUnnamedAssociatedTypes)
+                    .with_span(associated_type.span());
             }
 
             let first_field = associated_type.unnamed.first().unwrap();
 
-            let ty = if let Some(ty) = get_field_type_override(&first_field.attrs) {
-                ty.parse()?
-            } else {
-                RustType::try_from(&first_field.ty)?
-            };
+            let ty = parse_field_type(first_field)?;
 
             Ok(RustEnumVariant::Tuple { ty, shared })
         }
@@ -456,13 +644,9 @@ fn parse_enum_variant(
             fields: fields_named
                 .named
                 .iter()
-                .filter(|f| !is_skipped(&f.attrs, target_os))
+                .filter(|f| !is_skipped(&f.attrs, cfg))
                 .map(|f| {
-                    let field_type = if let Some(ty) = get_field_type_override(&f.attrs) {
-                        ty.parse()?
-                    } else {
-                        RustType::try_from(&f.ty)?
-                    };
+                    let field_type = parse_field_type(f)?;
 
                     let has_default = serde_default(&f.attrs);
                     let decorators = get_field_decorators(&f.attrs);
@@ -473,9 +657,10 @@ fn parse_enum_variant(
                         comments: parse_comment_attrs(&f.attrs),
                         has_default,
                         decorators,
+                        languages: get_language_filter(&f.attrs)?,
                     })
                 })
-                .collect::<Result<Vec<_>, ParseError>>()?,
+                .collect::<Result<Vec<_>, SpannedError>>()?,
             shared,
         }),
     }
@@ -483,11 +668,12 @@ fn parse_enum_variant(
 
 /// Parses a type alias into a definition that more succinctly represents what
 /// typeshare needs to generate code for other languages.
-pub(crate) fn parse_type_alias(t: &ItemType) -> Result<RustItem, ParseError> {
+pub(crate) fn parse_type_alias(t: &ItemType) -> Result<RustItem, SpannedError> {
     let ty = if let Some(ty) = get_serialized_as_type(&t.attrs) {
-        ty.parse()?
+        ty.parse()
+            .with_span(serialized_as_span(&t.attrs, t.ident.span()))?
     } else {
-        RustType::try_from(t.ty.as_ref())?
+        RustType::try_from(t.ty.as_ref()).with_span(t.ty.span())?
     };
 
     let generic_types = t
@@ -507,36 +693,42 @@ pub(crate) fn parse_type_alias(t: &ItemType) -> Result<RustItem, ParseError> {
         generic_types,
         decorators: get_decorators(&t.attrs),
         is_redacted: is_redacted(&t.attrs),
+        languages: get_language_filter(&t.attrs)?,
     }))
 }
 
 /// Parses a const variant.
-pub(crate) fn parse_const(c: &ItemConst) -> Result<RustItem, ParseError> {
-    let expr = parse_const_expr(&c.expr)?;
+pub(crate) fn parse_const(c: &ItemConst) -> Result<RustItem, SpannedError> {
+    let expr = parse_const_expr(&c.expr).with_span(c.expr.span())?;
 
     // serialized_as needs to be supported in case the user wants to use a different type
     // for the constant variable in a different language
-    let ty = if let Some(ty) = get_serialized_as_type(&c.attrs) {
-        ty.parse()?
+    let (ty, ty_span) = if let Some(ty) = get_serialized_as_type(&c.attrs) {
+        let span = serialized_as_span(&c.attrs, c.ty.span());
+        (ty.parse().with_span(span)?, span)
     } else {
-        RustType::try_from(c.ty.as_ref())?
+        (
+            RustType::try_from(c.ty.as_ref()).with_span(c.ty.span())?,
+            c.ty.span(),
+        )
     };
 
     match &ty {
         RustType::Special(SpecialRustType::HashMap(_, _))
         | RustType::Special(SpecialRustType::Vec(_))
         | RustType::Special(SpecialRustType::Option(_)) => {
-            return Err(ParseError::RustConstTypeInvalid);
+            return Err(ParseError::RustConstTypeInvalid).with_span(ty_span);
         }
         RustType::Special(_) => (),
         RustType::Simple { .. } => (),
-        _ => return Err(ParseError::RustConstTypeInvalid),
+        _ => return Err(ParseError::RustConstTypeInvalid).with_span(ty_span),
     };
 
     Ok(RustItem::Const(RustConst {
         id: get_ident(Some(&c.ident), &c.attrs, &None),
         r#type: ty,
         expr,
+        languages: get_language_filter(&c.attrs)?,
     }))
 }
 
@@ -571,8 +763,31 @@ fn parse_const_expr(e: &Expr) -> Result<RustConstExpr, ParseError> {
         .unwrap_or(Err(ParseError::RustConstTypeInvalid))
 }
 
+/// Parses the type of a field, honoring `#[typeshare(serialized_as = "...")]`.
+fn parse_field_type(f: &syn::Field) -> Result<RustType, SpannedError> {
+    if let Some(ty) = get_field_type_override(&f.attrs) {
+        ty.parse()
+            .with_span(serialized_as_span(&f.attrs, f.ty.span()))
+    } else {
+        RustType::try_from(&f.ty).with_span(f.ty.span())
+    }
+}
+
 // Helpers
 
+/// Span of the `{name}` argument in `#[{ident}(...)]`, or `fallback` if there is none.
+fn meta_item_span(attrs: &[Attribute], name: &str, ident: &str, fallback: Span) -> Span {
+    attrs
+        .iter()
+        .flat_map(|attr| get_meta_items(attr, ident))
+        .find(|meta| meta.path().is_ident(name))
+        .map_or(fallback, |meta| meta.span())
+}
+
+fn serialized_as_span(attrs: &[Attribute], fallback: Span) -> Span {
+    meta_item_span(attrs, "serialized_as", TYPESHARE, fallback)
+}
+
 /// Checks the given attrs for `#[typeshare]`
 pub(crate) fn has_typeshare_annotation(attrs: &[syn::Attribute]) -> bool {
     attrs
@@ -682,14 +897,57 @@ fn parse_comment_attrs(attrs: &[Attribute]) -> Vec<String> {
 }
 
 // `#[typeshare(skip)]` or `#[serde(skip)]`
-fn is_skipped(attrs: &[syn::Attribute], target_os: &[String]) -> bool {
+fn is_skipped(attrs: &[syn::Attribute], cfg: &CfgOptions) -> bool {
     let typeshare_skip = attrs.iter().any(|attr| {
         get_meta_items(attr, SERDE)
             .chain(get_meta_items(attr, TYPESHARE))
             .any(|arg| matches!(arg, Meta::Path(path) if path.is_ident("skip")))
     });
 
-    typeshare_skip || !accept_target_os(attrs, target_os)
+    typeshare_skip || !accept_cfg(attrs, cfg)
+}
+
+/// Reads `#[typeshare(lang = "swift, kotlin")]` and `#[typeshare(skip(typescript))]`.
+fn get_language_filter(attrs: &[Attribute]) -> Result<LanguageFilter, SpannedError> {
+    let mut filter = LanguageFilter::default();
+
+    for meta in attrs
+        .iter()
+        .flat_map(|attr| get_meta_items(attr, TYPESHARE))
+    {
+        match &meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("lang") => {
+                let span = name_value.value.span();
+                let languages = expr_to_string(&name_value.value)
+                    .ok_or_else(|| ParseError::MalformedAttribute {
+                        key: "lang".into(),
+                        expected: "lang = \"...\"".into(),
+                    })
+                    .with_span(span)?
+                    .split(',')
+                    .map(|language| language.trim().parse::<SupportedLanguage>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .with_span(span)?;
+                filter
+                    .include
+                    .get_or_insert_with(HashSet::new)
+                    .extend(languages);
+            }
+            Meta::List(list) if list.path.is_ident("skip") => {
+                let languages = list
+                    .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                    .with_span(list.span())?
+                    .iter()
+                    .map(SupportedLanguage::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .with_span(list.tokens.span())?;
+                filter.exclude.extend(languages);
+            }
+            _ => (),
+        }
+    }
+
+    Ok(filter)
 }
 
 // `#[typeshare(redacted)]`
@@ -715,14 +973,62 @@ fn serde_flatten(attrs: &[syn::Attribute]) -> bool {
     serde_attr(attrs, "flatten")
 }
 
-/// Checks the struct or enum for decorators like `#[typeshare(typescript(readonly)]`
-/// Takes a slice of `syn::Attribute`, returns a `HashMap<language, BTreeSet<decorator>>`, where `language` is `SupportedLanguage`
-/// and `decorator` is `FieldDecorator`. Field decorators are ordered in a `BTreeSet` for consistent code generation.
-fn get_field_decorators(
-    attrs: &[Attribute],
-) -> HashMap<SupportedLanguage, BTreeSet<FieldDecorator>> {
-    let languages: HashSet<SupportedLanguage> = SupportedLanguage::all_languages().collect();
+/// Parses the contents of a field's language list like `typescript(readonly, type = "any")`.
+pub(crate) fn parse_field_decorator_list(list: &MetaList) -> syn::Result<Vec<Meta>> {
+    list.parse_args_with(|input: &ParseBuffer| {
+        let mut res: Vec<Meta> = vec![];
+
+        loop {
+            if input.is_empty() {
+                break;
+            }
+
+            let ident = input.call(Ident::parse_any)?;
+
+            // Parse `readonly` or any other single ident optionally followed by a comma
+            if input.peek(Token![,]) || input.is_empty() {
+                input.parse::<Token![,]>().unwrap_or_default();
+                res.push(Meta::Path(ident.into()));
+                continue;
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            // Parse `= "any | undefined"` or any other eq sign followed by a string literal,
+            // or an integer literal like `tag = 3`
+
+            let eq_token = input.parse::<Token![=]>()?;
+
+            let lit = if input.peek(syn::LitInt) {
+                input.parse::<syn::LitInt>()?.into()
+            } else {
+                input.parse::<LitStr>()?.into()
+            };
+            res.push(Meta::NameValue(MetaNameValue {
+                path: ident.into(),
+                eq_token,
+                value: Expr::Lit(ExprLit {
+                    attrs: Vec::new(),
+                    lit,
+                }),
+            }));
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+        Ok(res)
+    })
+}
 
+/// Checks the field or enum variant for decorators like `#[typeshare(typescript(readonly)]`
+/// Takes a slice of `syn::Attribute`, returns a [`FieldDecoratorMap`] of `SupportedLanguage`
+/// to `FieldDecorator`. Field decorators are ordered in a `BTreeSet` for consistent code generation.
+fn get_field_decorators(attrs: &[Attribute]) -> FieldDecoratorMap {
     attrs
         .iter()
         .flat_map(|attr| get_meta_items(attr, TYPESHARE))
@@ -733,73 +1039,35 @@ fn get_field_decorators(
                 None
             }
         })
-        .filter_map(|list: MetaList| match list.path.get_ident() {
-            Some(ident) if languages.contains(&ident.try_into().unwrap()) => {
-                Some((ident.try_into().unwrap(), list))
-            }
-            _ => None,
+        .filter_map(|list: MetaList| {
+            let language = SupportedLanguage::try_from(list.path.get_ident()?).ok()?;
+            Some((language, list))
         })
         .map(|(language, list): (SupportedLanguage, MetaList)| {
             (
                 language,
-                list.parse_args_with(|input: &ParseBuffer| {
-                    let mut res: Vec<Meta> = vec![];
-
-                    loop {
-                        if input.is_empty() {
-                            break;
-                        }
-
-                        let ident = input.call(Ident::parse_any)?;
-
-                        // Parse `readonly` or any other single ident optionally followed by a comma
-                        if input.peek(Token![,]) || input.is_empty() {
-                            input.parse::<Token![,]>().unwrap_or_default();
-                            res.push(Meta::Path(ident.into()));
-                            continue;
-                        }
-
-                        if input.is_empty() {
-                            break;
+                parse_field_decorator_list(&list)
+                    .iter()
+                    .flatten()
+                    .filter_map(|nested| match nested {
+                        Meta::Path(path) if path.segments.len() == 1 => {
+                            Some(FieldDecorator::Word(path.get_ident()?.to_string()))
                         }
-
-                        // Parse `= "any | undefined"` or any other eq sign followed by a string literal
-
-                        let eq_token = input.parse::<Token![=]>()?;
-
-                        let value: LitStr = input.parse()?;
-                        res.push(Meta::NameValue(MetaNameValue {
-                            path: ident.into(),
-                            eq_token,
-                            value: Expr::Lit(ExprLit {
-                                attrs: Vec::new(),
-                                lit: value.into(),
-                            }),
-                        }));
-
-                        if input.is_empty() {
-                            break;
-                        }
-
-                        input.parse::<Token![,]>()?;
-                    }
-                    Ok(res)
-                })
-                .iter()
-                .flatten()
-                .filter_map(|nested| match nested {
-                    Meta::Path(path) if path.segments.len() == 1 => {
-                        Some(FieldDecorator::Word(path.get_ident()?.to_string()))
-                    }
-                    Meta::NameValue(name_value) => Some(FieldDecorator::NameValue(
-                        name_value.path.get_ident()?.to_string(),
-                        expr_to_string(&name_value.value)?,
-                    )),
-                    // TODO: this should throw a visible error since it suggests a malformed
-                    //       attribute.
-                    _ => None,
-                })
-                .collect::<Vec<FieldDecorator>>(),
+                        Meta::NameValue(name_value) => Some(FieldDecorator::NameValue(
+                            name_value.path.get_ident()?.to_string(),
+                            match &name_value.value {
+                                // Numbers, like `proto(tag = 3)`
+                                Expr::Lit(syn::ExprLit {
+                                    lit: syn::Lit::Int(int),
+                                    ..
+                                }) => int.base10_digits().to_string(),
+                                value => expr_to_string(value)?,
+                            },
+                        )),
+                        // Malformed decorators are reported by `attribute_check`.
+                        _ => None,
+                    })
+                    .collect::<Vec<FieldDecorator>>(),
             )
         })
         .fold(HashMap::new(), |mut acc, (language, decorators)| {
@@ -827,22 +1095,46 @@ fn literal_to_string(lit: &syn::Lit) -> Option<String> {
 fn get_decorators(attrs: &[syn::Attribute]) -> DecoratorMap {
     let mut decorator_map: DecoratorMap = DecoratorMap::new();
 
-    for decorator_kind in [
-        DecoratorKind::Swift,
-        DecoratorKind::SwiftGenericConstraints,
-        DecoratorKind::Kotlin,
-    ] {
+    for decorator_kind in DecoratorKind::all() {
         for value in get_name_value_meta_items(attrs, decorator_kind.as_str(), TYPESHARE) {
-            decorator_map
-                .entry(decorator_kind)
-                .or_default()
-                .extend(value.split(',').map(|s| s.trim().to_string()));
+            let decorators = decorator_map.entry(decorator_kind).or_default();
+            // Keep the order the decorators were written in, e.g. for base classes.
+            for decorator in split_decorators(&value).map(|s| s.trim().to_string()) {
+                if !decorators.contains(&decorator) {
+                    decorators.push(decorator);
+                }
+            }
         }
     }
 
     decorator_map
 }
 
+/// Splits a list of decorators at the commas that are not within brackets or quotes, so that
+/// `python = "Auditable, Mixin(frozen=True, slots=True)"` is two decorators.
+fn split_decorators(value: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[' | '{' | '<') => depth += 1,
+            (None, ')' | ']' | '}' | '>') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(&value[start..]);
+    parts.into_iter()
+}
+
 fn get_tag_key(attrs: &[syn::Attribute]) -> Option<String> {
     get_name_value_meta_items(attrs, "tag", SERDE).next()
 }
@@ -857,6 +1149,23 @@ pub(crate) fn remove_dash_from_identifier(name: &str) -> String {
     name.replace('-', "_")
 }
 
+#[test]
+fn test_split_decorators() {
+    assert_eq!(
+        split_decorators("Auditable, frozen=True").collect::<Vec<_>>(),
+        ["Auditable", " frozen=True"]
+    );
+    assert_eq!(
+        split_decorators("Mixin(frozen=True, slots=True), Generic[A, B], title='a, b'")
+            .collect::<Vec<_>>(),
+        [
+            "Mixin(frozen=True, slots=True)",
+            " Generic[A, B]",
+            " title='a, b'"
+        ]
+    );
+}
+
 #[test]
 fn test_rename_all_to_case() {
     let test_word = "test_case";
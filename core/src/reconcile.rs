@@ -6,7 +6,7 @@
 use crate::{
     language::CrateName,
     parser::ParsedData,
-    rust_types::{RustEnum, RustEnumVariant, RustType, SpecialRustType},
+    rust_types::{RustEnum, RustEnumVariant, RustType, RustTypeAlias, SpecialRustType},
     visitors::ImportedType,
 };
 use log::{debug, info};
@@ -67,6 +67,84 @@ pub fn reconcile_aliases(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>
     }
 }
 
+/// Copy the aliases and enums each crate imports from other crates into its `foreign_aliases`
+/// and `foreign_enums`.
+///
+/// Generators that inline aliases or expand enums where they are used can't import them from
+/// the file of another crate, so they need their definitions. Imports that are not found in
+/// their crate fall back to the first other crate defining the type, like re-exports do when
+/// generating imports.
+pub fn reconcile_foreign_types(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>) {
+    let mut foreign_types = BTreeMap::new();
+
+    for (crate_name, parsed_data) in crate_parsed_data.iter() {
+        let mut aliases = Vec::<RustTypeAlias>::new();
+        let mut enums = Vec::<RustEnum>::new();
+
+        for import in parsed_data
+            .import_types
+            .iter()
+            .filter(|import| &import.base_crate != crate_name)
+        {
+            let is_imported = |original: &str, renamed: &str| {
+                import.type_name == "*"
+                    || import.type_name == original
+                    || import.type_name == renamed
+            };
+            let defined_in = |data: &ParsedData| {
+                data.aliases
+                    .iter()
+                    .any(|a| is_imported(&a.id.original, &a.id.renamed))
+                    || data
+                        .enums
+                        .iter()
+                        .any(|e| is_imported(&e.shared().id.original, &e.shared().id.renamed))
+            };
+            let Some(defining) = crate_parsed_data
+                .get(&import.base_crate)
+                .filter(|data| defined_in(data))
+                .or_else(|| {
+                    crate_parsed_data
+                        .iter()
+                        .filter(|(name, _)| *name != crate_name)
+                        .map(|(_, data)| data)
+                        .find(|data| defined_in(data))
+                })
+            else {
+                continue;
+            };
+
+            aliases.extend(
+                defining
+                    .aliases
+                    .iter()
+                    .filter(|a| is_imported(&a.id.original, &a.id.renamed))
+                    .cloned(),
+            );
+            enums.extend(
+                defining
+                    .enums
+                    .iter()
+                    .filter(|e| is_imported(&e.shared().id.original, &e.shared().id.renamed))
+                    .cloned(),
+            );
+        }
+
+        aliases.sort();
+        aliases.dedup_by(|a, b| a.id == b.id);
+        enums.sort();
+        enums.dedup_by(|a, b| a.shared().id == b.shared().id);
+        foreign_types.insert(crate_name.clone(), (aliases, enums));
+    }
+
+    for (crate_name, (aliases, enums)) in foreign_types {
+        if let Some(parsed_data) = crate_parsed_data.get_mut(&crate_name) {
+            parsed_data.foreign_aliases = aliases;
+            parsed_data.foreign_enums = enums;
+        }
+    }
+}
+
 /// Traverse all the parsed typeshare data and collect all types that have been renamed
 /// via `serde(rename)` into a mapping of original name to renamed name.
 fn collect_serde_renames(crate_parsed_data: &BTreeMap<CrateName, ParsedData>) -> RenamedTypes {
@@ -183,3 +261,87 @@ fn resolve_renamed(
         .or_else(|| name_map.get(crate_name))
         .map(ToOwned::to_owned)
 }
+
+#[cfg(test)]
+mod test {
+    use super::reconcile_foreign_types;
+    use crate::{
+        context::{ParseContext, ParseFileContext},
+        language::CrateName,
+        parser::{parse, ParsedData},
+    };
+    use std::collections::BTreeMap;
+
+    fn parse_crate(crate_name: &str, source_code: &str) -> (CrateName, ParsedData) {
+        let parsed_data = parse(
+            &ParseContext {
+                multi_file: true,
+                ..Default::default()
+            },
+            ParseFileContext {
+                source_code: source_code.into(),
+                crate_name: crate_name.into(),
+                file_name: format!("{crate_name}.ts"),
+                file_path: format!("{crate_name}/src/lib.rs").into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        (crate_name.into(), parsed_data)
+    }
+
+    #[test]
+    fn test_foreign_types() {
+        let mut crate_parsed_data = BTreeMap::from([
+            parse_crate(
+                "a",
+                r#"
+                #[typeshare]
+                pub type Id = String;
+
+                #[typeshare]
+                pub type Unused = String;
+
+                #[typeshare]
+                pub enum Kind { A, B }
+                "#,
+            ),
+            parse_crate(
+                "b",
+                r#"
+                #[typeshare]
+                pub type Label = String;
+                "#,
+            ),
+            parse_crate(
+                "c",
+                r#"
+                use a::{Id, Kind};
+                // Re-exported by `a`, but defined in `b`.
+                use a::Label;
+
+                #[typeshare]
+                pub struct Item { id: Id, kind: Kind, label: Label }
+                "#,
+            ),
+        ]);
+        reconcile_foreign_types(&mut crate_parsed_data);
+
+        let c = &crate_parsed_data[&CrateName::from("c")];
+        let aliases = c
+            .foreign_aliases
+            .iter()
+            .map(|a| a.id.original.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(aliases, ["Id", "Label"]);
+        let enums = c
+            .foreign_enums
+            .iter()
+            .map(|e| e.shared().id.original.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(enums, ["Kind"]);
+        assert!(crate_parsed_data[&CrateName::from("a")]
+            .foreign_aliases
+            .is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use quote::ToTokens;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Display;
 use std::str::FromStr;
 use std::{collections::HashMap, convert::TryFrom};
@@ -11,7 +11,7 @@ use crate::parser::DecoratorKind;
 use crate::visitors::accept_type;
 
 /// Type level typeshare attributes are mapped by target language and a mapping of attribute.
-pub type DecoratorMap = HashMap<DecoratorKind, BTreeSet<String>>;
+pub type DecoratorMap = HashMap<DecoratorKind, Vec<String>>;
 
 /// Identifier used in Rust structs, enums, and fields. It includes the `original` name and the `renamed` value after the transformation based on `serde` attributes.
 #[derive(Debug, Clone, PartialEq)]
@@ -53,6 +53,8 @@ pub struct RustStruct {
     pub decorators: DecoratorMap,
     /// True if this struct contains data that needs to be redacted
     pub is_redacted: bool,
+    /// Languages this struct is generated for.
+    pub languages: LanguageFilter,
 }
 
 impl PartialEq for RustStruct {
@@ -89,6 +91,8 @@ pub struct RustConst {
     pub r#type: RustType,
     /// The expression that the constant contains.
     pub expr: RustConstExpr,
+    /// Languages this constant is generated for.
+    pub languages: LanguageFilter,
 }
 
 impl PartialEq for RustConst {
@@ -123,6 +127,8 @@ pub struct RustTypeAlias {
     pub decorators: DecoratorMap,
     /// True if this type alias contains data that needs to be redacted
     pub is_redacted: bool,
+    /// Languages this type alias is generated for.
+    pub languages: LanguageFilter,
 }
 
 impl PartialEq for RustTypeAlias {
@@ -160,7 +166,29 @@ pub struct RustField {
     pub has_default: bool,
     /// Language-specific decorators assigned to a given field.
     /// The keys are language names (e.g. SupportedLanguage::TypeScript), the values are field decorators (e.g. readonly)
-    pub decorators: HashMap<SupportedLanguage, BTreeSet<FieldDecorator>>,
+    pub decorators: FieldDecoratorMap,
+    /// Languages this field is generated for.
+    pub languages: LanguageFilter,
+}
+
+/// The languages an item, field or variant is generated for, set with
+/// `#[typeshare(lang = "swift, kotlin")]` and `#[typeshare(skip(typescript))]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageFilter {
+    /// Only generate for these languages, if present.
+    pub include: Option<HashSet<SupportedLanguage>>,
+    /// Never generate for these languages.
+    pub exclude: HashSet<SupportedLanguage>,
+}
+
+impl LanguageFilter {
+    /// Returns true if code should be generated for the given language.
+    pub fn includes(&self, language: SupportedLanguage) -> bool {
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.contains(&language))
+            && !self.exclude.contains(&language)
+    }
 }
 
 /// A single decorator on a field in Rust code.
@@ -498,6 +526,33 @@ impl RustType {
         }
         .filter(|s| accept_type(s))
     }
+
+    /// Replace the generic parameters `parameters`, e.g. of a type alias, with `arguments`.
+    pub(crate) fn substitute(&self, parameters: &[String], arguments: &[RustType]) -> RustType {
+        let sub = |ty: &RustType| Box::new(ty.substitute(parameters, arguments));
+        match self {
+            RustType::Simple { id } => parameters
+                .iter()
+                .position(|p| p == id)
+                .and_then(|i| arguments.get(i))
+                .cloned()
+                .unwrap_or_else(|| self.clone()),
+            RustType::Generic { id, parameters: p } => RustType::Generic {
+                id: id.clone(),
+                parameters: p.iter().map(|p| *sub(p)).collect(),
+            },
+            RustType::Special(special) => RustType::Special(match special {
+                SpecialRustType::Vec(rtype) => SpecialRustType::Vec(sub(rtype)),
+                SpecialRustType::Array(rtype, len) => SpecialRustType::Array(sub(rtype), *len),
+                SpecialRustType::Slice(rtype) => SpecialRustType::Slice(sub(rtype)),
+                SpecialRustType::Option(rtype) => SpecialRustType::Option(sub(rtype)),
+                SpecialRustType::HashMap(rtype1, rtype2) => {
+                    SpecialRustType::HashMap(sub(rtype1), sub(rtype2))
+                }
+                other => other.clone(),
+            }),
+        }
+    }
 }
 
 struct RustRefTypeIter<'a> {
@@ -523,16 +578,35 @@ impl<'a> Iterator for RustRefTypeIter<'a> {
     }
 }
 
+/// Language-specific decorators assigned to a field or enum variant.
+pub type FieldDecoratorMap = HashMap<SupportedLanguage, BTreeSet<FieldDecorator>>;
+
+/// Find the value of a `name = "value"` decorator for the given language.
+fn decorator_value<'a>(
+    decorators: &'a FieldDecoratorMap,
+    language: SupportedLanguage,
+    key: &str,
+) -> Option<&'a str> {
+    decorators.get(&language)?.iter().find_map(|fd| match fd {
+        FieldDecorator::NameValue(name, value) if name == key => Some(value.as_str()),
+        _ => None,
+    })
+}
+
 impl RustField {
     /// Returns an type override, if it exists, on this field for a given language.
     pub fn type_override(&self, language: SupportedLanguage) -> Option<&str> {
-        self.decorators
-            .get(&language)?
-            .iter()
-            .find_map(|fd| match fd {
-                FieldDecorator::NameValue(name, ty) if name == "type" => Some(ty.as_str()),
-                _ => None,
-            })
+        decorator_value(&self.decorators, language, "type")
+    }
+
+    /// Returns a name override, if it exists, on this field for a given language.
+    pub fn name_override(&self, language: SupportedLanguage) -> Option<&str> {
+        decorator_value(&self.decorators, language, "name")
+    }
+
+    /// Returns a default value override, if it exists, on this field for a given language.
+    pub fn default_override(&self, language: SupportedLanguage) -> Option<&str> {
+        decorator_value(&self.decorators, language, "default")
     }
 }
 
@@ -541,10 +615,20 @@ impl RustField {
 pub enum RustTypeFormatError {
     #[error("Generic parameter `{0}` is forbidden in Go")]
     GenericsForbiddenInGo(String),
+    #[error("Generic parameter `{0}` is forbidden in Protocol Buffers")]
+    GenericsForbiddenInProto(String),
+    #[error("Generic parameter `{0}` is forbidden in GraphQL")]
+    GenericsForbiddenInGraphQL(String),
+    #[error("`{0}` is not a GraphQL input type, so it can't be used in one")]
+    OutputTypeInGraphQLInput(String),
+    #[error("Generic parameter `{0}` is forbidden in Avro")]
+    GenericsForbiddenInAvro(String),
     #[error("Generic type `{0}` cannot be used as a map key in Typescript")]
     GenericKeyForbiddenInTS(String),
     #[error("The special type `{0}` is not supported in this language")]
     UnsupportedSpecialType(String),
+    #[error("The type mapping `{0}` is not valid JSON: {1}")]
+    InvalidJsonMapping(String, String),
 }
 
 impl SpecialRustType {
@@ -708,6 +792,13 @@ impl RustEnum {
             Self::Unit(shared) | Self::Algebraic { shared, .. } => shared,
         }
     }
+
+    /// Get a mutable reference to the inner shared content
+    pub fn shared_mut(&mut self) -> &mut RustEnumShared {
+        match self {
+            Self::Unit(shared) | Self::Algebraic { shared, .. } => shared,
+        }
+    }
 }
 
 /// Enum information shared among different enum types
@@ -730,6 +821,8 @@ pub struct RustEnumShared {
     pub is_recursive: bool,
     /// True if this enum contains data that needs to be redacted
     pub is_redacted: bool,
+    /// Languages this enum is generated for.
+    pub languages: LanguageFilter,
 }
 
 /// Parsed information about a Rust enum variant
@@ -771,6 +864,17 @@ pub struct RustEnumVariantShared {
     pub id: Id,
     /// Comments applied to the variant
     pub comments: Vec<String>,
+    /// Language-specific decorators assigned to the variant, e.g. `swift(name = "kind")`.
+    pub decorators: FieldDecoratorMap,
+    /// Languages this variant is generated for.
+    pub languages: LanguageFilter,
+}
+
+impl RustEnumVariantShared {
+    /// Returns a name override, if it exists, on this variant for a given language.
+    pub fn name_override(&self, language: SupportedLanguage) -> Option<&str> {
+        decorator_value(&self.decorators, language, "name")
+    }
 }
 
 /// An enum that encapsulates units of code generation for Typeshare.
@@ -787,3 +891,15 @@ pub enum RustItem {
     /// A `const` definition
     Const(RustConst),
 }
+
+impl RustItem {
+    /// Languages this item is generated for.
+    pub fn languages(&self) -> &LanguageFilter {
+        match self {
+            RustItem::Struct(s) => &s.languages,
+            RustItem::Enum(e) => &e.shared().languages,
+            RustItem::Alias(a) => &a.languages,
+            RustItem::Const(c) => &c.languages,
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! Validation passes that run after all source files have been parsed.
+//!
+//! In single file mode every crate is merged into one output unit, and in multi file
+//! mode every module of a crate is collapsed into one output file. Two typeshared items
+//! with the same renamed identifier would end up as conflicting declarations in the
+//! generated code, so we report them as errors.
+//!
+//! Items can also be restricted to some languages with `#[typeshare(lang = "...")]` or
+//! `#[typeshare(skip(...))]`, and a generated type must not reference a type that is left
+//! out for the same language. Types with the same name can be defined in several crates, so
+//! references are resolved to the crate of the type they refer to first.
+use crate::{
+    language::{CrateName, SupportedLanguage},
+    parser::{ErrorInfo, ParseError, ParsedData, TypeLocation},
+    rust_types::{Id, LanguageFilter, RustEnumVariant, RustType},
+    visitors::ImportedType,
+};
+use log::debug;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Check every output unit for duplicate type names and record an error for each
+/// conflicting definition.
+pub fn check_duplicate_types(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>) {
+    for (crate_name, parsed_data) in crate_parsed_data {
+        // Files are walked in parallel so sort for deterministic reporting.
+        let duplicates = parsed_data
+            .type_locations
+            .iter()
+            .filter(|(_, locations)| locations.len() > 1)
+            .map(|(name, locations)| {
+                let mut locations = locations.clone();
+                locations.sort_by_key(location_key);
+                (name.clone(), locations)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        for (name, locations) in duplicates {
+            debug!("{crate_name} has duplicate type {name}: {locations:?}");
+            let (first, rest) = locations.split_first().expect("at least two locations");
+
+            parsed_data
+                .errors
+                .extend(rest.iter().map(|duplicate| ErrorInfo {
+                    file_name: duplicate.file_name.clone(),
+                    item_name: Some(name.clone()),
+                    location: Some(duplicate.location.clone()),
+                    error: ParseError::DuplicateTypeName {
+                        name: name.clone(),
+                        first_defined: first.clone(),
+                    },
+                }));
+        }
+    }
+}
+
+/// Record an error for every type generated for `language` that references a type
+/// which is not generated for `language`.
+pub fn check_language_references(
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    language: SupportedLanguage,
+) {
+    let excluded = crate_parsed_data
+        .iter()
+        .flat_map(|(crate_name, parsed_data)| {
+            type_names(parsed_data)
+                .filter(|(_, languages)| !languages.includes(language))
+                .map(move |(name, _)| (crate_name.clone(), name.to_owned()))
+        })
+        .collect::<HashSet<_>>();
+
+    if excluded.is_empty() {
+        return;
+    }
+
+    let mut defining_crates = HashMap::<String, BTreeSet<CrateName>>::new();
+    for (crate_name, parsed_data) in crate_parsed_data.iter() {
+        for (name, _) in type_names(parsed_data) {
+            defining_crates
+                .entry(name.to_owned())
+                .or_default()
+                .insert(crate_name.clone());
+        }
+    }
+
+    for (crate_name, parsed_data) in crate_parsed_data.iter_mut() {
+        let is_excluded = |name: &str| {
+            referenced_crate(
+                crate_name,
+                &parsed_data.import_types,
+                &defining_crates,
+                name,
+            )
+            .is_some_and(|defining| excluded.contains(&(defining.clone(), name.to_owned())))
+        };
+        let references = type_references(parsed_data, language)
+            .into_iter()
+            .flat_map(|(id, ty)| {
+                ty.all_reference_type_names()
+                    .filter(|name| is_excluded(name))
+                    .map(move |name| (id.original.as_str(), id.renamed.as_str(), name))
+            })
+            .collect::<BTreeSet<_>>();
+
+        let errors = references
+            .into_iter()
+            .map(|(name, renamed, referenced)| {
+                debug!("{name} references excluded type {referenced}");
+                let defined = parsed_data
+                    .type_locations
+                    .get(renamed)
+                    .and_then(|locations| locations.iter().min_by_key(|l| location_key(l)));
+                ErrorInfo {
+                    file_name: defined.map_or_else(
+                        || parsed_data.file_name.clone(),
+                        |defined| defined.file_name.clone(),
+                    ),
+                    item_name: Some(name.to_string()),
+                    location: defined.map(|defined| defined.location.clone()),
+                    error: ParseError::ExcludedTypeReference {
+                        name: name.to_string(),
+                        referenced: referenced.to_string(),
+                        language,
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+
+        parsed_data.errors.extend(errors);
+    }
+}
+
+/// Files are walked in parallel, so definitions are ordered by where they are.
+fn location_key(location: &TypeLocation) -> (String, usize, usize) {
+    (
+        location.file_name.clone(),
+        location.location.line,
+        location.location.column,
+    )
+}
+
+/// Names of the types defined in a crate along with the languages they are generated for.
+/// References use the Rust name of a type until its serde rename is reconciled, so both
+/// are included.
+fn type_names(parsed_data: &ParsedData) -> impl Iterator<Item = (&str, &LanguageFilter)> {
+    parsed_data
+        .structs
+        .iter()
+        .map(|s| (&s.id, &s.languages))
+        .chain(
+            parsed_data
+                .enums
+                .iter()
+                .map(|e| (&e.shared().id, &e.shared().languages)),
+        )
+        .chain(parsed_data.aliases.iter().map(|a| (&a.id, &a.languages)))
+        .flat_map(|(id, languages)| {
+            [
+                (id.original.as_str(), languages),
+                (id.renamed.as_str(), languages),
+            ]
+        })
+}
+
+/// The crate defining a type referenced from `crate_name`: the crate itself if it defines
+/// the type, or else the crate it is imported from. Imports of re-exported types fall back
+/// to another crate defining the type.
+fn referenced_crate<'a>(
+    crate_name: &'a CrateName,
+    import_types: &HashSet<ImportedType>,
+    defining_crates: &'a HashMap<String, BTreeSet<CrateName>>,
+    name: &str,
+) -> Option<&'a CrateName> {
+    let defining = defining_crates.get(name)?;
+    if defining.contains(crate_name) {
+        return Some(crate_name);
+    }
+
+    let mut imported_from = import_types
+        .iter()
+        .filter(|import| import.type_name == name || import.type_name == "*")
+        .map(|import| &import.base_crate)
+        .peekable();
+    imported_from.peek()?;
+    imported_from
+        .find_map(|base_crate| defining.get(base_crate))
+        .or_else(|| defining.iter().next())
+}
+
+/// Every type referenced by the fields and variants generated for `language`, along with
+/// the item referencing it.
+fn type_references(parsed_data: &ParsedData, language: SupportedLanguage) -> Vec<(&Id, &RustType)> {
+    let mut references = Vec::new();
+
+    for s in parsed_data
+        .structs
+        .iter()
+        .filter(|s| s.languages.includes(language))
+    {
+        references.extend(
+            s.fields
+                .iter()
+                .filter(|f| f.languages.includes(language))
+                .map(|f| (&s.id, &f.ty)),
+        );
+    }
+
+    for e in parsed_data
+        .enums
+        .iter()
+        .map(|e| e.shared())
+        .filter(|e| e.languages.includes(language))
+    {
+        for variant in e
+            .variants
+            .iter()
+            .filter(|v| v.shared().languages.includes(language))
+        {
+            match variant {
+                RustEnumVariant::Unit(_) => (),
+                RustEnumVariant::Tuple { ty, .. } => references.push((&e.id, ty)),
+                RustEnumVariant::AnonymousStruct { fields, .. } => references.extend(
+                    fields
+                        .iter()
+                        .filter(|f| f.languages.includes(language))
+                        .map(|f| (&e.id, &f.ty)),
+                ),
+            }
+        }
+    }
+
+    references.extend(
+        parsed_data
+            .aliases
+            .iter()
+            .filter(|a| a.languages.includes(language))
+            .map(|a| (&a.id, &a.r#type)),
+    );
+
+    references
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_duplicate_types, check_language_references};
+    use crate::{
+        context::{ParseContext, ParseFileContext},
+        language::{CrateName, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
+        parser::{parse, ParseError, ParsedData},
+    };
+    use cool_asserts::assert_matches;
+    use std::collections::BTreeMap;
+
+    fn parse_file(source_code: &str, file_path: &str) -> ParsedData {
+        parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source_code.into(),
+                crate_name: SINGLE_FILE_CRATE_NAME,
+                file_name: "file_name".into(),
+                file_path: file_path.into(),
+            },
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    fn parse_crate(source_code: &str, crate_name: &str) -> (CrateName, ParsedData) {
+        let parsed_data = parse(
+            &ParseContext {
+                multi_file: true,
+                ..Default::default()
+            },
+            ParseFileContext {
+                source_code: source_code.into(),
+                crate_name: crate_name.into(),
+                file_name: "lib.rs".into(),
+                file_path: format!("{crate_name}/src/lib.rs").into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        (crate_name.into(), parsed_data)
+    }
+
+    #[test]
+    fn test_duplicate_across_files() {
+        let mut parsed_data = parse_file(
+            "#[typeshare] pub struct Config { a: String }",
+            "crate_b/src/lib.rs",
+        );
+        parsed_data += parse_file(
+            r#"
+            #[typeshare]
+            #[serde(rename = "Config")]
+            pub enum Settings { A, B }
+
+            #[typeshare]
+            pub struct Other;
+            "#,
+            "crate_a/src/lib.rs",
+        );
+
+        let mut crate_parsed_data = BTreeMap::from([(SINGLE_FILE_CRATE_NAME, parsed_data)]);
+        check_duplicate_types(&mut crate_parsed_data);
+
+        assert_matches!(
+            crate_parsed_data[&SINGLE_FILE_CRATE_NAME].errors.as_slice(),
+            [error] => {
+                assert_eq!(error.file_name, "crate_b/src/lib.rs");
+                assert_matches!(
+                    &error.location,
+                    Some(location) => assert_eq!((location.line, location.column), (1, 25))
+                );
+                assert_matches!(
+                    &error.error,
+                    ParseError::DuplicateTypeName { name, first_defined } => {
+                        assert_eq!(name, "Config");
+                        assert_eq!(first_defined.file_name, "crate_a/src/lib.rs");
+                        assert_eq!(
+                            (first_defined.location.line, first_defined.location.column),
+                            (4, 22)
+                        );
+                        assert_eq!(first_defined.to_string(), "crate_a/src/lib.rs:4:22");
+                    }
+                );
+            }
+        );
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        let mut parsed_data = parse_file("#[typeshare] pub struct A;", "a.rs");
+        parsed_data += parse_file("#[typeshare] pub struct B;", "b.rs");
+
+        let mut crate_parsed_data = BTreeMap::from([(SINGLE_FILE_CRATE_NAME, parsed_data)]);
+        check_duplicate_types(&mut crate_parsed_data);
+
+        assert!(crate_parsed_data[&SINGLE_FILE_CRATE_NAME].errors.is_empty());
+    }
+
+    #[test]
+    fn test_excluded_type_reference() {
+        let parsed_data = parse_file(
+            r#"
+            #[typeshare(lang = "swift, kotlin")]
+            pub struct MobileOnly;
+
+            #[typeshare]
+            pub struct Shared {
+                #[typeshare(skip(typescript))]
+                mobile: MobileOnly,
+            }
+
+            #[typeshare]
+            pub struct Broken {
+                mobile: Vec<MobileOnly>,
+            }
+            "#,
+            "a.rs",
+        );
+
+        let mut crate_parsed_data = BTreeMap::from([(SINGLE_FILE_CRATE_NAME, parsed_data)]);
+        check_language_references(&mut crate_parsed_data, SupportedLanguage::Swift);
+        assert!(crate_parsed_data[&SINGLE_FILE_CRATE_NAME].errors.is_empty());
+
+        check_language_references(&mut crate_parsed_data, SupportedLanguage::TypeScript);
+        assert_matches!(
+            crate_parsed_data[&SINGLE_FILE_CRATE_NAME].errors.as_slice(),
+            [error] => {
+                assert_eq!(error.file_name, "a.rs");
+                assert_matches!(
+                    &error.error,
+                    ParseError::ExcludedTypeReference { name, referenced, language } => {
+                        assert_eq!(name, "Broken");
+                        assert_eq!(referenced, "MobileOnly");
+                        assert_eq!(*language, SupportedLanguage::TypeScript);
+                    }
+                );
+            }
+        );
+    }
+
+    #[test]
+    fn test_excluded_type_reference_across_crates() {
+        let mut crate_parsed_data = BTreeMap::from([
+            parse_crate(
+                r#"
+                #[typeshare(lang = "swift")]
+                pub struct Shared;
+                "#,
+                "crate_a",
+            ),
+            parse_crate(
+                r#"
+                #[typeshare]
+                pub struct Shared;
+
+                #[typeshare]
+                pub struct User {
+                    shared: Shared,
+                }
+                "#,
+                "crate_b",
+            ),
+            parse_crate(
+                r#"
+                use crate_a::Shared;
+
+                #[typeshare]
+                pub struct Team {
+                    shared: Shared,
+                }
+                "#,
+                "crate_c",
+            ),
+        ]);
+        check_language_references(&mut crate_parsed_data, SupportedLanguage::TypeScript);
+
+        assert!(crate_parsed_data[&CrateName::from("crate_b")]
+            .errors
+            .is_empty());
+        assert_matches!(
+            crate_parsed_data[&CrateName::from("crate_c")].errors.as_slice(),
+            [error] => assert_matches!(
+                &error.error,
+                ParseError::ExcludedTypeReference { name, referenced, .. } => {
+                    assert_eq!(name, "Team");
+                    assert_eq!(referenced, "Shared");
+                }
+            )
+        );
+    }
+}
@@ -1,16 +1,22 @@
 //! Visitors to collect various items from the AST.
 use crate::{
+    attribute_check::{check_const, check_enum, check_struct, check_type_alias},
+    cfg::{accept_cfg, ExpandCfgAttrs},
     context::ParseContext,
     language::CrateName,
     parser::{
         has_typeshare_annotation, parse_const, parse_enum, parse_struct, parse_type_alias,
-        ErrorInfo, ParseError, ParsedData,
+        ErrorInfo, ParsedData, SourceLocation, SpannedError, TypeLocation,
     },
-    rust_types::{RustEnumVariant, RustItem},
-    target_os_check::accept_target_os,
+    rust_types::{RustEnumVariant, RustItem, RustType, SpecialRustType},
 };
 use log::debug;
-use std::{collections::HashSet, ops::Not, path::PathBuf};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ops::Not,
+    path::PathBuf,
+};
 use syn::{visit::Visit, Attribute, ItemUse, UseTree};
 
 /// List of some popular crate names that we can ignore
@@ -50,7 +56,11 @@ const IGNORED_TYPES: &[&str] = &["Option", "String", "Vec", "HashMap", "T", "I54
 pub struct TypeShareVisitor<'a> {
     parsed_data: ParsedData,
     file_path: PathBuf,
+    source_code: &'a str,
     parse_context: &'a ParseContext<'a>,
+    /// Imports are needed to reconcile types across crates in multi file mode, and otherwise
+    /// only to resolve type mapping keys that are paths.
+    collect_imports: bool,
 }
 
 impl<'a> TypeShareVisitor<'a> {
@@ -60,17 +70,30 @@ impl<'a> TypeShareVisitor<'a> {
         crate_name: CrateName,
         file_name: String,
         file_path: PathBuf,
+        source_code: &'a str,
     ) -> Self {
         Self {
             parsed_data: ParsedData::new(crate_name, file_name, parse_context.multi_file),
             file_path,
+            source_code,
             parse_context,
+            collect_imports: parse_context.multi_file
+                || parse_context
+                    .type_mapping_keys
+                    .iter()
+                    .any(|key| key.contains("::")),
         }
     }
 
     #[inline]
     /// Consume the visitor and return parsed data.
-    pub fn parsed_data(self) -> Option<ParsedData> {
+    pub fn parsed_data(mut self) -> Option<ParsedData> {
+        if self.collect_imports {
+            self.qualify_mapped_types();
+            if !self.parsed_data.multi_file {
+                self.parsed_data.import_types.clear();
+            }
+        }
         self.parsed_data.is_empty().not().then(|| {
             if self.parsed_data.multi_file {
                 let mut s = self;
@@ -83,16 +106,43 @@ impl<'a> TypeShareVisitor<'a> {
     }
 
     #[inline]
-    fn collect_result(&mut self, result: Result<RustItem, ParseError>) {
+    fn collect_result(&mut self, result: Result<RustItem, SpannedError>, item: &syn::Ident) {
         match result {
-            Ok(data) => self.parsed_data.push(data),
-            Err(error) => self.parsed_data.errors.push(ErrorInfo {
+            Ok(data) => self.parsed_data.push(
+                data,
+                TypeLocation {
+                    file_name: self.file_path.to_string_lossy().into_owned(),
+                    location: SourceLocation::from_span(item.span(), self.source_code),
+                },
+            ),
+            Err(SpannedError { error, span }) => self.parsed_data.errors.push(ErrorInfo {
                 file_name: self.file_path.to_string_lossy().into_owned(),
+                item_name: Some(item.to_string()),
+                location: Some(SourceLocation::from_span(span, self.source_code)),
                 error,
             }),
         }
     }
 
+    /// Record problems with the typeshare attributes of an item, as errors in strict mode
+    /// and warnings otherwise.
+    fn collect_attribute_problems(&mut self, problems: Vec<SpannedError>, item: &syn::Ident) {
+        let problems = problems
+            .into_iter()
+            .map(|SpannedError { error, span }| ErrorInfo {
+                file_name: self.file_path.to_string_lossy().into_owned(),
+                item_name: Some(item.to_string()),
+                location: Some(SourceLocation::from_span(span, self.source_code)),
+                error,
+            });
+
+        if self.parse_context.strict {
+            self.parsed_data.errors.extend(problems);
+        } else {
+            self.parsed_data.warnings.extend(problems);
+        }
+    }
+
     /// After collecting all imports we now want to retain only those
     /// that are referenced by the typeshared types.
     fn reconcile_referenced_types(&mut self) {
@@ -193,11 +243,88 @@ impl<'a> TypeShareVisitor<'a> {
         self.parsed_data.import_types = diff;
     }
 
-    /// Is this type annotated with at `#[cfg(target_os = "target")]` that does
-    /// not match `--target-os` argument?
+    /// Rename references to types whose full path is a type mapping key, such as `uuid::Uuid`,
+    /// to that path so the mapping applies to them. Paths are resolved against the imports and
+    /// types of this file; names that resolve to more than one mapped path keep their bare name.
+    fn qualify_mapped_types(&mut self) {
+        let mut paths = HashMap::<&str, HashSet<&str>>::new();
+        for key in &self.parse_context.type_mapping_keys {
+            let path = key
+                .split_once('<')
+                .map_or(key.as_str(), |(path, _)| path)
+                .trim();
+            let Some((root, rest)) = path.split_once("::") else {
+                continue;
+            };
+            let name = rest.rsplit_once("::").map_or(rest, |(_, name)| name);
+            let base_crate = match root {
+                "crate" | "super" | "self" => self.parsed_data.crate_name.clone(),
+                root => CrateName::from(root.to_owned()),
+            };
+            let imported = self
+                .parsed_data
+                .import_types
+                .iter()
+                .any(|imp| imp.base_crate == base_crate && imp.type_name == name);
+            let local = base_crate == self.parsed_data.crate_name
+                && self.parsed_data.type_names.contains(name);
+            if imported || local {
+                paths.entry(name).or_default().insert(path);
+            }
+        }
+
+        let qualified = paths
+            .into_iter()
+            .filter(|(_, paths)| paths.len() == 1)
+            .flat_map(|(name, paths)| paths.into_iter().map(move |path| (name, path)))
+            .collect::<HashMap<_, _>>();
+        if qualified.is_empty() {
+            return;
+        }
+
+        let data = &mut self.parsed_data;
+        for field in data.structs.iter_mut().flat_map(|s| s.fields.iter_mut()) {
+            qualify_type(&mut field.ty, &qualified);
+        }
+        for variant in data
+            .enums
+            .iter_mut()
+            .flat_map(|e| e.shared_mut().variants.iter_mut())
+        {
+            match variant {
+                RustEnumVariant::Unit(_) => (),
+                RustEnumVariant::Tuple { ty, .. } => qualify_type(ty, &qualified),
+                RustEnumVariant::AnonymousStruct { fields, .. } => {
+                    for field in fields {
+                        qualify_type(&mut field.ty, &qualified);
+                    }
+                }
+            }
+        }
+        for alias in &mut data.aliases {
+            qualify_type(&mut alias.r#type, &qualified);
+        }
+        for c in &mut data.consts {
+            qualify_type(&mut c.r#type, &qualified);
+        }
+    }
+
+    /// Expand an item's `cfg_attr` attributes against the active cfg options. The item is
+    /// only cloned if it has any.
+    fn expand_cfg_attrs<'i, T: Clone + ExpandCfgAttrs>(&self, item: &'i T) -> Cow<'i, T> {
+        if !item.has_cfg_attrs() {
+            return Cow::Borrowed(item);
+        }
+        let mut item = item.clone();
+        item.expand_cfg_attrs(&self.parse_context.cfg);
+        Cow::Owned(item)
+    }
+
+    /// Is this type annotated with a `#[cfg(...)]` predicate that does
+    /// not match the active cfg options?
     #[inline(always)]
-    fn target_os_accepted(&self, attrs: &[Attribute]) -> bool {
-        accept_target_os(attrs, &self.parse_context.target_os)
+    fn cfg_accepted(&self, attrs: &[Attribute]) -> bool {
+        accept_cfg(attrs, &self.parse_context.cfg)
     }
 }
 
@@ -205,7 +332,7 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Find any reference types that are not part of
     /// the `use` import statements.
     fn visit_path(&mut self, p: &'ast syn::Path) {
-        if !self.parsed_data.multi_file {
+        if !self.collect_imports {
             return;
         }
         let extract_root_and_types = |p: &syn::Path| {
@@ -258,7 +385,7 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
 
     /// Collect referenced imports.
     fn visit_item_use(&mut self, i: &'ast ItemUse) {
-        if !self.parsed_data.multi_file {
+        if !self.collect_imports {
             return;
         }
         self.parsed_data
@@ -275,9 +402,11 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Collect rust structs.
     fn visit_item_struct(&mut self, i: &'ast syn::ItemStruct) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_struct(i, &self.parse_context.target_os));
+        let item = self.expand_cfg_attrs(i);
+        if has_typeshare_annotation(&item.attrs) && self.cfg_accepted(&item.attrs) {
+            debug!("\tParsing {}", item.ident);
+            self.collect_attribute_problems(check_struct(&item), &item.ident);
+            self.collect_result(parse_struct(&item, &self.parse_context.cfg), &item.ident);
         }
 
         syn::visit::visit_item_struct(self, i);
@@ -286,9 +415,11 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Collect rust enums.
     fn visit_item_enum(&mut self, i: &'ast syn::ItemEnum) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_enum(i, &self.parse_context.target_os));
+        let item = self.expand_cfg_attrs(i);
+        if has_typeshare_annotation(&item.attrs) && self.cfg_accepted(&item.attrs) {
+            debug!("\tParsing {}", item.ident);
+            self.collect_attribute_problems(check_enum(&item), &item.ident);
+            self.collect_result(parse_enum(&item, &self.parse_context.cfg), &item.ident);
         }
 
         syn::visit::visit_item_enum(self, i);
@@ -297,9 +428,11 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Collect rust type aliases.
     fn visit_item_type(&mut self, i: &'ast syn::ItemType) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_type_alias(i));
+        let item = self.expand_cfg_attrs(i);
+        if has_typeshare_annotation(&item.attrs) && self.cfg_accepted(&item.attrs) {
+            debug!("\tParsing {}", item.ident);
+            self.collect_attribute_problems(check_type_alias(&item), &item.ident);
+            self.collect_result(parse_type_alias(&item), &item.ident);
         }
 
         syn::visit::visit_item_type(self, i);
@@ -308,9 +441,11 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     // Collect rust consts.
     fn visit_item_const(&mut self, i: &'ast syn::ItemConst) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_const(i));
+        let item = self.expand_cfg_attrs(i);
+        if has_typeshare_annotation(&item.attrs) && self.cfg_accepted(&item.attrs) {
+            debug!("\tParsing {}", item.ident);
+            self.collect_attribute_problems(check_const(&item), &item.ident);
+            self.collect_result(parse_const(&item), &item.ident);
         }
 
         syn::visit::visit_item_const(self, i);
@@ -328,7 +463,7 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     // }
 
     fn visit_file(&mut self, i: &'ast syn::File) {
-        if self.target_os_accepted(&i.attrs) {
+        if self.cfg_accepted(&i.attrs) {
             syn::visit::visit_file(self, i);
         }
     }
@@ -444,6 +579,33 @@ impl Iterator for ItemUseIter<'_> {
     }
 }
 
+/// Rename a type reference, and any references among its parameters, to its mapped path.
+fn qualify_type(ty: &mut RustType, qualified: &HashMap<&str, &str>) {
+    match ty {
+        RustType::Simple { id } | RustType::Generic { id, .. } => {
+            if let Some(path) = qualified.get(id.as_str()) {
+                *id = path.to_string();
+            }
+            if let RustType::Generic { parameters, .. } = ty {
+                for ty in parameters {
+                    qualify_type(ty, qualified);
+                }
+            }
+        }
+        RustType::Special(special) => match special {
+            SpecialRustType::Vec(ty)
+            | SpecialRustType::Array(ty, _)
+            | SpecialRustType::Slice(ty)
+            | SpecialRustType::Option(ty) => qualify_type(ty, qualified),
+            SpecialRustType::HashMap(key, value) => {
+                qualify_type(key, qualified);
+                qualify_type(value, qualified);
+            }
+            _ => (),
+        },
+    }
+}
+
 fn parse_import<'a>(
     item_use: &'a ItemUse,
     crate_name: &'a CrateName,
@@ -454,7 +616,11 @@ fn parse_import<'a>(
 #[cfg(test)]
 mod test {
     use super::{parse_import, TypeShareVisitor};
-    use crate::{context::ParseContext, visitors::ImportedType};
+    use crate::{
+        context::ParseContext,
+        rust_types::{RustType, SpecialRustType},
+        visitors::ImportedType,
+    };
     use cool_asserts::assert_matches;
     use itertools::Itertools;
     use syn::{visit::Visit, File};
@@ -628,7 +794,9 @@ mod test {
         let parse_context = ParseContext {
             ignored_types: Vec::new(),
             multi_file: true,
-            target_os: Vec::new(),
+            cfg: Default::default(),
+            strict: false,
+            type_mapping_keys: Vec::new(),
         };
 
         let file: File = syn::parse_str(rust_code).unwrap();
@@ -637,6 +805,7 @@ mod test {
             "my_crate".into(),
             "my_file".into(),
             "file_path".into(),
+            rust_code,
         );
         visitor.visit_file(&file);
 
@@ -698,4 +867,74 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_qualify_mapped_types() {
+        let parse_context = ParseContext {
+            type_mapping_keys: vec![
+                "accounts::Id".to_string(),
+                "devices::Id".to_string(),
+                "uuid::Uuid".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let field_types = |rust_code: &str| {
+            let file: File = syn::parse_str(rust_code).unwrap();
+            let mut visitor = TypeShareVisitor::new(
+                &parse_context,
+                "my_crate".into(),
+                "my_file".into(),
+                "file_path".into(),
+                rust_code,
+            );
+            visitor.visit_file(&file);
+            visitor.parsed_data().unwrap().structs[0]
+                .fields
+                .iter()
+                .map(|f| f.ty.clone())
+                .collect_vec()
+        };
+
+        assert_eq!(
+            field_types(
+                "
+                use accounts::Id;
+
+                #[typeshare]
+                struct Account {
+                    id: Id,
+                    sessions: Vec<uuid::Uuid>,
+                }
+                "
+            ),
+            [
+                RustType::Simple {
+                    id: "accounts::Id".into()
+                },
+                RustType::Special(SpecialRustType::Vec(Box::new(RustType::Simple {
+                    id: "uuid::Uuid".into()
+                }))),
+            ]
+        );
+        assert_eq!(
+            field_types(
+                "
+                use devices::Id;
+
+                #[typeshare]
+                struct Device {
+                    id: Id,
+                    uuid: Uuid,
+                }
+                "
+            ),
+            [
+                RustType::Simple {
+                    id: "devices::Id".into()
+                },
+                RustType::Simple { id: "Uuid".into() },
+            ]
+        );
+    }
 }
@@ -157,3 +157,68 @@ mod serde_attributes_on_enums {
         ));
     }
 }
+
+mod error_locations {
+    use super::*;
+    use typeshare_core::parser::{ErrorInfo, SourceLocation};
+
+    fn parse_errors(source: &str) -> Vec<ErrorInfo> {
+        parser::parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source.to_string(),
+                crate_name: "default_name".into(),
+                file_name: "file_name".into(),
+                file_path: "file_path".into(),
+            },
+        )
+        .unwrap()
+        .unwrap()
+        .errors
+    }
+
+    #[test]
+    fn unsupported_field_type() {
+        let source = r##"#[typeshare]
+pub struct Foo {
+    pub bar: Vec<i64>,
+}
+"##;
+
+        let errors = parse_errors(source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].item_name.as_deref(), Some("Foo"));
+        assert_eq!(
+            errors[0].location,
+            Some(SourceLocation {
+                line: 3,
+                column: 14,
+                end_column: 22,
+                source_line: "    pub bar: Vec<i64>,".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn serde_attribute_on_enum() {
+        let source = r##"#[typeshare]
+#[serde(tag = "bla")]
+pub enum Foo {
+    Variant1,
+}
+"##;
+
+        let errors = parse_errors(source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].item_name.as_deref(), Some("Foo"));
+        assert_eq!(
+            errors[0].location,
+            Some(SourceLocation {
+                line: 2,
+                column: 9,
+                end_column: 20,
+                source_line: r#"#[serde(tag = "bla")]"#.into(),
+            })
+        );
+    }
+}
@@ -10,6 +10,7 @@ use std::{
     sync::Once,
 };
 use typeshare_core::{
+    cfg::CfgOptions,
     context::{ParseContext, ParseFileContext},
     language::{CrateName, Language},
     reconcile::reconcile_aliases,
@@ -101,7 +102,13 @@ fn check(
 
     let mut typeshare_output: Vec<u8> = Vec::new();
     let parse_context = ParseContext {
-        target_os: target_os.iter().map(ToString::to_string).collect(),
+        cfg: CfgOptions::from_target_os(
+            &target_os
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+        ),
+        type_mapping_keys: lang.type_map().keys().cloned().collect(),
         ..Default::default()
     };
 
@@ -148,12 +155,41 @@ macro_rules! output_file_for_ident {
     (typescript) => {
         "output.ts"
     };
+    (zod) => {
+        "output.zod.ts"
+    };
+    (graphql) => {
+        "output.graphql"
+    };
+    (avro) => {
+        "output.avsc"
+    };
     (go) => {
         "output.go"
     };
     (python) => {
         "output.py"
     };
+    (json_schema) => {
+        "output.json"
+    };
+    (openapi) => {
+        "output.yaml"
+    };
+    (proto) => {
+        "output.proto"
+    };
+    (java) => {
+        "output.java"
+    };
+
+    (csharp) => {
+        "output.cs"
+    };
+
+    (dart) => {
+        "output.dart"
+    };
 }
 
 /// Simplifies the construction of `Language` instances for each language.
@@ -222,6 +258,145 @@ macro_rules! language_instance {
         })
     };
 
+    // Default JSON Schema
+    (json_schema) => {
+        language_instance!(json_schema { })
+    };
+
+    // JSON Schema with configuration fields forwarded
+    (json_schema {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::JsonSchema {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default OpenAPI
+    (openapi) => {
+        language_instance!(openapi { })
+    };
+
+    // OpenAPI with configuration fields forwarded
+    (openapi {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::OpenApi {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default proto
+    (proto) => {
+        language_instance!(proto { })
+    };
+
+    // proto with configuration fields forwarded
+    (proto {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::Proto {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default Java
+    (java) => {
+        language_instance!(java {
+            package: "com.agilebits.onepassword".to_string(),
+            class_name: "Accounts".to_string(),
+        })
+    };
+
+    // Java with configuration fields forwarded
+    (java {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::Java {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default C#
+    (csharp) => {
+        language_instance!(csharp {
+            namespace: "AgileBits.OnePassword".to_string(),
+        })
+    };
+
+    // C# with configuration fields forwarded
+    (csharp {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::CSharp {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default zod
+    (zod) => {
+        language_instance!(zod { })
+    };
+
+    // zod with configuration fields forwarded
+    (zod {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::Zod {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default Dart
+    (dart) => {
+        language_instance!(dart { })
+    };
+
+    // Dart with configuration fields forwarded
+    (dart {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::Dart {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default Avro
+    (avro) => {
+        language_instance!(avro { })
+    };
+
+    // Avro with configuration fields forwarded
+    (avro {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::Avro {
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
+    // Default GraphQL
+    (graphql) => {
+        language_instance!(graphql { })
+    };
+
+    // GraphQL with configuration fields forwarded
+    (graphql {$($field:ident: $val:expr),* $(,)?}) => {
+        #[allow(clippy::needless_update)]
+        Box::new(typeshare_core::language::GraphQL {
+            no_version_header: true,
+            $($field: $val,)*
+            ..Default::default()
+        })
+    };
+
     // Default scala
     (scala) => {
         language_instance!(scala {
@@ -434,6 +609,13 @@ static TYPESCRIPT_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
     .collect()
 });
 
+static ZOD_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
+    [("Url", "z.string().url()")]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+});
+
 static GO_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
     [
         ("Url", "string"),
@@ -445,6 +627,52 @@ static GO_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
     .collect()
 });
 
+static GENERIC_TYPESCRIPT_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
+    [
+        ("NonEmpty<T>", "Array<{0}>"),
+        ("Id<T>", "string"),
+        ("Pair<A, B>", "[{0}, {1}]"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+});
+
+static GENERIC_KOTLIN_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
+    [
+        ("NonEmpty<T>", "List<{0}>"),
+        ("Id<T>", "String"),
+        ("Pair<A, B>", "Pair<{0}, {1}>"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+});
+
+static QUALIFIED_TYPESCRIPT_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
+    [
+        ("uuid::Uuid", "string"),
+        ("accounts::Id", "AccountId"),
+        ("devices::Id", "DeviceId"),
+        ("Url", "string"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+});
+
+static QUALIFIED_GO_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
+    [
+        ("uuid::Uuid", "uuid.UUID"),
+        ("accounts::Id", "int64"),
+        ("devices::Id", "string"),
+        ("Url", "string"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+});
+
 static PYTHON_MAPPINGS: Lazy<HashMap<String, String>> = Lazy::new(|| {
     [
         ("Url", "AnyUrl"),
@@ -556,7 +784,7 @@ tests! {
         go,
         python
     ];
-    can_override_types: [swift, kotlin, scala, typescript, go];
+    can_override_types: [swift, kotlin, scala, typescript, go, python];
 
     /// Structs
     can_generate_simple_struct_with_a_comment: [kotlin, swift, typescript, scala, go, python];
@@ -689,6 +917,33 @@ tests! {
     excluded_by_target_os: [ swift, kotlin, scala, typescript, go,python ] target_os: ["android", "macos"];
     // excluded_by_target_os_full_module: [swift] target_os: "ios";
     serde_rename_references: [ swift, kotlin, scala, typescript, go ];
+    can_handle_cfg_attr: [ swift, kotlin, scala, typescript, go, python ];
+    can_filter_languages: [ swift, kotlin, scala, typescript, go, python ];
+    can_override_names_per_language: [ swift, kotlin, go ];
+    can_generate_language_decorators: [ typescript, python, go, scala ];
+    can_map_generic_types: [
+        typescript {
+            type_mappings: super::GENERIC_TYPESCRIPT_MAPPINGS.clone(),
+        },
+        kotlin {
+            package: "com.agilebits.onepassword".to_string(),
+            module_name: String::new(),
+            type_mappings: super::GENERIC_KOTLIN_MAPPINGS.clone(),
+        },
+    ];
+    can_map_qualified_types: [
+        typescript {
+            type_mappings: super::QUALIFIED_TYPESCRIPT_MAPPINGS.clone(),
+        },
+        go {
+            type_mappings: super::QUALIFIED_GO_MAPPINGS.clone(),
+        },
+    ];
+    can_customize_go_struct_tags: [
+        go {
+            tag_template: Some(r#"db:"{field}" yaml:"{name}""#.to_string()),
+        },
+    ];
     test_custom_serialize_deserialize_functions: [    go
     {
         type_mappings: super::GO_MAPPINGS.clone(),
@@ -703,4 +958,21 @@ tests! {
     }
     ];
     no_mangle: [swift, kotlin, scala, typescript, go];
+    can_generate_json_schema: [json_schema, openapi];
+    can_generate_proto: [proto { package: "accounts.v1".to_string() }];
+    can_generate_java_records: [java];
+    can_generate_csharp_records: [csharp];
+    can_generate_dart_classes: [dart];
+    can_generate_graphql_schema: [graphql];
+    can_generate_avro_schema: [avro { namespace: "com.example.events".to_string() }];
+    can_generate_typescript_type_guards: [typescript { type_guards: true }];
+    can_generate_zod_schemas: [
+        typescript {
+            type_mappings: super::TYPESCRIPT_MAPPINGS.clone(),
+        },
+        zod {
+            type_mappings: super::TYPESCRIPT_MAPPINGS.clone(),
+            zod_type_mappings: super::ZOD_MAPPINGS.clone(),
+        },
+    ];
 }